@@ -1,5 +1,15 @@
 use rustc_version::version;
-use std::{error::Error, fs::write};
+use std::{env, error::Error, fs::write};
+
+// Features that enable a TLS implementation in reqwest, and with it custom root certificates.
+const TLS_FEATURES: [&str; 6] = [
+    "DEFAULT_TLS",
+    "NATIVE_TLS",
+    "NATIVE_TLS_VENDORED",
+    "RUSTLS_TLS",
+    "RUSTLS_TLS_MANUAL_ROOTS",
+    "RUSTLS_TLS_WEBPKI_ROOTS",
+];
 
 fn main() -> Result<(), Box<dyn Error>> {
     write(
@@ -11,5 +21,27 @@ fn main() -> Result<(), Box<dyn Error>> {
         ),
     )?;
 
+    // reqwest uses the Fetch API on wasm32, where neither TLS settings nor Tokio are available.
+    let wasm = env::var("CARGO_CFG_TARGET_ARCH").map_or(false, |arch| arch == "wasm32");
+
+    println!("cargo:rustc-check-cfg=cfg(tls)");
+    if !wasm
+        && TLS_FEATURES
+            .iter()
+            .any(|feature| env::var_os(format!("CARGO_FEATURE_{feature}")).is_some())
+    {
+        println!("cargo:rustc-cfg=tls");
+    }
+
+    println!("cargo:rustc-check-cfg=cfg(tokio)");
+    if !wasm && env::var_os("CARGO_FEATURE_TOKIO").is_some() {
+        println!("cargo:rustc-cfg=tokio");
+    }
+
+    println!("cargo:rustc-check-cfg=cfg(async_io)");
+    if !wasm && env::var_os("CARGO_FEATURE_ASYNC_IO").is_some() {
+        println!("cargo:rustc-cfg=async_io");
+    }
+
     Ok(())
 }
@@ -0,0 +1,237 @@
+//! Access tokens for bearer authentication that expire and need to be refreshed.
+//!
+//! A `TokenProvider` set on the `Configuration` is asked for a token before every request, and
+//! takes precedence over the other authentication modes. If the server rejects a token with
+//! `401 Unauthorized`, the provider is told to discard it and the request is sent once more with a
+//! new token. `OAuth2TokenProvider` implements the client credentials flow of the Infobip API.
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use reqwest::header::USER_AGENT;
+use reqwest::StatusCode;
+use serde_derive::Deserialize;
+
+use crate::api::timer::Instant;
+use crate::api::transport::{default_transport, Transport};
+use crate::api::{user_agent, SdkError, REQUEST_FACTORY};
+use crate::configuration::{Configuration, SecretString};
+
+/// Path of the endpoint that issues access tokens.
+pub const PATH_TOKEN: &str = "/auth/1/oauth2/token";
+
+/// Future returned by `TokenProvider::token`.
+#[cfg(not(target_arch = "wasm32"))]
+pub type TokenFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<SecretString, SdkError>> + Send + 'a>>;
+
+/// Future returned by `TokenProvider::token`. It is not `Send` on `wasm32`, like
+/// `TransportFuture`.
+#[cfg(target_arch = "wasm32")]
+pub type TokenFuture<'a> = Pin<Box<dyn Future<Output = Result<SecretString, SdkError>> + 'a>>;
+
+/// Provides the access token sent as bearer authentication with every request.
+///
+/// # Example
+/// ```
+/// # use infobip_sdk::api::auth::{TokenFuture, TokenProvider};
+/// #[derive(Debug)]
+/// struct EnvTokenProvider;
+///
+/// impl TokenProvider for EnvTokenProvider {
+///     fn token(&self) -> TokenFuture<'_> {
+///         Box::pin(async { Ok(std::env::var("IB_ACCESS_TOKEN").unwrap_or_default().into()) })
+///     }
+/// }
+/// ```
+pub trait TokenProvider: fmt::Debug + Send + Sync {
+    /// Returns a valid access token, obtaining a new one if needed.
+    fn token(&self) -> TokenFuture<'_>;
+
+    /// Discards the given token after the server rejected it, so that the next call to `token()`
+    /// obtains a new one. Does nothing by default.
+    fn invalidate(&self, _token: &str) {}
+}
+
+/// Obtains access tokens from the Infobip API with the OAuth2 client credentials flow.
+///
+/// Tokens are cached and refreshed ahead of expiry, one refresh at a time, so concurrent requests
+/// share the same token.
+///
+/// # Example
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use infobip_sdk::api::auth::OAuth2TokenProvider;
+/// # use infobip_sdk::configuration::Configuration;
+/// let mut configuration = Configuration::builder("https://some.api.infobip.com")
+///     .build()
+///     .unwrap();
+/// let token_provider =
+///     OAuth2TokenProvider::new(&configuration, "some-client-id", "some-client-secret");
+/// configuration.set_token_provider(Arc::new(token_provider));
+/// ```
+#[derive(Debug)]
+pub struct OAuth2TokenProvider {
+    token_url: String,
+    client_id: String,
+    client_secret: SecretString,
+    refresh_margin: Duration,
+    transport: Arc<dyn Transport>,
+    cache: Mutex<Option<CachedToken>>,
+    // Held while a new token is fetched, so that concurrent requests wait for it.
+    refresh: futures_util::lock::Mutex<()>,
+}
+
+#[derive(Debug)]
+struct CachedToken {
+    value: SecretString,
+    refresh_at: Option<Instant>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: SecretString,
+    expires_in: Option<u64>,
+}
+
+// Error response of the token endpoint, as defined by RFC 6749.
+#[derive(Deserialize)]
+struct TokenErrorResponse {
+    error: String,
+    error_description: Option<String>,
+}
+
+// Describes a failed token request with the OAuth 2.0 error of the response, or its raw body.
+fn token_error_message(status: StatusCode, text: &str) -> String {
+    let details = match serde_json::from_str::<TokenErrorResponse>(text) {
+        Ok(TokenErrorResponse {
+            error,
+            error_description: Some(description),
+        }) => format!("{}: {}", error, description),
+        Ok(TokenErrorResponse { error, .. }) => error,
+        Err(_) => text.trim().to_string(),
+    };
+
+    if details.is_empty() {
+        format!("token endpoint returned {}", status)
+    } else {
+        format!("token endpoint returned {}: {}", status, details)
+    }
+}
+
+impl OAuth2TokenProvider {
+    /// Creates a new `OAuth2TokenProvider` that obtains tokens from the token endpoint of the
+    /// base URL of the configuration, refreshing them a minute before they expire. Token requests
+    /// go through the proxy, timeouts and root certificates of the configuration.
+    pub fn new(
+        configuration: &Configuration,
+        client_id: impl Into<String>,
+        client_secret: impl Into<SecretString>,
+    ) -> OAuth2TokenProvider {
+        OAuth2TokenProvider {
+            token_url: format!(
+                "{}{}",
+                configuration.base_url().trim_end_matches('/'),
+                PATH_TOKEN
+            ),
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            refresh_margin: Duration::from_secs(60),
+            transport: default_transport(configuration),
+            cache: Mutex::new(None),
+            refresh: futures_util::lock::Mutex::new(()),
+        }
+    }
+
+    /// Sets how long before expiry a token is refreshed. Tokens that live shorter than twice the
+    /// margin are refreshed halfway through their lifetime instead.
+    pub fn with_refresh_margin(mut self, refresh_margin: Duration) -> Self {
+        self.refresh_margin = refresh_margin;
+        self
+    }
+
+    /// Sends token requests through the given transport instead of the one of the configuration.
+    pub fn with_transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    // Returns the cached token, unless it is missing or due for a refresh.
+    fn cached(&self) -> Option<SecretString> {
+        self.cache
+            .lock()
+            .unwrap()
+            .as_ref()
+            .filter(|token| token.refresh_at.map_or(true, |at| Instant::now() < at))
+            .map(|token| token.value.clone())
+    }
+
+    async fn fetch(&self) -> Result<CachedToken, SdkError> {
+        let request = REQUEST_FACTORY
+            .post(&self.token_url)
+            .header(USER_AGENT, user_agent())
+            .form(&[
+                ("client_id", self.client_id.as_str()),
+                ("client_secret", self.client_secret.expose_secret()),
+                ("grant_type", "client_credentials"),
+            ])
+            .build()?;
+        let requested_at = Instant::now();
+
+        let response = self.transport.execute(request).await?;
+        let status = response.status();
+        let text = response.text().await?;
+
+        if !status.is_success() {
+            return Err(SdkError::Token(token_error_message(status, &text)));
+        }
+
+        let token: TokenResponse = serde_json::from_str(&text)?;
+        // A lifetime too long to represent is treated like no expiry.
+        let refresh_at = token.expires_in.and_then(|expires_in| {
+            let lifetime = Duration::from_secs(expires_in);
+            requested_at.checked_add(lifetime - self.refresh_margin.min(lifetime / 2))
+        });
+
+        Ok(CachedToken {
+            value: token.access_token,
+            refresh_at,
+        })
+    }
+}
+
+impl TokenProvider for OAuth2TokenProvider {
+    fn token(&self) -> TokenFuture<'_> {
+        Box::pin(async move {
+            if let Some(token) = self.cached() {
+                return Ok(token);
+            }
+
+            let _refresh = self.refresh.lock().await;
+            // Another request may have fetched a token while this one was waiting.
+            if let Some(token) = self.cached() {
+                return Ok(token);
+            }
+
+            let token = self.fetch().await?;
+            let value = token.value.clone();
+            *self.cache.lock().unwrap() = Some(token);
+
+            Ok(value)
+        })
+    }
+
+    fn invalidate(&self, token: &str) {
+        let mut cache = self.cache.lock().unwrap();
+
+        // Keeps a token that was already refreshed by another request.
+        if cache
+            .as_ref()
+            .map_or(false, |cached| cached.value.expose_secret() == token)
+        {
+            *cache = None;
+        }
+    }
+}
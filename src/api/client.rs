@@ -0,0 +1,118 @@
+//! Module with the client that gives access to every enabled channel.
+
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+
+use crate::api::raw::{Raw, RawRequest};
+use crate::api::transport::{default_transport, Transport};
+use crate::api::{execute_endpoint, SdkError, SdkResponse};
+use crate::configuration::Configuration;
+
+#[cfg(feature = "email")]
+use crate::api::email::EmailClient;
+
+#[cfg(feature = "sms")]
+use crate::api::sms::SmsClient;
+
+#[cfg(feature = "whatsapp")]
+use crate::api::whatsapp::WhatsAppClient;
+
+/// Main asynchronous client for all Infobip channels.
+///
+/// Holds one configuration and one transport, so every channel client it returns shares the same
+/// connection pool, rate limiter and middleware. Channel clients are cheap to create, so they can
+/// be requested for every call. Only the channels enabled as cargo features are available.
+///
+/// # Example
+/// ```no_run
+/// # use infobip_sdk::api::client::InfobipClient;
+/// # use infobip_sdk::configuration::Configuration;
+/// # use infobip_sdk::model::sms::PreviewRequestBody;
+/// # use reqwest::StatusCode;
+/// #
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = InfobipClient::with_configuration(Configuration::from_env_api_key()?);
+///
+/// let response = client.sms().preview(PreviewRequestBody::new("Hello!")).await?;
+///
+/// assert_eq!(response.status, StatusCode::OK);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct InfobipClient {
+    configuration: Configuration,
+    transport: Arc<dyn Transport>,
+}
+
+impl InfobipClient {
+    /// Builds and returns a new `InfobipClient` with a specified configuration.
+    pub fn with_configuration(configuration: Configuration) -> Self {
+        let transport = default_transport(&configuration);
+        InfobipClient::with_transport(configuration, transport)
+    }
+
+    /// Builds and returns a new `InfobipClient` with a specified configuration, sending requests
+    /// through a custom transport.
+    pub fn with_transport(configuration: Configuration, transport: Arc<dyn Transport>) -> Self {
+        InfobipClient {
+            configuration,
+            transport,
+        }
+    }
+
+    /// Returns the configuration shared by all channel clients.
+    pub fn configuration(&self) -> &Configuration {
+        &self.configuration
+    }
+
+    /// Returns the transport shared by all channel clients.
+    pub fn transport(&self) -> &Arc<dyn Transport> {
+        &self.transport
+    }
+
+    /// Sends a request to any path of the API, for endpoints the SDK does not model. Available
+    /// without any channel feature. See `api::raw`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "InfobipClient::raw",
+            skip_all,
+            fields(
+                path = request.path(),
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
+    pub async fn raw<T: DeserializeOwned>(
+        &self,
+        request: RawRequest,
+    ) -> Result<SdkResponse<T>, SdkError> {
+        execute_endpoint(
+            self.transport.as_ref(),
+            &self.configuration,
+            &Raw::new(request),
+        )
+        .await
+    }
+
+    /// Returns a client for the SMS channel.
+    #[cfg(feature = "sms")]
+    pub fn sms(&self) -> SmsClient {
+        SmsClient::with_transport(self.configuration.clone(), self.transport.clone())
+    }
+
+    /// Returns a client for the Email channel.
+    #[cfg(feature = "email")]
+    pub fn email(&self) -> EmailClient {
+        EmailClient::with_transport(self.configuration.clone(), self.transport.clone())
+    }
+
+    /// Returns a client for the WhatsApp channel.
+    #[cfg(feature = "whatsapp")]
+    pub fn whatsapp(&self) -> WhatsAppClient {
+        WhatsAppClient::with_transport(self.configuration.clone(), self.transport.clone())
+    }
+}
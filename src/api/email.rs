@@ -1,29 +1,45 @@
 //! Module with client and endpoint functions for the Email channel.
 
-use std::collections::HashMap;
 use std::io;
+use std::sync::Arc;
 
+use futures_util::Stream;
 use reqwest::multipart::Form;
 use reqwest::multipart::Part;
-use tokio::io::AsyncReadExt;
-use validator::Validate;
-
-use crate::api::{
-    build_api_error, send_multipart_request, send_no_body_request, send_valid_json_request,
-    SdkError, SdkResponse,
-};
-use crate::configuration::Configuration;
+use serde::de::DeserializeOwned;
+
+use crate::api::email::endpoints::*;
+use crate::api::endpoint::Endpoint;
+use crate::api::idempotency::{self, generate_id};
+use crate::api::pages::{self, Page, Window};
+use crate::api::raw::{Raw, RawRequest};
+use crate::api::transport::{default_transport, Transport};
+use crate::api::{execute_endpoint, SdkError, SdkResponse};
+use crate::configuration::{Configuration, RetryPolicy};
 use crate::model::email::{
-    AddDomainRequestBody, AddDomainResponseBody, BulksQueryParameters, BulksResponseBody,
-    DeliveryReportsQueryParameters, DeliveryReportsResponseBody, DomainResponseBody,
-    DomainsQueryParameters, DomainsResponseBody, LogsQueryParameters, LogsResponseBody,
-    RescheduleQueryParameters, RescheduleRequestBody, RescheduleResponseBody,
-    ScheduledStatusQueryParameters, ScheduledStatusResponseBody, SendRequestBody, SendResponseBody,
-    UpdateScheduledStatusQueryParameters, UpdateScheduledStatusRequestBody,
-    UpdateScheduledStatusResponseBody, UpdateTrackingRequestBody, UpdateTrackingResponseBody,
-    ValidateAddressRequestBody, ValidateAddressResponseBody,
+    AddDomainRequestBody, AddDomainResponseBody, Attachment, BulksQueryParameters,
+    BulksResponseBody, DeliveryReportsQueryParameters, DeliveryReportsResponseBody, Domain,
+    DomainResponseBody, DomainsQueryParameters, DomainsResponseBody, Log, LogsQueryParameters,
+    LogsResponseBody, Report, RescheduleQueryParameters, RescheduleRequestBody,
+    RescheduleResponseBody, ScheduledStatusQueryParameters, ScheduledStatusResponseBody,
+    SendRequestBody, SendResponseBody, SentMessageDetails, UpdateScheduledStatusQueryParameters,
+    UpdateScheduledStatusRequestBody, UpdateScheduledStatusResponseBody, UpdateTrackingRequestBody,
+    UpdateTrackingResponseBody, ValidateAddressRequestBody, ValidateAddressResponseBody,
 };
 
+#[cfg(tokio)]
+use tokio::runtime::Runtime;
+
+#[cfg(tokio)]
+use crate::api::blocking_runtime;
+#[cfg(tokio)]
+use crate::api::pages::BlockingIter;
+
+pub mod endpoints;
+
+// Number of domains per page when the query parameters do not set one.
+const DEFAULT_DOMAINS_PAGE_SIZE: i32 = 10;
+
 pub const PATH_ADD_DOMAIN: &str = "/email/1/domains";
 pub const PATH_DELETE_DOMAIN: &str = "/email/1/domains/{domainName}";
 pub const PATH_GET_BULKS: &str = "/email/1/bulks";
@@ -39,15 +55,54 @@ pub const PATH_UPDATE_TRACKING: &str = "/email/1/domains/{domainName}/tracking";
 pub const PATH_VALIDATE: &str = "/email/2/validation";
 pub const PATH_VERIFY_DOMAIN: &str = "/email/1/domains/{domainName}/verify";
 
-async fn file_part(file_name: String) -> io::Result<Part> {
-    let mut file = tokio::fs::File::open(file_name.clone()).await?;
-    let mut buffer = Vec::new();
-    let count = file.read_to_end(&mut buffer).await?;
+fn to_part(attachment: &Attachment) -> Part {
+    Part::bytes(attachment.contents.clone()).file_name(attachment.file_name.clone())
+}
+
+// Returns the files read from disk, followed by the ones given in memory.
+async fn read_attachments(
+    file_names: &Option<Vec<String>>,
+    contents: &Option<Vec<Attachment>>,
+) -> io::Result<Vec<Attachment>> {
+    let mut attachments = Vec::new();
+
+    for file_name in file_names.iter().flatten() {
+        attachments.push(Attachment::new(file_name, read_file(file_name).await?));
+    }
+    attachments.extend(contents.iter().flatten().cloned());
+
+    Ok(attachments)
+}
+
+#[cfg(tokio)]
+async fn read_file(file_name: &str) -> io::Result<Vec<u8>> {
+    tokio::fs::read(file_name).await
+}
 
-    Ok(Part::stream_with_length(buffer, count as u64).file_name(file_name))
+// Without Tokio, there is no executor-independent way to read files asynchronously, so files are
+// read on the calling thread.
+#[cfg(all(not(tokio), not(target_arch = "wasm32")))]
+async fn read_file(file_name: &str) -> io::Result<Vec<u8>> {
+    std::fs::read(file_name)
 }
 
-async fn build_form(request_body: SendRequestBody) -> io::Result<Form> {
+#[cfg(target_arch = "wasm32")]
+async fn read_file(file_name: &str) -> io::Result<Vec<u8>> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!(
+            "can not read {} on wasm32, use attachment_contents instead",
+            file_name
+        ),
+    ))
+}
+
+fn build_form(
+    request_body: &SendRequestBody,
+    attachments: &[Attachment],
+    inline_images: &[Attachment],
+) -> Form {
+    let request_body = request_body.clone();
     let mut form = Form::new().text("to", request_body.to.clone());
 
     if let Some(from) = request_body.from {
@@ -74,15 +129,11 @@ async fn build_form(request_body: SendRequestBody) -> io::Result<Form> {
     if let Some(template_id) = request_body.template_id {
         form = form.text("templateId", template_id.to_string());
     }
-    if let Some(attachments) = request_body.attachments {
-        for attachment in attachments {
-            form = form.part("attachment", file_part(attachment).await?);
-        }
+    for attachment in attachments {
+        form = form.part("attachment", to_part(attachment));
     }
-    if let Some(inline_images) = request_body.inline_images {
-        for inline_image in inline_images {
-            form = form.part("inlineImage", file_part(inline_image).await?);
-        }
+    for inline_image in inline_images {
+        form = form.part("inlineImage", to_part(inline_image));
     }
     if let Some(intermediate_report) = request_body.intermediate_report {
         form = form.text("intermediateReport", intermediate_report.to_string());
@@ -133,25 +184,206 @@ async fn build_form(request_body: SendRequestBody) -> io::Result<Form> {
         form = form.text("landingPageId", landing_page_id);
     }
 
-    Ok(form)
+    form
+}
+
+// Fills in the bulk ID if it is missing, and returns it. The message ID is only filled in for a
+// single recipient, as the API would give it to every message of the bulk.
+fn fill_ids(request_body: &mut SendRequestBody) -> String {
+    let bulk_id = match &request_body.bulk_id {
+        Some(bulk_id) => bulk_id.clone(),
+        None => generate_id(),
+    };
+
+    let single_recipient =
+        !request_body.to.contains(',') && request_body.cc.is_none() && request_body.bcc.is_none();
+    if single_recipient {
+        request_body
+            .message_id
+            .get_or_insert_with(|| idempotency::message_id(&bulk_id, 0));
+    }
+    request_body.bulk_id = Some(bulk_id.clone());
+
+    bulk_id
 }
 
 /// Main asynchronous client for the Infobip Email channel.
 #[derive(Clone, Debug)]
 pub struct EmailClient {
     pub configuration: Configuration,
-    pub http_client: reqwest::Client,
+    pub transport: Arc<dyn Transport>,
 }
 
 impl EmailClient {
     /// Builds and returns a new asynchronous `EmailClient` with a specified configuration.
     pub fn with_configuration(configuration: Configuration) -> Self {
+        let transport = default_transport(&configuration);
+        EmailClient::with_transport(configuration, transport)
+    }
+
+    /// Builds and returns a new asynchronous `EmailClient` with a specified configuration, sending
+    /// requests through a custom transport.
+    pub fn with_transport(configuration: Configuration, transport: Arc<dyn Transport>) -> Self {
         EmailClient {
             configuration,
-            http_client: reqwest::Client::new(),
+            transport,
         }
     }
 
+    /// Returns a copy of this client that sends every request exactly once, ignoring the retry
+    /// policy of its configuration. Use it for calls that must not be repeated.
+    pub fn without_retries(&self) -> Self {
+        let mut client = self.clone();
+        client
+            .configuration
+            .set_retry_policy(RetryPolicy::disabled());
+
+        client
+    }
+
+    /// Returns a copy of this client that sends the given correlation ID in the
+    /// `X-Correlation-Id` header of every request, e.g. to match a call with your own logs.
+    pub fn with_correlation_id(&self, correlation_id: impl Into<String>) -> Self {
+        let mut client = self.clone();
+        client.configuration.set_correlation_id(correlation_id);
+
+        client
+    }
+
+    /// Returns a copy of this client with idempotent sends enabled. See
+    /// `Configuration::set_idempotent_sends()`.
+    pub fn with_idempotent_sends(&self) -> Self {
+        let mut client = self.clone();
+        client.configuration.set_idempotent_sends(true);
+
+        client
+    }
+
+    /// Sends a request to an endpoint, which can be one of `api::email::endpoints` or one defined
+    /// in your own code. See `api::endpoint`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use infobip_sdk::api::email::endpoints::GetDomain;
+    /// # use infobip_sdk::api::email::EmailClient;
+    /// # use infobip_sdk::configuration::Configuration;
+    /// # use reqwest::StatusCode;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let email_client = EmailClient::with_configuration(Configuration::from_env_api_key()?);
+    ///
+    /// let response = email_client.execute(GetDomain::new("example.com")).await?;
+    ///
+    /// assert_eq!(response.status, StatusCode::OK);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "EmailClient::execute",
+            skip_all,
+            fields(
+                path = endpoint.path_template(),
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
+    pub async fn execute<E: Endpoint>(
+        &self,
+        endpoint: E,
+    ) -> Result<SdkResponse<E::Response>, SdkError> {
+        self.request(endpoint).await
+    }
+
+    /// Sends a request to any path of the API, for endpoints the SDK does not model. The response
+    /// body is deserialized into `T`, e.g. `serde_json::Value`. See `api::raw`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use infobip_sdk::api::raw::RawRequest;
+    /// # use infobip_sdk::api::email::EmailClient;
+    /// # use infobip_sdk::configuration::Configuration;
+    /// # use serde_json::Value;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let email_client = EmailClient::with_configuration(Configuration::from_env_api_key()?);
+    ///
+    /// let response = email_client
+    ///     .raw::<Value>(RawRequest::get("/email/1/logs").query("limit", 10))
+    ///     .await?;
+    ///
+    /// println!("{}", response.body);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "EmailClient::raw",
+            skip_all,
+            fields(
+                path = request.path(),
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
+    pub async fn raw<T: DeserializeOwned>(
+        &self,
+        request: RawRequest,
+    ) -> Result<SdkResponse<T>, SdkError> {
+        self.request(Raw::new(request)).await
+    }
+
+    // Sends a request to an endpoint, within the span of the calling method.
+    async fn request<E: Endpoint>(
+        &self,
+        endpoint: E,
+    ) -> Result<SdkResponse<E::Response>, SdkError> {
+        execute_endpoint(self.transport.as_ref(), &self.configuration, &endpoint).await
+    }
+
+    // Returns the response of a sent bulk, rebuilt from its email logs, or `None` if no email of
+    // the bulk was logged.
+    async fn logged_bulk(
+        &self,
+        bulk_id: &str,
+    ) -> Result<Option<SdkResponse<SendResponseBody>>, SdkError> {
+        let query_parameters = LogsQueryParameters {
+            bulk_id: Some(bulk_id.to_string()),
+            limit: Some(1000),
+            ..Default::default()
+        };
+        let response = self.logs(query_parameters).await?;
+
+        let logs = response.body.results.unwrap_or_default();
+        if logs.is_empty() {
+            return Ok(None);
+        }
+
+        let messages = logs
+            .into_iter()
+            .map(|log| SentMessageDetails {
+                to: log.to,
+                message_id: log.message_id,
+                status: log.status,
+            })
+            .collect();
+
+        Ok(Some(SdkResponse {
+            body: SendResponseBody {
+                bulk_id: Some(bulk_id.to_string()),
+                messages: Some(messages),
+            },
+            status: response.status,
+            metadata: response.metadata,
+        }))
+    }
+
     /// Send an email or multiple emails to a recipient or multiple recipients with CC/BCC enabled.
     ///
     /// # Example
@@ -177,34 +409,36 @@ impl EmailClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "EmailClient::send",
+            skip_all,
+            fields(
+                path = PATH_SEND,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn send(
         &self,
-        request_body: SendRequestBody,
+        mut request_body: SendRequestBody,
     ) -> Result<SdkResponse<SendResponseBody>, SdkError> {
-        request_body.validate()?;
+        if !self.configuration.idempotent_sends() {
+            return self.request(SendEmail::new(request_body).await?).await;
+        }
 
-        let form = build_form(request_body).await?;
+        let bulk_id = fill_ids(&mut request_body);
+        let endpoint = SendEmail::new(request_body).await?;
+        let configuration = idempotency::without_retries(&self.configuration);
+        let (configuration, endpoint) = (&configuration, &endpoint);
 
-        let response = send_multipart_request(
-            &self.http_client,
-            &self.configuration,
-            form,
-            reqwest::Method::POST,
-            PATH_SEND,
+        idempotency::send(
+            self.configuration.retry_policy(),
+            move || execute_endpoint(self.transport.as_ref(), configuration, endpoint),
+            || self.logged_bulk(&bulk_id),
         )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        .await
     }
 
     /// See the scheduled time of your Email messages.
@@ -228,34 +462,22 @@ impl EmailClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "EmailClient::bulks",
+            skip_all,
+            fields(
+                path = PATH_GET_BULKS,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn bulks(
         &self,
         query_parameters: BulksQueryParameters,
     ) -> Result<SdkResponse<BulksResponseBody>, SdkError> {
-        query_parameters.validate()?;
-
-        let parameters_map = HashMap::from([("bulkId".to_string(), query_parameters.bulk_id)]);
-
-        let response = send_no_body_request(
-            &self.http_client,
-            &self.configuration,
-            parameters_map,
-            reqwest::Method::GET,
-            PATH_GET_BULKS,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(GetBulks::new(query_parameters)).await
     }
 
     /// Change the date and time for sending scheduled messages.
@@ -280,36 +502,24 @@ impl EmailClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "EmailClient::reschedule",
+            skip_all,
+            fields(
+                path = PATH_RESCHEDULE,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn reschedule(
         &self,
         query_parameters: RescheduleQueryParameters,
         request_body: RescheduleRequestBody,
     ) -> Result<SdkResponse<RescheduleResponseBody>, SdkError> {
-        query_parameters.validate()?;
-
-        let parameters_map = HashMap::from([("bulkId".to_string(), query_parameters.bulk_id)]);
-
-        let response = send_valid_json_request(
-            &self.http_client,
-            &self.configuration,
-            request_body,
-            parameters_map,
-            reqwest::Method::PUT,
-            PATH_RESCHEDULE,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(Reschedule::new(query_parameters, request_body))
+            .await
     }
 
     /// See the status of scheduled email messages.
@@ -333,34 +543,23 @@ impl EmailClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "EmailClient::scheduled_status",
+            skip_all,
+            fields(
+                path = PATH_GET_SCHEDULED_STATUS,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn scheduled_status(
         &self,
         query_parameters: ScheduledStatusQueryParameters,
     ) -> Result<SdkResponse<ScheduledStatusResponseBody>, SdkError> {
-        query_parameters.validate()?;
-
-        let parameters_map = HashMap::from([("bulkId".to_string(), query_parameters.bulk_id)]);
-
-        let response = send_no_body_request(
-            &self.http_client,
-            &self.configuration,
-            parameters_map,
-            reqwest::Method::GET,
-            PATH_GET_SCHEDULED_STATUS,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(GetScheduledStatus::new(query_parameters))
+            .await
     }
 
     /// Change status or completely cancel sending of scheduled messages.
@@ -385,36 +584,24 @@ impl EmailClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "EmailClient::update_scheduled_status",
+            skip_all,
+            fields(
+                path = PATH_UPDATE_SCHEDULED_STATUS,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn update_scheduled_status(
         &self,
         query_parameters: UpdateScheduledStatusQueryParameters,
         request_body: UpdateScheduledStatusRequestBody,
     ) -> Result<SdkResponse<UpdateScheduledStatusResponseBody>, SdkError> {
-        query_parameters.validate()?;
-
-        let parameters_map = HashMap::from([("bulkId".to_string(), query_parameters.bulk_id)]);
-
-        let response = send_valid_json_request(
-            &self.http_client,
-            &self.configuration,
-            request_body,
-            parameters_map,
-            reqwest::Method::PUT,
-            PATH_UPDATE_SCHEDULED_STATUS,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(UpdateScheduledStatus::new(query_parameters, request_body))
+            .await
     }
 
     ///  one-time delivery reports for all sent emails.
@@ -438,43 +625,81 @@ impl EmailClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "EmailClient::delivery_reports",
+            skip_all,
+            fields(
+                path = PATH_GET_DELIVERY_REPORTS,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn delivery_reports(
         &self,
         query_parameters: DeliveryReportsQueryParameters,
     ) -> Result<SdkResponse<DeliveryReportsResponseBody>, SdkError> {
-        query_parameters.validate()?;
-
-        let mut parameters_map = HashMap::<String, String>::new();
-        if let Some(bulk_id) = query_parameters.bulk_id {
-            parameters_map.insert("bulkId".to_string(), bulk_id);
-        }
-        if let Some(message_id) = query_parameters.message_id {
-            parameters_map.insert("messageId".to_string(), message_id);
-        }
-        if let Some(limit) = query_parameters.limit {
-            parameters_map.insert("limit".to_string(), limit.to_string());
-        }
+        self.request(GetDeliveryReports::new(query_parameters))
+            .await
+    }
 
-        let response = send_no_body_request(
-            &self.http_client,
-            &self.configuration,
-            parameters_map,
-            reqwest::Method::GET,
-            PATH_GET_DELIVERY_REPORTS,
+    /// Streams delivery reports, requesting batches until no new reports are returned.
+    ///
+    /// Every request is sent with the same query parameters, as the API returns each report only
+    /// once. Reports with a message ID that was already returned are skipped, and the stream ends
+    /// after `max_results` reports or the first error.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use futures_util::StreamExt;
+    /// # use infobip_sdk::api::email::EmailClient;
+    /// # use infobip_sdk::configuration::Configuration;
+    /// # use infobip_sdk::model::email::DeliveryReportsQueryParameters;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let email_client = EmailClient::with_configuration(Configuration::from_env_api_key()?);
+    ///
+    /// let reports =
+    ///     email_client.delivery_reports_stream(DeliveryReportsQueryParameters::new(), 5000);
+    /// futures_util::pin_mut!(reports);
+    ///
+    /// while let Some(report) = reports.next().await {
+    ///     println!("{:?}", report?.status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn delivery_reports_stream(
+        &self,
+        query_parameters: DeliveryReportsQueryParameters,
+        max_results: usize,
+    ) -> impl Stream<Item = Result<Report, SdkError>> + '_ {
+        let key = |report: &Report| report.message_id.clone();
+
+        pages::walk(
+            query_parameters,
+            max_results,
+            key,
+            move |query_parameters| async move {
+                let next = query_parameters.clone();
+                let reports = self
+                    .delivery_reports(query_parameters)
+                    .await?
+                    .body
+                    .results
+                    .unwrap_or_default();
+
+                let full = next
+                    .limit
+                    .map_or(true, |limit| reports.len() >= limit as usize);
+                Ok(Page {
+                    next: (full && !reports.is_empty()).then_some(next),
+                    results: reports,
+                })
+            },
         )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
     }
 
     ///  email logs of sent Email messagesId for request. Email logs
@@ -499,58 +724,100 @@ impl EmailClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "EmailClient::logs",
+            skip_all,
+            fields(
+                path = PATH_GET_LOGS,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn logs(
         &self,
         query_parameters: LogsQueryParameters,
     ) -> Result<SdkResponse<LogsResponseBody>, SdkError> {
-        query_parameters.validate()?;
-
-        let mut parameters_map = HashMap::<String, String>::new();
-        if let Some(message_id) = query_parameters.message_id {
-            parameters_map.insert("messageId".to_string(), message_id);
-        }
-        if let Some(from) = query_parameters.from {
-            parameters_map.insert("from".to_string(), from);
-        }
-        if let Some(to) = query_parameters.to {
-            parameters_map.insert("to".to_string(), to);
-        }
-        if let Some(bulk_id) = query_parameters.bulk_id {
-            parameters_map.insert("bulkId".to_string(), bulk_id);
-        }
-        if let Some(general_status) = query_parameters.general_status {
-            parameters_map.insert("generalStatus".to_string(), general_status);
-        }
-        if let Some(sent_since) = query_parameters.sent_since {
-            parameters_map.insert("sentSince".to_string(), sent_since);
-        }
-        if let Some(sent_until) = query_parameters.sent_until {
-            parameters_map.insert("sentUntil".to_string(), sent_until);
-        }
-        if let Some(limit) = query_parameters.limit {
-            parameters_map.insert("limit".to_string(), limit.to_string());
-        }
+        self.request(GetLogs::new(query_parameters)).await
+    }
 
-        let response = send_no_body_request(
-            &self.http_client,
-            &self.configuration,
-            parameters_map,
-            reqwest::Method::GET,
-            PATH_GET_LOGS,
+    /// Streams email logs, walking back in time from the newest ones.
+    ///
+    /// The first request is sent with the given query parameters. Every next one asks for the logs
+    /// sent until the earliest log of the previous one, and logs with a message ID that was
+    /// already returned are skipped. If a request returns only logs sent in the same millisecond,
+    /// the logs of that millisecond are requested on their own, with a `limit` of 1000, the most
+    /// the API allows, before the stream moves on to the logs sent before them. Logs beyond the
+    /// first 1000 of one millisecond can not be listed by time, and are skipped. The stream ends
+    /// once a request returns fewer logs than `limit`, after `max_results` logs or after the first
+    /// error.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use futures_util::StreamExt;
+    /// # use infobip_sdk::api::email::EmailClient;
+    /// # use infobip_sdk::configuration::Configuration;
+    /// # use infobip_sdk::model::email::LogsQueryParameters;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let email_client = EmailClient::with_configuration(Configuration::from_env_api_key()?);
+    ///
+    /// let logs = email_client.logs_stream(LogsQueryParameters::new(), 5000);
+    /// futures_util::pin_mut!(logs);
+    ///
+    /// while let Some(log) = logs.next().await {
+    ///     println!("{:?}", log?.message_id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn logs_stream(
+        &self,
+        query_parameters: LogsQueryParameters,
+        max_results: usize,
+    ) -> impl Stream<Item = Result<Log, SdkError>> + '_ {
+        let key = |log: &Log| log.message_id.clone();
+        let window = |query_parameters: &LogsQueryParameters| Window {
+            sent_since: query_parameters.sent_since.clone(),
+            sent_until: query_parameters.sent_until.clone(),
+            limit: query_parameters.limit,
+        };
+        let first = window(&query_parameters);
+
+        pages::walk(
+            query_parameters,
+            max_results,
+            key,
+            move |query_parameters| {
+                let first = first.clone();
+                async move {
+                    let current = window(&query_parameters);
+                    let next = query_parameters.clone();
+                    let logs = self
+                        .logs(query_parameters)
+                        .await?
+                        .body
+                        .results
+                        .unwrap_or_default();
+
+                    let sent_at = logs.iter().map(|log| log.sent_at.as_deref());
+                    let next = pages::next_window(&first, &current, sent_at).map(|window| {
+                        LogsQueryParameters {
+                            sent_since: window.sent_since,
+                            sent_until: window.sent_until,
+                            limit: window.limit,
+                            ..next
+                        }
+                    });
+                    Ok(Page {
+                        next,
+                        results: logs,
+                    })
+                }
+            },
         )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
     }
 
     /// Run validation to identify poor quality emails to clean up your recipient list.
@@ -574,31 +841,22 @@ impl EmailClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "EmailClient::validate_address",
+            skip_all,
+            fields(
+                path = PATH_VALIDATE,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn validate_address(
         &self,
         request_body: ValidateAddressRequestBody,
     ) -> Result<SdkResponse<ValidateAddressResponseBody>, SdkError> {
-        let response = send_valid_json_request(
-            &self.http_client,
-            &self.configuration,
-            request_body,
-            HashMap::new(),
-            reqwest::Method::POST,
-            PATH_VALIDATE,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(ValidateAddress::new(request_body)).await
     }
 
     ///  all domains associated with the account. It also provides details of the
@@ -623,40 +881,84 @@ impl EmailClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "EmailClient::domains",
+            skip_all,
+            fields(
+                path = PATH_GET_DOMAINS,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn domains(
         &self,
         query_parameters: DomainsQueryParameters,
     ) -> Result<SdkResponse<DomainsResponseBody>, SdkError> {
-        query_parameters.validate()?;
-
-        let mut parameters_map = HashMap::<String, String>::new();
-        if let Some(size) = query_parameters.size {
-            parameters_map.insert("size".to_string(), size.to_string());
-        }
-        if let Some(page) = query_parameters.page {
-            parameters_map.insert("page".to_string(), page.to_string());
-        }
+        self.request(GetDomains::new(query_parameters)).await
+    }
 
-        let response = send_no_body_request(
-            &self.http_client,
-            &self.configuration,
-            parameters_map,
-            reqwest::Method::GET,
-            PATH_GET_DOMAINS,
+    /// Streams the domains of the account, requesting one page after the other.
+    ///
+    /// The first request is sent with the given query parameters, and every next one asks for the
+    /// following page, until the last page reported by the API. Domains with a name that was
+    /// already returned are skipped, and the stream ends after `max_results` domains or the first
+    /// error.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use futures_util::StreamExt;
+    /// # use infobip_sdk::api::email::EmailClient;
+    /// # use infobip_sdk::configuration::Configuration;
+    /// # use infobip_sdk::model::email::DomainsQueryParameters;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let email_client = EmailClient::with_configuration(Configuration::from_env_api_key()?);
+    ///
+    /// let mut query_parameters = DomainsQueryParameters::new();
+    /// query_parameters.size = Some(20);
+    ///
+    /// let domains = email_client.domains_stream(query_parameters, 100);
+    /// futures_util::pin_mut!(domains);
+    ///
+    /// while let Some(domain) = domains.next().await {
+    ///     println!("{:?}", domain?.domain_name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn domains_stream(
+        &self,
+        query_parameters: DomainsQueryParameters,
+        max_results: usize,
+    ) -> impl Stream<Item = Result<Domain, SdkError>> + '_ {
+        let key = |domain: &Domain| domain.domain_name.clone();
+
+        pages::walk(
+            query_parameters,
+            max_results,
+            key,
+            move |query_parameters| async move {
+                let mut next = query_parameters.clone();
+                let body = self.domains(query_parameters).await?.body;
+                let domains = body.results.unwrap_or_default();
+
+                let page = next.page.unwrap_or(0);
+                let more = match body.paging.and_then(|paging| paging.total_pages) {
+                    Some(total_pages) => page + 1 < total_pages,
+                    None => {
+                        domains.len() >= next.size.unwrap_or(DEFAULT_DOMAINS_PAGE_SIZE) as usize
+                    }
+                };
+                next.page = Some(page + 1);
+                Ok(Page {
+                    next: (more && !domains.is_empty()).then_some(next),
+                    results: domains,
+                })
+            },
         )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
     }
 
     /// This method allows you to add new domains with a limit to create a maximum of 1000 domains
@@ -681,31 +983,22 @@ impl EmailClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "EmailClient::add_domain",
+            skip_all,
+            fields(
+                path = PATH_ADD_DOMAIN,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn add_domain(
         &self,
         request_body: AddDomainRequestBody,
     ) -> Result<SdkResponse<AddDomainResponseBody>, SdkError> {
-        let response = send_valid_json_request(
-            &self.http_client,
-            &self.configuration,
-            request_body,
-            HashMap::new(),
-            reqwest::Method::POST,
-            PATH_ADD_DOMAIN,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(AddDomain::new(request_body)).await
     }
 
     ///  the details of the domain like the DNS records, tracking details, active/blocked
@@ -727,32 +1020,22 @@ impl EmailClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "EmailClient::domain",
+            skip_all,
+            fields(
+                path = PATH_GET_DOMAIN,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn domain(
         &self,
         domain_name: &str,
     ) -> Result<SdkResponse<DomainResponseBody>, SdkError> {
-        let path = PATH_GET_DOMAIN.replace("{domainName}", domain_name);
-
-        let response = send_no_body_request(
-            &self.http_client,
-            &self.configuration,
-            HashMap::new(),
-            reqwest::Method::GET,
-            path.as_str(),
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(GetDomain::new(domain_name)).await
     }
 
     /// This method allows you to delete an existing domain.
@@ -773,26 +1056,21 @@ impl EmailClient {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn delete_domain(&self, domain_name: &str) -> Result<reqwest::StatusCode, SdkError> {
-        let path = PATH_DELETE_DOMAIN.replace("{domainName}", domain_name);
-
-        let response = send_no_body_request(
-            &self.http_client,
-            &self.configuration,
-            HashMap::new(),
-            reqwest::Method::DELETE,
-            path.as_str(),
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "EmailClient::delete_domain",
+            skip_all,
+            fields(
+                path = PATH_DELETE_DOMAIN,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
         )
-        .await?;
-
-        let status = response.status();
+    )]
+    pub async fn delete_domain(&self, domain_name: &str) -> Result<reqwest::StatusCode, SdkError> {
+        let response = self.request(DeleteDomain::new(domain_name)).await?;
 
-        if status.is_success() {
-            Ok(status)
-        } else {
-            let text = response.text().await?;
-            Err(build_api_error(status, &text))
-        }
+        Ok(response.status)
     }
 
     /// Update tracking events for the provided domain. Tracking events can be updated only for
@@ -818,34 +1096,24 @@ impl EmailClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "EmailClient::update_tracking",
+            skip_all,
+            fields(
+                path = PATH_UPDATE_TRACKING,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn update_tracking(
         &self,
         domain_name: &str,
         request_body: UpdateTrackingRequestBody,
     ) -> Result<SdkResponse<UpdateTrackingResponseBody>, SdkError> {
-        let path = PATH_UPDATE_TRACKING.replace("{domainName}", domain_name);
-
-        let response = send_valid_json_request(
-            &self.http_client,
-            &self.configuration,
-            request_body,
-            HashMap::new(),
-            reqwest::Method::PUT,
-            path.as_str(),
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(UpdateTracking::new(domain_name, request_body))
+            .await
     }
 
     /// Verify records(TXT, MX, DKIM) associated with the provided domain.
@@ -866,25 +1134,248 @@ impl EmailClient {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn verify_domain(&self, domain_name: &str) -> Result<reqwest::StatusCode, SdkError> {
-        let path = PATH_VERIFY_DOMAIN.replace("{domainName}", domain_name);
-
-        let response = send_no_body_request(
-            &self.http_client,
-            &self.configuration,
-            HashMap::new(),
-            reqwest::Method::POST,
-            path.as_str(),
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "EmailClient::verify_domain",
+            skip_all,
+            fields(
+                path = PATH_VERIFY_DOMAIN,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
         )
-        .await?;
+    )]
+    pub async fn verify_domain(&self, domain_name: &str) -> Result<reqwest::StatusCode, SdkError> {
+        let response = self.request(VerifyDomain::new(domain_name)).await?;
 
-        let status = response.status();
+        Ok(response.status)
+    }
+}
 
-        if status.is_success() {
-            Ok(status)
-        } else {
-            let text = response.text().await?;
-            Err(build_api_error(status, &text))
+/// Blocking client for the Infobip Email channel.
+///
+/// Calls the asynchronous `EmailClient` on a runtime owned by the client, so both share request
+/// building and response handling.
+#[cfg(tokio)]
+#[derive(Clone, Debug)]
+pub struct BlockingEmailClient {
+    client: EmailClient,
+    runtime: Arc<Runtime>,
+}
+
+#[cfg(tokio)]
+impl BlockingEmailClient {
+    /// Builds and returns a new `BlockingEmailClient` with a specified configuration.
+    pub fn with_configuration(configuration: Configuration) -> BlockingEmailClient {
+        let transport = default_transport(&configuration);
+        BlockingEmailClient::with_transport(configuration, transport)
+    }
+
+    /// Builds and returns a new `BlockingEmailClient` with a specified configuration and a custom
+    /// transport to send requests.
+    ///
+    /// # Panics
+    /// Panics if the runtime used to drive requests can not be created.
+    pub fn with_transport(
+        configuration: Configuration,
+        transport: Arc<dyn Transport>,
+    ) -> BlockingEmailClient {
+        BlockingEmailClient {
+            client: EmailClient::with_transport(configuration, transport),
+            runtime: blocking_runtime(),
         }
     }
+
+    /// Returns a copy of this client that sends every request exactly once, ignoring the retry
+    /// policy of its configuration. Use it for calls that must not be repeated.
+    pub fn without_retries(&self) -> Self {
+        BlockingEmailClient {
+            client: self.client.without_retries(),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// Returns a copy of this client that sends the given correlation ID in the
+    /// `X-Correlation-Id` header of every request, e.g. to match a call with your own logs.
+    pub fn with_correlation_id(&self, correlation_id: impl Into<String>) -> Self {
+        BlockingEmailClient {
+            client: self.client.with_correlation_id(correlation_id),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// Returns a copy of this client with idempotent sends enabled. See
+    /// `Configuration::set_idempotent_sends()`.
+    pub fn with_idempotent_sends(&self) -> Self {
+        BlockingEmailClient {
+            client: self.client.with_idempotent_sends(),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// Blocking version of `EmailClient::execute()`.
+    pub fn execute<E: Endpoint>(&self, endpoint: E) -> Result<SdkResponse<E::Response>, SdkError> {
+        self.runtime.block_on(self.client.execute(endpoint))
+    }
+
+    /// Blocking version of `EmailClient::raw()`.
+    pub fn raw<T: DeserializeOwned>(
+        &self,
+        request: RawRequest,
+    ) -> Result<SdkResponse<T>, SdkError> {
+        self.runtime.block_on(self.client.raw(request))
+    }
+
+    /// Blocking version of `EmailClient::send()`.
+    pub fn send(
+        &self,
+        request_body: SendRequestBody,
+    ) -> Result<SdkResponse<SendResponseBody>, SdkError> {
+        self.runtime.block_on(self.client.send(request_body))
+    }
+
+    /// Blocking version of `EmailClient::bulks()`.
+    pub fn bulks(
+        &self,
+        query_parameters: BulksQueryParameters,
+    ) -> Result<SdkResponse<BulksResponseBody>, SdkError> {
+        self.runtime.block_on(self.client.bulks(query_parameters))
+    }
+
+    /// Blocking version of `EmailClient::reschedule()`.
+    pub fn reschedule(
+        &self,
+        query_parameters: RescheduleQueryParameters,
+        request_body: RescheduleRequestBody,
+    ) -> Result<SdkResponse<RescheduleResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.reschedule(query_parameters, request_body))
+    }
+
+    /// Blocking version of `EmailClient::scheduled_status()`.
+    pub fn scheduled_status(
+        &self,
+        query_parameters: ScheduledStatusQueryParameters,
+    ) -> Result<SdkResponse<ScheduledStatusResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.scheduled_status(query_parameters))
+    }
+
+    /// Blocking version of `EmailClient::update_scheduled_status()`.
+    pub fn update_scheduled_status(
+        &self,
+        query_parameters: UpdateScheduledStatusQueryParameters,
+        request_body: UpdateScheduledStatusRequestBody,
+    ) -> Result<SdkResponse<UpdateScheduledStatusResponseBody>, SdkError> {
+        self.runtime.block_on(
+            self.client
+                .update_scheduled_status(query_parameters, request_body),
+        )
+    }
+
+    /// Blocking version of `EmailClient::delivery_reports()`.
+    pub fn delivery_reports(
+        &self,
+        query_parameters: DeliveryReportsQueryParameters,
+    ) -> Result<SdkResponse<DeliveryReportsResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.delivery_reports(query_parameters))
+    }
+
+    /// Blocking version of `EmailClient::delivery_reports_stream()`.
+    pub fn delivery_reports_iter(
+        &self,
+        query_parameters: DeliveryReportsQueryParameters,
+        max_results: usize,
+    ) -> impl Iterator<Item = Result<Report, SdkError>> + '_ {
+        BlockingIter::new(
+            &self.runtime,
+            self.client
+                .delivery_reports_stream(query_parameters, max_results),
+        )
+    }
+
+    /// Blocking version of `EmailClient::logs()`.
+    pub fn logs(
+        &self,
+        query_parameters: LogsQueryParameters,
+    ) -> Result<SdkResponse<LogsResponseBody>, SdkError> {
+        self.runtime.block_on(self.client.logs(query_parameters))
+    }
+
+    /// Blocking version of `EmailClient::logs_stream()`.
+    pub fn logs_iter(
+        &self,
+        query_parameters: LogsQueryParameters,
+        max_results: usize,
+    ) -> impl Iterator<Item = Result<Log, SdkError>> + '_ {
+        BlockingIter::new(
+            &self.runtime,
+            self.client.logs_stream(query_parameters, max_results),
+        )
+    }
+
+    /// Blocking version of `EmailClient::validate_address()`.
+    pub fn validate_address(
+        &self,
+        request_body: ValidateAddressRequestBody,
+    ) -> Result<SdkResponse<ValidateAddressResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.validate_address(request_body))
+    }
+
+    /// Blocking version of `EmailClient::domains()`.
+    pub fn domains(
+        &self,
+        query_parameters: DomainsQueryParameters,
+    ) -> Result<SdkResponse<DomainsResponseBody>, SdkError> {
+        self.runtime.block_on(self.client.domains(query_parameters))
+    }
+
+    /// Blocking version of `EmailClient::domains_stream()`.
+    pub fn domains_iter(
+        &self,
+        query_parameters: DomainsQueryParameters,
+        max_results: usize,
+    ) -> impl Iterator<Item = Result<Domain, SdkError>> + '_ {
+        BlockingIter::new(
+            &self.runtime,
+            self.client.domains_stream(query_parameters, max_results),
+        )
+    }
+
+    /// Blocking version of `EmailClient::add_domain()`.
+    pub fn add_domain(
+        &self,
+        request_body: AddDomainRequestBody,
+    ) -> Result<SdkResponse<AddDomainResponseBody>, SdkError> {
+        self.runtime.block_on(self.client.add_domain(request_body))
+    }
+
+    /// Blocking version of `EmailClient::domain()`.
+    pub fn domain(&self, domain_name: &str) -> Result<SdkResponse<DomainResponseBody>, SdkError> {
+        self.runtime.block_on(self.client.domain(domain_name))
+    }
+
+    /// Blocking version of `EmailClient::delete_domain()`.
+    pub fn delete_domain(&self, domain_name: &str) -> Result<reqwest::StatusCode, SdkError> {
+        self.runtime
+            .block_on(self.client.delete_domain(domain_name))
+    }
+
+    /// Blocking version of `EmailClient::update_tracking()`.
+    pub fn update_tracking(
+        &self,
+        domain_name: &str,
+        request_body: UpdateTrackingRequestBody,
+    ) -> Result<SdkResponse<UpdateTrackingResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.update_tracking(domain_name, request_body))
+    }
+
+    /// Blocking version of `EmailClient::verify_domain()`.
+    pub fn verify_domain(&self, domain_name: &str) -> Result<reqwest::StatusCode, SdkError> {
+        self.runtime
+            .block_on(self.client.verify_domain(domain_name))
+    }
 }
@@ -0,0 +1,554 @@
+//! Endpoints of the Email channel, which `EmailClient` calls. Each one can also be passed to
+//! `EmailClient::execute()`.
+
+use reqwest::{Method, RequestBuilder};
+use serde::de::IgnoredAny;
+use validator::Validate;
+
+use crate::api::email::*;
+use crate::api::email::{build_form, read_attachments};
+use crate::api::endpoint::{Endpoint, Json, NoBody, RequestBody};
+use crate::api::SdkError;
+use crate::model::email::{
+    AddDomainRequestBody, AddDomainResponseBody, Attachment, BulksQueryParameters,
+    BulksResponseBody, DeliveryReportsQueryParameters, DeliveryReportsResponseBody,
+    DomainResponseBody, DomainsQueryParameters, DomainsResponseBody, LogsQueryParameters,
+    LogsResponseBody, RescheduleQueryParameters, RescheduleRequestBody, RescheduleResponseBody,
+    ScheduledStatusQueryParameters, ScheduledStatusResponseBody, SendRequestBody, SendResponseBody,
+    UpdateScheduledStatusQueryParameters, UpdateScheduledStatusRequestBody,
+    UpdateScheduledStatusResponseBody, UpdateTrackingRequestBody, UpdateTrackingResponseBody,
+    ValidateAddressRequestBody, ValidateAddressResponseBody,
+};
+
+/// Sends emails, as a multipart form. See `EmailClient::send()`.
+#[derive(Clone, Debug)]
+pub struct SendEmail {
+    request_body: SendRequestBody,
+    attachments: Vec<Attachment>,
+    inline_images: Vec<Attachment>,
+}
+
+impl SendEmail {
+    /// Validates the request body and reads its attachments and inline images from disk.
+    pub async fn new(request_body: SendRequestBody) -> Result<Self, SdkError> {
+        request_body.validate()?;
+
+        let attachments =
+            read_attachments(&request_body.attachments, &request_body.attachment_contents).await?;
+        let inline_images = read_attachments(
+            &request_body.inline_images,
+            &request_body.inline_image_contents,
+        )
+        .await?;
+
+        Ok(SendEmail {
+            request_body,
+            attachments,
+            inline_images,
+        })
+    }
+}
+
+impl RequestBody for SendEmail {
+    fn add_to(&self, builder: RequestBuilder) -> RequestBuilder {
+        builder.multipart(build_form(
+            &self.request_body,
+            &self.attachments,
+            &self.inline_images,
+        ))
+    }
+}
+
+impl Endpoint for SendEmail {
+    type Query = ();
+    type Body = Self;
+    type Response = SendResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_SEND
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(self)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.request_body.validate()?)
+    }
+}
+
+/// Gets the scheduled emails of a bulk. See `EmailClient::bulks()`.
+#[derive(Clone, Debug)]
+pub struct GetBulks {
+    query_parameters: BulksQueryParameters,
+}
+
+impl GetBulks {
+    pub fn new(query_parameters: BulksQueryParameters) -> Self {
+        GetBulks { query_parameters }
+    }
+}
+
+impl Endpoint for GetBulks {
+    type Query = BulksQueryParameters;
+    type Body = NoBody;
+    type Response = BulksResponseBody;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_GET_BULKS
+    }
+
+    fn query(&self) -> Option<&Self::Query> {
+        Some(&self.query_parameters)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.query_parameters.validate()?)
+    }
+}
+
+/// Changes the date and time a bulk is sent at. See `EmailClient::reschedule()`.
+#[derive(Clone, Debug)]
+pub struct Reschedule {
+    query_parameters: RescheduleQueryParameters,
+    body: Json<RescheduleRequestBody>,
+}
+
+impl Reschedule {
+    pub fn new(
+        query_parameters: RescheduleQueryParameters,
+        request_body: RescheduleRequestBody,
+    ) -> Self {
+        Reschedule {
+            query_parameters,
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for Reschedule {
+    type Query = RescheduleQueryParameters;
+    type Body = Json<RescheduleRequestBody>;
+    type Response = RescheduleResponseBody;
+
+    fn method(&self) -> Method {
+        Method::PUT
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_RESCHEDULE
+    }
+
+    fn query(&self) -> Option<&Self::Query> {
+        Some(&self.query_parameters)
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        self.query_parameters.validate()?;
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Gets the status of a scheduled bulk. See `EmailClient::scheduled_status()`.
+#[derive(Clone, Debug)]
+pub struct GetScheduledStatus {
+    query_parameters: ScheduledStatusQueryParameters,
+}
+
+impl GetScheduledStatus {
+    pub fn new(query_parameters: ScheduledStatusQueryParameters) -> Self {
+        GetScheduledStatus { query_parameters }
+    }
+}
+
+impl Endpoint for GetScheduledStatus {
+    type Query = ScheduledStatusQueryParameters;
+    type Body = NoBody;
+    type Response = ScheduledStatusResponseBody;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_GET_SCHEDULED_STATUS
+    }
+
+    fn query(&self) -> Option<&Self::Query> {
+        Some(&self.query_parameters)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.query_parameters.validate()?)
+    }
+}
+
+/// Pauses, resumes or cancels a scheduled bulk. See `EmailClient::update_scheduled_status()`.
+#[derive(Clone, Debug)]
+pub struct UpdateScheduledStatus {
+    query_parameters: UpdateScheduledStatusQueryParameters,
+    body: Json<UpdateScheduledStatusRequestBody>,
+}
+
+impl UpdateScheduledStatus {
+    pub fn new(
+        query_parameters: UpdateScheduledStatusQueryParameters,
+        request_body: UpdateScheduledStatusRequestBody,
+    ) -> Self {
+        UpdateScheduledStatus {
+            query_parameters,
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for UpdateScheduledStatus {
+    type Query = UpdateScheduledStatusQueryParameters;
+    type Body = Json<UpdateScheduledStatusRequestBody>;
+    type Response = UpdateScheduledStatusResponseBody;
+
+    fn method(&self) -> Method {
+        Method::PUT
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_UPDATE_SCHEDULED_STATUS
+    }
+
+    fn query(&self) -> Option<&Self::Query> {
+        Some(&self.query_parameters)
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        self.query_parameters.validate()?;
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Gets delivery reports of sent emails. See `EmailClient::delivery_reports()`.
+#[derive(Clone, Debug)]
+pub struct GetDeliveryReports {
+    query_parameters: DeliveryReportsQueryParameters,
+}
+
+impl GetDeliveryReports {
+    pub fn new(query_parameters: DeliveryReportsQueryParameters) -> Self {
+        GetDeliveryReports { query_parameters }
+    }
+}
+
+impl Endpoint for GetDeliveryReports {
+    type Query = DeliveryReportsQueryParameters;
+    type Body = NoBody;
+    type Response = DeliveryReportsResponseBody;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_GET_DELIVERY_REPORTS
+    }
+
+    fn query(&self) -> Option<&Self::Query> {
+        Some(&self.query_parameters)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.query_parameters.validate()?)
+    }
+}
+
+/// Gets the logs of sent emails. See `EmailClient::logs()`.
+#[derive(Clone, Debug)]
+pub struct GetLogs {
+    query_parameters: LogsQueryParameters,
+}
+
+impl GetLogs {
+    pub fn new(query_parameters: LogsQueryParameters) -> Self {
+        GetLogs { query_parameters }
+    }
+}
+
+impl Endpoint for GetLogs {
+    type Query = LogsQueryParameters;
+    type Body = NoBody;
+    type Response = LogsResponseBody;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_GET_LOGS
+    }
+
+    fn query(&self) -> Option<&Self::Query> {
+        Some(&self.query_parameters)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.query_parameters.validate()?)
+    }
+}
+
+/// Validates an email address. See `EmailClient::validate_address()`.
+#[derive(Clone, Debug)]
+pub struct ValidateAddress {
+    body: Json<ValidateAddressRequestBody>,
+}
+
+impl ValidateAddress {
+    pub fn new(request_body: ValidateAddressRequestBody) -> Self {
+        ValidateAddress {
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for ValidateAddress {
+    type Query = ();
+    type Body = Json<ValidateAddressRequestBody>;
+    type Response = ValidateAddressResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_VALIDATE
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Gets a page of the domains of the account. See `EmailClient::domains()`.
+#[derive(Clone, Debug)]
+pub struct GetDomains {
+    query_parameters: DomainsQueryParameters,
+}
+
+impl GetDomains {
+    pub fn new(query_parameters: DomainsQueryParameters) -> Self {
+        GetDomains { query_parameters }
+    }
+}
+
+impl Endpoint for GetDomains {
+    type Query = DomainsQueryParameters;
+    type Body = NoBody;
+    type Response = DomainsResponseBody;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_GET_DOMAINS
+    }
+
+    fn query(&self) -> Option<&Self::Query> {
+        Some(&self.query_parameters)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.query_parameters.validate()?)
+    }
+}
+
+/// Adds a domain to the account. See `EmailClient::add_domain()`.
+#[derive(Clone, Debug)]
+pub struct AddDomain {
+    body: Json<AddDomainRequestBody>,
+}
+
+impl AddDomain {
+    pub fn new(request_body: AddDomainRequestBody) -> Self {
+        AddDomain {
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for AddDomain {
+    type Query = ();
+    type Body = Json<AddDomainRequestBody>;
+    type Response = AddDomainResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_ADD_DOMAIN
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Gets a domain of the account. See `EmailClient::domain()`.
+#[derive(Clone, Debug)]
+pub struct GetDomain {
+    domain_name: String,
+}
+
+impl GetDomain {
+    pub fn new(domain_name: &str) -> Self {
+        GetDomain {
+            domain_name: domain_name.to_string(),
+        }
+    }
+}
+
+impl Endpoint for GetDomain {
+    type Query = ();
+    type Body = NoBody;
+    type Response = DomainResponseBody;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_GET_DOMAIN
+    }
+
+    fn path_params(&self) -> Vec<(&str, &str)> {
+        vec![("domainName", &self.domain_name)]
+    }
+}
+
+/// Deletes a domain of the account. See `EmailClient::delete_domain()`.
+#[derive(Clone, Debug)]
+pub struct DeleteDomain {
+    domain_name: String,
+}
+
+impl DeleteDomain {
+    pub fn new(domain_name: &str) -> Self {
+        DeleteDomain {
+            domain_name: domain_name.to_string(),
+        }
+    }
+}
+
+impl Endpoint for DeleteDomain {
+    type Query = ();
+    type Body = NoBody;
+    type Response = IgnoredAny;
+
+    fn method(&self) -> Method {
+        Method::DELETE
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_DELETE_DOMAIN
+    }
+
+    fn path_params(&self) -> Vec<(&str, &str)> {
+        vec![("domainName", &self.domain_name)]
+    }
+}
+
+/// Updates the tracking events of a domain. See `EmailClient::update_tracking()`.
+#[derive(Clone, Debug)]
+pub struct UpdateTracking {
+    domain_name: String,
+    body: Json<UpdateTrackingRequestBody>,
+}
+
+impl UpdateTracking {
+    pub fn new(domain_name: &str, request_body: UpdateTrackingRequestBody) -> Self {
+        UpdateTracking {
+            domain_name: domain_name.to_string(),
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for UpdateTracking {
+    type Query = ();
+    type Body = Json<UpdateTrackingRequestBody>;
+    type Response = UpdateTrackingResponseBody;
+
+    fn method(&self) -> Method {
+        Method::PUT
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_UPDATE_TRACKING
+    }
+
+    fn path_params(&self) -> Vec<(&str, &str)> {
+        vec![("domainName", &self.domain_name)]
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Verifies a domain of the account. See `EmailClient::verify_domain()`.
+#[derive(Clone, Debug)]
+pub struct VerifyDomain {
+    domain_name: String,
+}
+
+impl VerifyDomain {
+    pub fn new(domain_name: &str) -> Self {
+        VerifyDomain {
+            domain_name: domain_name.to_string(),
+        }
+    }
+}
+
+impl Endpoint for VerifyDomain {
+    type Query = ();
+    type Body = NoBody;
+    type Response = IgnoredAny;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_VERIFY_DOMAIN
+    }
+
+    fn path_params(&self) -> Vec<(&str, &str)> {
+        vec![("domainName", &self.domain_name)]
+    }
+}
@@ -0,0 +1,155 @@
+//! Description of API endpoints, so that every endpoint is called the same way.
+//!
+//! An `Endpoint` tells the clients which method and path to call, with which query parameters and
+//! body, and what the response deserializes into. Every method of the channel clients is
+//! implemented with one of the endpoints in `api::sms::endpoints`, `api::email::endpoints` and
+//! `api::whatsapp::endpoints`. Endpoints the SDK does not cover yet can be called by implementing
+//! the trait and passing them to `execute()` on any client, with the same authentication, retries,
+//! middleware and error handling as the rest of the SDK.
+//!
+//! # Example
+//! ```no_run
+//! # use infobip_sdk::api::endpoint::{Endpoint, NoBody};
+//! # use infobip_sdk::api::sms::SmsClient;
+//! # use infobip_sdk::configuration::Configuration;
+//! # use reqwest::Method;
+//! # use serde_derive::Deserialize;
+//! #[derive(Debug, Deserialize)]
+//! struct Balance {
+//!     balance: f64,
+//!     currency: String,
+//! }
+//!
+//! struct GetBalance;
+//!
+//! impl Endpoint for GetBalance {
+//!     type Query = ();
+//!     type Body = NoBody;
+//!     type Response = Balance;
+//!
+//!     fn method(&self) -> Method {
+//!         Method::GET
+//!     }
+//!
+//!     fn path_template(&self) -> &str {
+//!         "/account/1/balance"
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = SmsClient::with_configuration(Configuration::from_env_api_key()?);
+//!
+//! let response = client.execute(GetBalance).await?;
+//!
+//! println!("{} {}", response.body.balance, response.body.currency);
+//! # Ok(())
+//! # }
+//! ```
+use std::ops::{Deref, DerefMut};
+
+use reqwest::{Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::api::path::PathTemplate;
+use crate::api::SdkError;
+
+/// Describes an endpoint of the Infobip API and a request to it.
+pub trait Endpoint {
+    /// Query parameters of the request, serialized into the query string. Use `()` for endpoints
+    /// without query parameters.
+    ///
+    /// Fields are sent in the order they are declared and `None` values are left out. Booleans,
+    /// numbers, strings, unit enum variants and types serialized as strings, like dates, are sent
+    /// as they are, and every element of a sequence is sent under the name of its field, e.g.
+    /// `messageId=a&messageId=b`.
+    type Query: Serialize;
+
+    /// Body of the request. Use `NoBody` for endpoints without a body.
+    type Body: RequestBody;
+
+    /// Type the body of a successful response is deserialized into. An empty body is read as
+    /// `null`, so use `()` for endpoints that respond without one, or `serde::de::IgnoredAny` when
+    /// the body is not needed.
+    type Response: DeserializeOwned;
+
+    /// Returns the HTTP method of the endpoint.
+    fn method(&self) -> Method;
+
+    /// Returns the path of the endpoint, with placeholders like `{bulkId}` for path parameters,
+    /// e.g. one of the `PATH_*` constants.
+    fn path_template(&self) -> &str;
+
+    /// Returns the name and value of every placeholder of the path template. None by default.
+    ///
+    /// Values are percent-encoded, so they can contain any character. Every placeholder must have
+    /// a value, and every value must have a placeholder, or the request fails with
+    /// `SdkError::InvalidPath` before it is sent.
+    fn path_params(&self) -> Vec<(&str, &str)> {
+        Vec::new()
+    }
+
+    /// Returns the query parameters of the request. None by default.
+    fn query(&self) -> Option<&Self::Query> {
+        None
+    }
+
+    /// Returns the body of the request. None by default.
+    fn body(&self) -> Option<&Self::Body> {
+        None
+    }
+
+    /// Checks the query parameters and body before the request is sent. Does nothing by default.
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(())
+    }
+}
+
+/// Body of a request to an endpoint.
+pub trait RequestBody {
+    /// Adds the body and its content type to the request. It is called once for every attempt,
+    /// as some bodies, like multipart forms, can only be sent once.
+    fn add_to(&self, builder: RequestBuilder) -> RequestBuilder;
+}
+
+/// Body of requests to endpoints that do not take one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NoBody;
+
+impl RequestBody for NoBody {
+    fn add_to(&self, builder: RequestBuilder) -> RequestBuilder {
+        builder
+    }
+}
+
+/// Body sent as JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Json<T>(pub T);
+
+impl<T: Serialize> RequestBody for Json<T> {
+    fn add_to(&self, builder: RequestBuilder) -> RequestBuilder {
+        builder.json(&self.0)
+    }
+}
+
+impl<T> Deref for Json<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Json<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+// Replaces the placeholders of the path template of an endpoint with their percent-encoded values.
+pub(crate) fn render_path<E: Endpoint>(endpoint: &E) -> Result<String, SdkError> {
+    let template = PathTemplate::parse(endpoint.path_template())?;
+
+    Ok(template.render(&endpoint.path_params())?)
+}
@@ -0,0 +1,117 @@
+// Idempotent sends: message requests get bulk and message IDs generated once per call, so that a
+// request that failed without a clear outcome can be looked up by its IDs before it is sent again.
+// Without the lookup, a timed out send could deliver the same message twice.
+#[cfg(any(feature = "sms", feature = "email"))]
+use std::future::Future;
+
+#[cfg(any(feature = "sms", feature = "email"))]
+use crate::api::{is_connect_error, retry_delay, timer, SdkError, SdkResponse};
+use crate::configuration::{Configuration, RetryPolicy};
+
+// Returns a random ID for the bulk or message of one call. Only the attempts of that call share it,
+// so that identical requests sent on purpose are never mistaken for each other.
+pub(crate) fn generate_id() -> String {
+    format!("{:032x}", fastrand::u128(..))
+}
+
+// Returns the ID of the message at the given position of a bulk.
+pub(crate) fn message_id(bulk_id: &str, index: usize) -> String {
+    format!("{}-{}", bulk_id, index)
+}
+
+// Returns `true` if the request may have been processed although it failed: it timed out, the
+// connection broke after it was sent, or a server or gateway error was returned.
+#[cfg(any(feature = "sms", feature = "email"))]
+fn is_ambiguous(error: &SdkError) -> bool {
+    match error {
+        SdkError::Timeout { .. } | SdkError::Transport { .. } | SdkError::ServerError(_) => true,
+        SdkError::Reqwest { source, .. } => !is_connect_error(source) && !source.is_builder(),
+        SdkError::UnexpectedResponse { status, .. } => status.is_server_error(),
+        _ => false,
+    }
+}
+
+// Returns a copy of the retry policy that only retries failures which show that the request was
+// not processed, like rate limiting or connection errors.
+fn unambiguous(policy: &RetryPolicy) -> RetryPolicy {
+    RetryPolicy {
+        retry_on_status: policy
+            .retry_on_status
+            .iter()
+            .filter(|status| !status.is_server_error())
+            .copied()
+            .collect(),
+        retry_on_timeouts: false,
+        ..policy.clone()
+    }
+}
+
+// Returns a copy of the configuration that only retries failures which show that the request was
+// not processed.
+#[cfg(feature = "whatsapp")]
+pub(crate) fn without_ambiguous_retries(configuration: &Configuration) -> Configuration {
+    let mut configuration = configuration.clone();
+    configuration.set_retry_policy(unambiguous(configuration.retry_policy()));
+    configuration
+}
+
+// Returns a copy of the configuration that sends requests once, for `send()` to retry them.
+#[cfg(any(feature = "sms", feature = "email"))]
+pub(crate) fn without_retries(configuration: &Configuration) -> Configuration {
+    let mut configuration = configuration.clone();
+    configuration.set_retry_policy(RetryPolicy {
+        max_attempts: 1,
+        ..configuration.retry_policy().clone()
+    });
+    configuration
+}
+
+// Sends a request that carries client-generated IDs, with `send` making a single attempt. After an
+// ambiguous failure, `lookup` checks whether the request was processed after all. It is only sent
+// again, within the attempts of the retry policy, if the lookup found nothing. If the lookup
+// fails, the original error is returned. Other failures are retried as the policy allows.
+#[cfg(any(feature = "sms", feature = "email"))]
+pub(crate) async fn send<R, S, SF, L, LF>(
+    policy: &RetryPolicy,
+    send: S,
+    lookup: L,
+) -> Result<SdkResponse<R>, SdkError>
+where
+    S: Fn() -> SF,
+    SF: Future<Output = Result<SdkResponse<R>, SdkError>>,
+    L: Fn() -> LF,
+    LF: Future<Output = Result<Option<SdkResponse<R>>, SdkError>>,
+{
+    let retry_policy = unambiguous(policy);
+    let mut attempt = 1;
+
+    loop {
+        let error = match send().await {
+            Err(error) if is_ambiguous(&error) => error,
+            Err(error) => {
+                let outcome = match (error.status(), error.metadata()) {
+                    (Some(status), Some(metadata)) => Ok((status, &metadata.headers)),
+                    _ => Err(&error),
+                };
+                match retry_delay(&retry_policy, attempt, outcome) {
+                    Some(delay) => {
+                        timer::sleep(delay).await;
+                        attempt += 1;
+                        continue;
+                    }
+                    None => return Err(error),
+                }
+            }
+            result => return result,
+        };
+
+        // Gives the logs time to catch up with the request.
+        timer::sleep(policy.delay(attempt, None).unwrap_or(policy.max_delay)).await;
+
+        match lookup().await {
+            Ok(Some(response)) => return Ok(response),
+            Ok(None) if attempt < policy.max_attempts => attempt += 1,
+            _ => return Err(error),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+// Records request details on the span opened for each client method when the `tracing` feature is
+// enabled. Without the feature, every function is a no-op. Headers are never recorded, so API keys
+// and tokens do not end up in traces. Bodies are only recorded with the `tracing-bodies` feature.
+#[cfg(feature = "tracing")]
+use std::time::Duration;
+
+#[cfg(feature = "tracing")]
+use reqwest::{Method, Request, StatusCode};
+
+#[cfg(feature = "tracing")]
+use tracing::Span;
+
+#[cfg(feature = "tracing")]
+pub(crate) fn record_request(request: &Request) {
+    let span = Span::current();
+    span.record("method", request.method().as_str());
+    if let Some(correlation_id) = request
+        .headers()
+        .get(crate::api::CORRELATION_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+    {
+        span.record("correlation_id", correlation_id);
+    }
+
+    #[cfg(feature = "tracing-bodies")]
+    if let Some(body) = request.body().and_then(|body| body.as_bytes()) {
+        tracing::trace!(body = %String::from_utf8_lossy(body), "request body");
+    }
+}
+
+#[cfg(feature = "tracing")]
+pub(crate) fn record_outcome(status: Option<StatusCode>, attempts: u32, latency: Duration) {
+    let span = Span::current();
+
+    if let Some(status) = status {
+        span.record("status", status.as_u16());
+    }
+    span.record("retries", attempts.saturating_sub(1));
+    span.record("latency_ms", latency.as_millis() as u64);
+}
+
+// Records the bulk ID and message IDs found in a successful response body. Only responses to
+// `POST` requests, which send messages, are searched for IDs, and only when the span is recorded,
+// so that large pages of logs and reports are not parsed twice.
+#[cfg(feature = "tracing")]
+pub(crate) fn record_response_body(method: &Method, text: &str) {
+    #[cfg(feature = "tracing-bodies")]
+    tracing::trace!(body = %text, "response body");
+
+    let span = Span::current();
+    if span.is_disabled() || method != Method::POST {
+        return;
+    }
+
+    let value: serde_json::Value = match serde_json::from_str(text) {
+        Ok(value) => value,
+        Err(_) => return,
+    };
+
+    if let Some(bulk_id) = value.get("bulkId").and_then(|id| id.as_str()) {
+        span.record("bulk_id", bulk_id);
+    }
+
+    let mut message_ids: Vec<&str> = value
+        .get("messages")
+        .and_then(|messages| messages.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|message| message.get("messageId").and_then(|id| id.as_str()))
+        .collect();
+    message_ids.extend(value.get("messageId").and_then(|id| id.as_str()));
+
+    if !message_ids.is_empty() {
+        span.record("message_ids", message_ids.join(",").as_str());
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+#[inline]
+pub(crate) fn record_request(_request: &reqwest::Request) {}
+
+#[cfg(not(feature = "tracing"))]
+#[inline]
+pub(crate) fn record_outcome(
+    _status: Option<reqwest::StatusCode>,
+    _attempts: u32,
+    _latency: std::time::Duration,
+) {
+}
+
+#[cfg(not(feature = "tracing"))]
+#[inline]
+pub(crate) fn record_response_body(_method: &reqwest::Method, _text: &str) {}
@@ -0,0 +1,68 @@
+//! Middleware that runs around every request sent by the clients.
+//!
+//! Middleware is added to a `Configuration` and applies to every client built from it. Each
+//! request passes through the middleware in the order it was added, then through the transport.
+//! Middleware can change the request before passing it on, inspect or change the response after
+//! it comes back, or return a response without calling the rest of the chain. With retries enabled,
+//! the chain runs once per attempt.
+use std::fmt;
+use std::sync::Arc;
+
+use reqwest::Request;
+
+use crate::api::transport::{Transport, TransportFuture};
+
+/// Runs around every request sent by the clients.
+///
+/// # Example
+/// ```
+/// # use std::time::Instant;
+/// # use infobip_sdk::api::middleware::{Middleware, Next};
+/// # use infobip_sdk::api::transport::TransportFuture;
+/// # use reqwest::Request;
+/// #[derive(Debug)]
+/// struct TenantMiddleware {
+///     tenant_id: reqwest::header::HeaderValue,
+/// }
+///
+/// impl Middleware for TenantMiddleware {
+///     fn handle<'a>(&'a self, mut request: Request, next: Next<'a>) -> TransportFuture<'a> {
+///         request.headers_mut().insert("X-Tenant-Id", self.tenant_id.clone());
+///
+///         Box::pin(async move {
+///             let start = Instant::now();
+///             let response = next.run(request).await;
+///             println!("request took {:?}", start.elapsed());
+///
+///             response
+///         })
+///     }
+/// }
+/// ```
+pub trait Middleware: fmt::Debug + Send + Sync {
+    /// Handles the request, usually by calling `next.run()` with it and returning the response.
+    fn handle<'a>(&'a self, request: Request, next: Next<'a>) -> TransportFuture<'a>;
+}
+
+/// The rest of the middleware chain, ending with the transport.
+pub struct Next<'a> {
+    transport: &'a dyn Transport,
+    middleware: &'a [Arc<dyn Middleware>],
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(transport: &'a dyn Transport, middleware: &'a [Arc<dyn Middleware>]) -> Self {
+        Next {
+            transport,
+            middleware,
+        }
+    }
+
+    /// Passes the request to the next middleware, or sends it if this is the end of the chain.
+    pub fn run(self, request: Request) -> TransportFuture<'a> {
+        match self.middleware.split_first() {
+            Some((current, rest)) => current.handle(request, Next::new(self.transport, rest)),
+            None => self.transport.execute(request),
+        }
+    }
+}
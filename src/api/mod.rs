@@ -1,11 +1,21 @@
 //! Endpoint functions and base response and error types
-use crate::configuration::{ApiKey, Configuration};
-use reqwest::{RequestBuilder, Response, StatusCode};
-use serde::Deserialize;
+use crate::api::endpoint::{render_path, Endpoint, RequestBody};
+use crate::api::middleware::Next;
+use crate::api::path::PathError;
+use crate::api::timer::{Instant, SystemTime};
+use crate::api::transport::Transport;
+use crate::configuration::{ApiKey, Configuration, RetryPolicy, SecretString};
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, USER_AGENT};
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize};
 use serde_derive::Serialize;
-use std::{collections::HashMap, fmt};
+use std::{
+    collections::HashMap,
+    fmt,
+    time::{Duration, UNIX_EPOCH},
+};
 use thiserror::Error;
-use validator::Validate;
+use zeroize::Zeroizing;
 
 #[cfg(feature = "email")]
 pub mod email;
@@ -16,14 +26,48 @@ pub mod sms;
 #[cfg(feature = "whatsapp")]
 pub mod whatsapp;
 
+pub mod auth;
+pub mod client;
+pub mod endpoint;
+pub mod middleware;
+pub mod path;
+pub mod raw;
+pub mod transport;
+
+#[cfg(all(any(test, feature = "testing"), not(target_arch = "wasm32")))]
+pub mod testing;
+
+#[cfg(any(feature = "email", feature = "sms", feature = "whatsapp"))]
+mod idempotency;
+mod instrument;
+#[cfg(any(feature = "email", feature = "sms"))]
+mod pages;
+mod query;
+pub(crate) mod timer;
+#[cfg(any(feature = "email", feature = "sms"))]
+mod timestamp;
+
+/// Name of the header that carries the correlation ID of every request.
+pub const CORRELATION_ID_HEADER: &str = "X-Correlation-Id";
+
 /// Holds the possible errors that can happen when calling the Infobip API.
+///
+/// Error responses with the status codes most callers handle differently have dedicated variants.
+/// Other 4xx responses are returned as `ApiRequestError`. The `is_*()` methods classify errors
+/// without matching on variants, e.g. to decide whether to retry a request later.
 #[derive(Error, Debug)]
 pub enum SdkError {
     #[error("request body has field errors")]
     Validation(#[from] validator::ValidationErrors),
 
+    /// The HTTP client failed, e.g. to connect. `correlation_id` is the ID the request was sent
+    /// with, if it was sent.
     #[error("HTTP client error")]
-    Reqwest(#[from] reqwest::Error),
+    Reqwest {
+        #[source]
+        source: reqwest::Error,
+        correlation_id: Option<String>,
+    },
 
     #[error("serialization error")]
     Serde(#[from] serde_json::Error),
@@ -33,6 +77,227 @@ pub enum SdkError {
 
     #[error("IO error")]
     Io(#[from] std::io::Error),
+
+    /// A custom transport failed to obtain a response, possibly after the request reached the
+    /// server. `correlation_id` is the ID the request was sent with.
+    #[error("transport error")]
+    Transport {
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+        correlation_id: Option<String>,
+    },
+
+    /// A custom transport failed before sending the request, e.g. to connect, so the server did
+    /// not receive it. `correlation_id` is the ID the request was to be sent with.
+    #[error("connection error")]
+    Connect {
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+        correlation_id: Option<String>,
+    },
+
+    #[error("could not obtain access token: {0}")]
+    Token(String),
+
+    /// The path template of the request is malformed or its parameters do not match it.
+    #[error("invalid request path")]
+    InvalidPath(#[from] PathError),
+
+    /// The credentials are missing, invalid or expired (401).
+    #[error("authentication failed")]
+    Unauthorized(#[source] ApiError),
+
+    /// The credentials are valid but not allowed to use the endpoint (403).
+    #[error("permission denied")]
+    Forbidden(#[source] ApiError),
+
+    /// The endpoint or the requested resource does not exist (404).
+    #[error("resource not found")]
+    NotFound(#[source] ApiError),
+
+    /// Too many requests were sent (429). `SdkError::retry_after()` returns the delay requested by
+    /// the server.
+    #[error("rate limited")]
+    RateLimited(#[source] ApiError),
+
+    /// The server failed to handle the request (5xx).
+    #[error("server error")]
+    ServerError(#[source] ApiError),
+
+    /// The request did not complete within the configured timeout. `correlation_id` is the ID the
+    /// request was sent with.
+    #[error("request timed out")]
+    Timeout {
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+        correlation_id: Option<String>,
+    },
+
+    /// An error response with a body that is not in the error format of the API, e.g. an HTML
+    /// page from a proxy or an empty body. The headers are in `metadata`.
+    #[error("unexpected error response with status {status}")]
+    UnexpectedResponse {
+        status: StatusCode,
+        body: String,
+        metadata: Box<ResponseMetadata>,
+    },
+}
+
+impl From<reqwest::Error> for SdkError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            SdkError::timeout(error)
+        } else {
+            SdkError::Reqwest {
+                source: error,
+                correlation_id: None,
+            }
+        }
+    }
+}
+
+impl SdkError {
+    /// Creates an `SdkError::Transport` from the error of a custom transport that may have sent
+    /// the request. Such errors are not retried, as the server may have processed the request.
+    pub fn transport(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> SdkError {
+        SdkError::Transport {
+            source: error.into(),
+            correlation_id: None,
+        }
+    }
+
+    /// Creates an `SdkError::Connect` from the error of a custom transport that failed before
+    /// sending any part of the request. Such errors are retried like connection errors.
+    pub fn transport_connect(
+        error: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> SdkError {
+        SdkError::Connect {
+            source: error.into(),
+            correlation_id: None,
+        }
+    }
+
+    /// Creates an `SdkError::Timeout` from the error of a custom transport that gave up waiting.
+    pub fn timeout(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> SdkError {
+        SdkError::Timeout {
+            source: error.into(),
+            correlation_id: None,
+        }
+    }
+
+    /// Returns the details of the error response, if the error was returned by the API.
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            SdkError::ApiRequestError(error)
+            | SdkError::Unauthorized(error)
+            | SdkError::Forbidden(error)
+            | SdkError::NotFound(error)
+            | SdkError::RateLimited(error)
+            | SdkError::ServerError(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Returns the headers, URL, elapsed time and number of attempts of the error response, if the
+    /// error was returned by the server.
+    pub fn metadata(&self) -> Option<&ResponseMetadata> {
+        match self {
+            SdkError::UnexpectedResponse { metadata, .. } => Some(metadata),
+            _ => self.api_error().map(|error| &*error.metadata),
+        }
+    }
+
+    /// Returns the correlation ID sent with the request, if the request was sent. Errors returned
+    /// by the server and failures without a response, like timeouts, both have it.
+    pub fn correlation_id(&self) -> Option<&str> {
+        match self {
+            SdkError::Reqwest { correlation_id, .. }
+            | SdkError::Transport { correlation_id, .. }
+            | SdkError::Connect { correlation_id, .. }
+            | SdkError::Timeout { correlation_id, .. } => correlation_id.as_deref(),
+            _ => self
+                .metadata()
+                .map(|metadata| metadata.correlation_id.as_str()),
+        }
+    }
+
+    // Records the correlation ID a request was sent with on errors without a response.
+    fn with_correlation_id(mut self, id: &str) -> SdkError {
+        if let SdkError::Reqwest { correlation_id, .. }
+        | SdkError::Transport { correlation_id, .. }
+        | SdkError::Connect { correlation_id, .. }
+        | SdkError::Timeout { correlation_id, .. } = &mut self
+        {
+            correlation_id.get_or_insert_with(|| id.to_string());
+        }
+
+        self
+    }
+
+    /// Returns the status code of the error response, if the error was returned by the server.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            SdkError::UnexpectedResponse { status, .. } => Some(*status),
+            _ => self.api_error().map(|error| error.status),
+        }
+    }
+
+    /// Returns the delay the server asked to wait before sending more requests, from the
+    /// `Retry-After` header of the error response.
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.metadata()
+            .and_then(|metadata| retry_after(&metadata.headers))
+    }
+
+    /// Returns `true` for failures that may succeed when the same request is sent again later:
+    /// rate limiting, server errors, timeouts and connection errors.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            SdkError::Timeout { .. } | SdkError::Connect { .. } => true,
+            SdkError::Reqwest { source, .. } => is_connect_error(source),
+            _ => self.is_rate_limited() || self.is_server_error(),
+        }
+    }
+
+    /// Returns `true` if the credentials were rejected or lack permission for the endpoint.
+    pub fn is_auth_error(&self) -> bool {
+        matches!(self, SdkError::Unauthorized(_) | SdkError::Forbidden(_))
+            || self.unexpected_status().map_or(false, |status| {
+                status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN
+            })
+    }
+
+    /// Returns `true` if the endpoint or the requested resource does not exist.
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, SdkError::NotFound(_))
+            || self.unexpected_status() == Some(StatusCode::NOT_FOUND)
+    }
+
+    /// Returns `true` if too many requests were sent.
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(self, SdkError::RateLimited(_))
+            || self.unexpected_status() == Some(StatusCode::TOO_MANY_REQUESTS)
+    }
+
+    /// Returns `true` if the server failed to handle the request.
+    pub fn is_server_error(&self) -> bool {
+        matches!(self, SdkError::ServerError(_))
+            || self
+                .unexpected_status()
+                .map_or(false, |status| status.is_server_error())
+    }
+
+    /// Returns `true` if the request timed out.
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, SdkError::Timeout { .. })
+    }
+
+    fn unexpected_status(&self) -> Option<StatusCode> {
+        match self {
+            SdkError::UnexpectedResponse { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
 }
 
 /// Holds the status code and error details when a 4xx or 5xx response is received.
@@ -40,6 +305,7 @@ pub enum SdkError {
 pub struct ApiError {
     pub details: ApiErrorDetails,
     pub status: StatusCode,
+    pub metadata: Box<ResponseMetadata>,
 }
 
 impl fmt::Display for ApiError {
@@ -98,151 +364,414 @@ impl fmt::Display for ApiErrorDetails {
 pub struct SdkResponse<T> {
     pub body: T,
     pub status: StatusCode,
+    pub metadata: ResponseMetadata,
+}
+
+/// Holds the details of the HTTP exchange that produced a response.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResponseMetadata {
+    /// Headers of the response, e.g. to read request IDs or rate limit counters.
+    pub headers: HeaderMap,
+
+    /// URL of the response, after any redirects.
+    pub url: reqwest::Url,
+
+    /// Time from sending the first attempt until the response body was read, including the delays
+    /// between retries.
+    pub elapsed: Duration,
+
+    /// Number of times the request was sent. It is 1 unless the request was retried.
+    pub attempts: u32,
+
+    /// Correlation ID sent in the `X-Correlation-Id` header of every attempt.
+    pub correlation_id: String,
+}
+
+// Response returned by `send_with_retries`, with what is needed to build its `ResponseMetadata`
+// and to record it.
+struct Exchange {
+    response: Response,
+    method: Method,
+    start: Instant,
+    attempts: u32,
+    correlation_id: String,
 }
 
-fn api_key_authorization_value(api_key: &ApiKey) -> String {
-    let key = api_key.key.to_owned();
-    let prefix = api_key
-        .prefix
-        .to_owned()
-        .unwrap_or_else(|| "App".to_string());
+// Generates a random correlation ID in the format of a version 4 UUID.
+fn generate_correlation_id() -> String {
+    let value = fastrand::u128(..) & !(0xf << 76) & !(0x3 << 62) | (0x4 << 76) | (0x2 << 62);
+
+    format!(
+        "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+        value >> 96,
+        (value >> 80) & 0xffff,
+        (value >> 64) & 0xffff,
+        (value >> 48) & 0xffff,
+        value & 0xffff_ffff_ffff
+    )
+}
+
+fn api_key_authorization_value(api_key: &ApiKey) -> Zeroizing<String> {
+    let prefix = api_key.prefix.as_deref().unwrap_or("App");
+
+    Zeroizing::new(format!("{} {}", prefix, api_key.key.expose_secret()))
+}
 
-    format!("{} {}", prefix, key)
+// Adds an authorization header that is redacted when the request is printed. Invalid values are
+// passed on as they are, so that reqwest reports them when the request is built.
+fn add_sensitive_authorization(builder: RequestBuilder, value: &str) -> RequestBuilder {
+    match HeaderValue::from_str(value) {
+        Ok(mut header_value) => {
+            header_value.set_sensitive(true);
+            builder.header(AUTHORIZATION, header_value)
+        }
+        Err(_) => builder.header(AUTHORIZATION, value),
+    }
 }
 
-// Async version of add_auth, uses async request builder.
+// Adds the authorization header matching the authentication details of the configuration.
 fn add_auth(mut builder: RequestBuilder, configuration: &Configuration) -> RequestBuilder {
     if let Some(api_key) = &configuration.api_key() {
-        builder = builder.header("Authorization", api_key_authorization_value(api_key));
+        builder = add_sensitive_authorization(builder, &api_key_authorization_value(api_key));
     } else if let Some(basic_auth) = &configuration.basic_auth() {
         builder = builder.basic_auth(
-            basic_auth.username.to_owned(),
-            basic_auth.password.to_owned(),
+            &basic_auth.username,
+            basic_auth
+                .password
+                .as_ref()
+                .map(|password| password.expose_secret()),
         );
     } else if let Some(token) = &configuration.bearer_access_token() {
-        builder = builder.bearer_auth(token);
+        builder = builder.bearer_auth(token.expose_secret());
     };
 
     builder
 }
 
+lazy_static::lazy_static! {
+    // Only used to build requests, which are then sent by the transport of each client.
+    static ref REQUEST_FACTORY: reqwest::Client = reqwest::Client::new();
+}
+
+// Returns a request builder with the default headers, timeout, authentication and user agent of
+// the configuration. Default headers never replace the authentication, user agent or correlation ID
+// headers.
+fn request_builder(
+    configuration: &Configuration,
+    method: reqwest::Method,
+    url: &str,
+) -> RequestBuilder {
+    let mut default_headers = configuration.default_headers().clone();
+    default_headers.remove(USER_AGENT);
+    default_headers.remove(CORRELATION_ID_HEADER);
+    if configuration.api_key().is_some()
+        || configuration.basic_auth().is_some()
+        || configuration.bearer_access_token().is_some()
+        || configuration.token_provider().is_some()
+    {
+        default_headers.remove(AUTHORIZATION);
+    }
+
+    let mut builder = REQUEST_FACTORY
+        .request(method, url)
+        .headers(default_headers);
+    if let Some(timeout) = configuration.timeout() {
+        builder = builder.timeout(timeout);
+    }
+
+    builder = add_auth(builder, configuration);
+    add_user_agent(builder, configuration)
+}
+
+// Builds the runtime on which a blocking client drives its asynchronous counterpart.
+#[cfg(all(tokio, any(feature = "email", feature = "sms", feature = "whatsapp")))]
+fn blocking_runtime() -> std::sync::Arc<tokio::runtime::Runtime> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("failed to build runtime for blocking client");
+
+    std::sync::Arc::new(runtime)
+}
+
 #[inline]
 fn user_agent() -> &'static str {
     include!("../../version.txt")
 }
 
-// Adds user agent to the request builder. Synchronous version.
-fn add_user_agent(builder: RequestBuilder) -> RequestBuilder {
-    builder.header("User-Agent", user_agent())
-}
-
-// Adds user agent to the request builder. Synchronous version.
-#[cfg(feature = "sms")]
-fn add_user_agent_blocking(
-    builder: reqwest::blocking::RequestBuilder,
-) -> reqwest::blocking::RequestBuilder {
-    builder.header("User-Agent", user_agent())
+// Adds user agent to the request builder.
+fn add_user_agent(builder: RequestBuilder, configuration: &Configuration) -> RequestBuilder {
+    match configuration.user_agent_suffix() {
+        Some(suffix) => builder.header(USER_AGENT, format!("{} {}", user_agent(), suffix)),
+        None => builder.header(USER_AGENT, user_agent()),
+    }
 }
 
-// Blocking version of add_auth, uses blocking request builder.
-#[cfg(feature = "sms")]
-fn add_auth_blocking(
-    mut builder: reqwest::blocking::RequestBuilder,
-    configuration: &Configuration,
-) -> reqwest::blocking::RequestBuilder {
-    if let Some(api_key) = &configuration.api_key() {
-        builder = builder.header("Authorization", api_key_authorization_value(api_key));
-    } else if let Some(basic_auth) = &configuration.basic_auth() {
-        builder = builder.basic_auth(
-            basic_auth.username.to_owned(),
-            basic_auth.password.to_owned(),
-        );
-    } else if let Some(token) = &configuration.bearer_access_token() {
-        builder = builder.bearer_auth(token);
+// Builds the error matching the status code of an error response. Bodies that are not in the error
+// format of the API are kept as they are, together with the status and headers.
+fn build_api_error(status: StatusCode, text: String, metadata: ResponseMetadata) -> SdkError {
+    let metadata = Box::new(metadata);
+    let error = match serde_json::from_str(&text) {
+        Ok(details) => ApiError {
+            details,
+            status,
+            metadata,
+        },
+        Err(_) => {
+            return SdkError::UnexpectedResponse {
+                status,
+                body: text,
+                metadata,
+            }
+        }
     };
 
-    builder
+    match status {
+        StatusCode::UNAUTHORIZED => SdkError::Unauthorized(error),
+        StatusCode::FORBIDDEN => SdkError::Forbidden(error),
+        StatusCode::NOT_FOUND => SdkError::NotFound(error),
+        StatusCode::TOO_MANY_REQUESTS => SdkError::RateLimited(error),
+        status if status.is_server_error() => SdkError::ServerError(error),
+        _ => SdkError::ApiRequestError(error),
+    }
+}
+
+// Reads the whole response body and returns it with the metadata of the response.
+async fn read_text(exchange: Exchange) -> Result<(String, ResponseMetadata), SdkError> {
+    let headers = exchange.response.headers().clone();
+    let url = exchange.response.url().clone();
+
+    let text = exchange
+        .response
+        .text()
+        .await
+        .map_err(|error| SdkError::from(error).with_correlation_id(&exchange.correlation_id))?;
+
+    Ok((
+        text,
+        ResponseMetadata {
+            headers,
+            url,
+            elapsed: exchange.start.elapsed(),
+            attempts: exchange.attempts,
+            correlation_id: exchange.correlation_id,
+        },
+    ))
 }
 
-fn build_api_error(status: StatusCode, text: &str) -> SdkError {
-    match serde_json::from_str(text) {
-        Ok(details) => SdkError::ApiRequestError(ApiError { details, status }),
-        Err(e) => SdkError::Serde(e),
+// Reads the response body, deserializing it on success or building an API error otherwise. An
+// empty body is read as `null`, for endpoints that respond without one.
+async fn read_response<T: DeserializeOwned>(
+    response: Exchange,
+) -> Result<SdkResponse<T>, SdkError> {
+    let status = response.response.status();
+    let method = response.method.clone();
+    let (text, metadata) = read_text(response).await?;
+
+    if status.is_success() {
+        instrument::record_response_body(&method, &text);
+
+        let body = if text.trim().is_empty() {
+            serde_json::from_str("null")?
+        } else {
+            serde_json::from_str(&text)?
+        };
+
+        Ok(SdkResponse {
+            body,
+            status,
+            metadata,
+        })
+    } else {
+        Err(build_api_error(status, text, metadata))
     }
 }
 
-async fn send_no_body_request(
-    client: &reqwest::Client,
-    configuration: &Configuration,
-    query_parameters: HashMap<String, String>,
-    method: reqwest::Method,
-    path: &str,
-) -> Result<Response, SdkError> {
-    let url = format!("{}{}", configuration.base_url(), path);
-    let mut builder = client.request(method, url).query(&query_parameters);
+// Parses a Retry-After header, which holds either a number of seconds or an HTTP date.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
 
-    builder = add_auth(builder, configuration);
-    builder = add_user_agent(builder);
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
 
-    Ok(builder.send().await?)
-}
+    let date = httpdate::parse_http_date(value).ok()?;
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .ok()?;
 
-async fn send_valid_json_request<T: Validate + serde::Serialize>(
-    client: &reqwest::Client,
-    configuration: &Configuration,
-    request_body: T,
-    query_parameters: HashMap<String, String>,
-    method: reqwest::Method,
-    path: &str,
-) -> Result<Response, SdkError> {
-    request_body.validate()?;
+    Some(date.duration_since(UNIX_EPOCH).ok()?.saturating_sub(now))
+}
 
-    let url = format!("{}{}", configuration.base_url(), path);
-    let mut builder = client
-        .request(method, url)
-        .json(&request_body)
-        .query(&query_parameters);
+// Returns the delay before the next attempt, or `None` if the outcome of the given attempt must
+// be returned to the caller.
+fn retry_delay(
+    policy: &RetryPolicy,
+    attempt: u32,
+    outcome: Result<(StatusCode, &HeaderMap), &SdkError>,
+) -> Option<Duration> {
+    if attempt >= policy.max_attempts {
+        return None;
+    }
 
-    builder = add_auth(builder, configuration);
-    builder = add_user_agent(builder);
+    match outcome {
+        Ok((status, headers)) if policy.retry_on_status.contains(&status) => {
+            policy.delay(attempt, retry_after(headers))
+        }
+        Err(SdkError::Reqwest { source, .. })
+            if policy.retry_on_connect_errors && is_connect_error(source) =>
+        {
+            policy.delay(attempt, None)
+        }
+        Err(SdkError::Timeout { .. }) if policy.retry_on_timeouts => policy.delay(attempt, None),
+        Err(SdkError::Connect { .. }) if policy.retry_on_connect_errors => {
+            policy.delay(attempt, None)
+        }
+        _ => None,
+    }
+}
 
-    Ok(builder.send().await?)
+// Returns `true` if the request failed before it could reach the server.
+#[cfg(not(target_arch = "wasm32"))]
+fn is_connect_error(error: &reqwest::Error) -> bool {
+    error.is_connect()
 }
 
-#[cfg(feature = "email")]
-async fn send_multipart_request(
-    client: &reqwest::Client,
-    configuration: &Configuration,
-    form: reqwest::multipart::Form,
-    method: reqwest::Method,
-    path: &str,
-) -> Result<Response, SdkError> {
-    let url = format!("{}{}", configuration.base_url(), path);
-    let mut builder = client.request(method, url);
+// The Fetch API reports every network failure alike, so none of them is known to have happened
+// before the request reached the server.
+#[cfg(target_arch = "wasm32")]
+fn is_connect_error(_error: &reqwest::Error) -> bool {
+    false
+}
 
-    builder = add_auth(builder, configuration);
-    builder = add_user_agent(builder);
+// Replaces the authorization header of the request with a bearer token from a token provider.
+fn set_bearer_token(request: &mut reqwest::Request, token: &SecretString) -> Result<(), SdkError> {
+    let value = Zeroizing::new(format!("Bearer {}", token.expose_secret()));
+    let mut value = HeaderValue::from_str(&value)
+        .map_err(|_| SdkError::Token("access token is not a valid header value".to_string()))?;
+    value.set_sensitive(true);
+    request.headers_mut().insert(AUTHORIZATION, value);
 
-    Ok(builder.multipart(form).send().await?)
+    Ok(())
 }
 
-#[cfg(feature = "sms")]
-fn send_blocking_valid_json_request<T: Validate + serde::Serialize>(
-    client: &reqwest::blocking::Client,
+// Sends the request produced by `build_request`, building it again for every retry allowed by the
+// retry policy of the configuration. Every attempt waits for the rate limiter, if one is set, and
+// runs through the middleware chain before reaching the transport. A request rejected with 401
+// while using a token provider is sent once more with a new token, without counting as a retry.
+// All attempts carry the same correlation ID, from the configuration or generated for this call.
+async fn send_with_retries<F>(
+    transport: &dyn Transport,
     configuration: &Configuration,
-    request_body: T,
-    method: reqwest::Method,
     path: &str,
-) -> Result<reqwest::blocking::Response, SdkError> {
-    request_body.validate()?;
+    build_request: F,
+) -> Result<Exchange, SdkError>
+where
+    F: Fn() -> RequestBuilder,
+{
+    let policy = configuration.retry_policy();
+    let start = Instant::now();
+    let correlation_id = configuration
+        .correlation_id()
+        .map_or_else(generate_correlation_id, str::to_string);
+    let mut attempt = 1;
+    let mut token_refreshed = false;
+
+    loop {
+        if let Some(rate_limiter) = configuration.rate_limiter() {
+            rate_limiter.acquire(path).await;
+        }
+
+        let mut request = build_request()
+            .header(CORRELATION_ID_HEADER, correlation_id.as_str())
+            .build()?;
+        let token = match configuration.token_provider() {
+            Some(token_provider) => {
+                let token = token_provider.token().await?;
+                set_bearer_token(&mut request, &token)?;
+                Some(token)
+            }
+            None => None,
+        };
+        instrument::record_request(&request);
+        let method = request.method().clone();
+
+        let result = Next::new(transport, configuration.middleware())
+            .run(request)
+            .await;
+
+        if let (Some(token_provider), Some(token), Ok(response)) =
+            (configuration.token_provider(), &token, &result)
+        {
+            if response.status() == StatusCode::UNAUTHORIZED && !token_refreshed {
+                token_provider.invalidate(token.expose_secret());
+                token_refreshed = true;
+                continue;
+            }
+        }
+
+        let outcome = match &result {
+            Ok(response) => Ok((response.status(), response.headers())),
+            Err(error) => Err(error),
+        };
+
+        match retry_delay(policy, attempt, outcome) {
+            Some(delay) => timer::sleep(delay).await,
+            None => {
+                let status = result.as_ref().ok().map(|response| response.status());
+                instrument::record_outcome(status, attempt, start.elapsed());
+
+                return match result {
+                    Ok(response) => Ok(Exchange {
+                        response,
+                        method,
+                        start,
+                        attempts: attempt,
+                        correlation_id,
+                    }),
+                    Err(error) => Err(error.with_correlation_id(&correlation_id)),
+                };
+            }
+        }
+
+        attempt += 1;
+    }
+}
 
+// Validates the request to the endpoint, sends it and reads its response.
+async fn execute_endpoint<E: Endpoint>(
+    transport: &dyn Transport,
+    configuration: &Configuration,
+    endpoint: &E,
+) -> Result<SdkResponse<E::Response>, SdkError> {
+    endpoint.validate()?;
+
+    let path = render_path(endpoint)?;
     let url = format!("{}{}", configuration.base_url(), path);
-    let mut builder = client.request(method, url);
+    let method = endpoint.method();
 
-    builder = add_auth_blocking(builder, configuration);
-    builder = add_user_agent_blocking(builder);
+    let query = match endpoint.query() {
+        Some(query) => query::to_pairs(query)?,
+        None => Vec::new(),
+    };
 
-    Ok(builder.json(&request_body).send()?)
+    let response = send_with_retries(transport, configuration, &path, || {
+        let mut builder = request_builder(configuration, method.clone(), &url);
+        if !query.is_empty() {
+            builder = builder.query(&query);
+        }
+        match endpoint.body() {
+            Some(body) => body.add_to(builder),
+            None => builder,
+        }
+    })
+    .await?;
+
+    read_response(response).await
 }
 
 mod tests;
@@ -0,0 +1,176 @@
+// Walks the pages or time windows of list endpoints, for the `*_stream()` methods of the clients.
+// Results already seen are skipped, as consecutive time windows share the results at their
+// boundary, and the walk stops once a page brings nothing new for a query that would be sent
+// again, there is no next page, or the cap on the number of results is reached.
+use std::collections::{HashSet, VecDeque};
+use std::future::Future;
+
+use futures_util::stream::{self, Stream};
+
+use crate::api::timestamp::{format_timestamp, parse_timestamp};
+use crate::api::SdkError;
+
+// Results of one request, and the query of the next one if there may be more.
+pub(crate) struct Page<T, Q> {
+    pub(crate) results: Vec<T>,
+    pub(crate) next: Option<Q>,
+}
+
+struct Walk<T, Q, F> {
+    next: Option<Q>,
+    fetch: F,
+    key: fn(&T) -> Option<String>,
+    seen: HashSet<String>,
+    buffer: VecDeque<T>,
+    remaining: usize,
+}
+
+// Returns the results of every page, starting with the given query, up to `max_results` of them.
+// Results are told apart by `key`, and the ones without a key are never skipped. An error ends the
+// stream after it is returned.
+pub(crate) fn walk<T, Q, F, Fut>(
+    query: Q,
+    max_results: usize,
+    key: fn(&T) -> Option<String>,
+    fetch: F,
+) -> impl Stream<Item = Result<T, SdkError>>
+where
+    Q: Clone + PartialEq,
+    F: FnMut(Q) -> Fut,
+    Fut: Future<Output = Result<Page<T, Q>, SdkError>>,
+{
+    let walk = Walk {
+        next: Some(query),
+        fetch,
+        key,
+        seen: HashSet::new(),
+        buffer: VecDeque::new(),
+        remaining: max_results,
+    };
+
+    stream::unfold(walk, |mut walk| async move {
+        loop {
+            if walk.remaining == 0 {
+                return None;
+            }
+            if let Some(result) = walk.buffer.pop_front() {
+                walk.remaining -= 1;
+                return Some((Ok(result), walk));
+            }
+
+            let query = walk.next.take()?;
+            let page = match (walk.fetch)(query.clone()).await {
+                Ok(page) => page,
+                Err(error) => return Some((Err(error), walk)),
+            };
+
+            let key = walk.key;
+            let seen = &mut walk.seen;
+            walk.buffer.extend(
+                page.results
+                    .into_iter()
+                    .filter(|result| key(result).map_or(true, |key| seen.insert(key))),
+            );
+            // Sending the same query again would bring nothing new either, but a page of results
+            // already seen may be followed by new ones, e.g. when a time window moves back.
+            if walk.buffer.is_empty() && page.next.as_ref() == Some(&query) {
+                return None;
+            }
+            walk.next = page.next;
+        }
+    })
+}
+
+// Largest number of logs the API returns for one request.
+const MAX_LOGS_LIMIT: i32 = 1000;
+
+// Time window of a logs query, whose results are sent back newest first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Window {
+    pub(crate) sent_since: Option<String>,
+    pub(crate) sent_until: Option<String>,
+    pub(crate) limit: Option<i32>,
+}
+
+// Returns the window after `current`, given the timestamps of its results, or `None` if it had
+// every log left. `first` is the window the walk started with. Timestamps are in the UTC format of
+// the API, e.g. `2022-10-05T16:28:52.000+0000`, and so sort like strings.
+//
+// The next window ends at the earliest timestamp, so that logs sharing it are not missed, and the
+// ones already returned are skipped by the walk. A full page whose results all share a timestamp
+// may not hold every log of that millisecond, so the millisecond is then listed on its own, with
+// the largest limit the API allows, before the walk moves on to the logs sent before it.
+pub(crate) fn next_window<'a>(
+    first: &Window,
+    current: &Window,
+    sent_at: impl Iterator<Item = Option<&'a str>>,
+) -> Option<Window> {
+    // Only the window of a crowded millisecond has another start than the first window.
+    if current.sent_since != first.sent_since {
+        return Some(Window {
+            sent_until: current.sent_since.clone(),
+            ..first.clone()
+        });
+    }
+
+    let sent_at: Vec<_> = sent_at.collect();
+    let full = current
+        .limit
+        .map_or(true, |limit| sent_at.len() >= limit as usize);
+    if !full {
+        return None;
+    }
+
+    let earliest = sent_at.iter().flatten().min()?;
+    let latest = sent_at.iter().flatten().max()?;
+    if earliest == latest {
+        // The API may treat the start as exclusive, so the window starts a millisecond earlier.
+        return Some(Window {
+            sent_since: Some(just_before(earliest)?),
+            sent_until: Some(earliest.to_string()),
+            limit: Some(MAX_LOGS_LIMIT),
+        });
+    }
+
+    Some(Window {
+        sent_until: Some(earliest.to_string()),
+        ..current.clone()
+    })
+}
+
+// Returns the timestamp a millisecond before the given one, which is in the format of the API,
+// e.g. `2022-10-05T16:28:52.000+0000`, or `None` if it is not.
+pub(crate) fn just_before(timestamp: &str) -> Option<String> {
+    parse_timestamp(timestamp).map(|millis| format_timestamp(millis - 1))
+}
+
+// Drives a stream on the runtime of a blocking client, one result at a time.
+#[cfg(tokio)]
+pub(crate) struct BlockingIter<'a, T> {
+    runtime: &'a tokio::runtime::Runtime,
+    stream: std::pin::Pin<Box<dyn Stream<Item = Result<T, SdkError>> + 'a>>,
+}
+
+#[cfg(tokio)]
+impl<'a, T> BlockingIter<'a, T> {
+    pub(crate) fn new(
+        runtime: &'a tokio::runtime::Runtime,
+        stream: impl Stream<Item = Result<T, SdkError>> + 'a,
+    ) -> Self {
+        BlockingIter {
+            runtime,
+            stream: Box::pin(stream),
+        }
+    }
+}
+
+#[cfg(tokio)]
+impl<'a, T> Iterator for BlockingIter<'a, T> {
+    type Item = Result<T, SdkError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        use futures_util::StreamExt;
+
+        self.runtime.block_on(self.stream.next())
+    }
+}
@@ -0,0 +1,177 @@
+//! Path templates, like the `PATH_*` constants of the channel modules.
+//!
+//! A template is a path with placeholders in braces, e.g. `/email/1/domains/{domainName}`. Every
+//! placeholder must be given a value, and values are percent-encoded, so that characters like `/`,
+//! `?` or `#` stay within their segment instead of changing the URL. Values for placeholders the
+//! template does not have are rejected, as they usually mean a typo in the name.
+//!
+//! # Example
+//! ```
+//! # use infobip_sdk::api::path::PathTemplate;
+//! let template = PathTemplate::parse("/whatsapp/2/senders/{sender}/templates/{templateName}")?;
+//!
+//! let path = template.render(&[("sender", "447860099299"), ("templateName", "sale/50%")])?;
+//!
+//! assert_eq!(path, "/whatsapp/2/senders/447860099299/templates/sale%2F50%25");
+//! # Ok::<(), infobip_sdk::api::path::PathError>(())
+//! ```
+use thiserror::Error;
+
+/// Error found while parsing or rendering a path template.
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+pub enum PathError {
+    /// The template has an unclosed brace, a brace within a placeholder or an empty placeholder.
+    #[error("malformed path template {0}")]
+    MalformedTemplate(String),
+
+    /// No value was given for a placeholder of the template.
+    #[error("missing value for path parameter {0}")]
+    MissingParameter(String),
+
+    /// A value was given for a placeholder the template does not have.
+    #[error("unknown path parameter {0}")]
+    UnknownParameter(String),
+
+    /// The value is empty, `.` or `..`, which would change the path instead of filling a segment.
+    #[error("invalid value for path parameter {0}")]
+    InvalidValue(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Part<'a> {
+    Literal(&'a str),
+    Placeholder(&'a str),
+}
+
+/// Parsed path template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathTemplate<'a> {
+    template: &'a str,
+    parts: Vec<Part<'a>>,
+}
+
+impl<'a> PathTemplate<'a> {
+    /// Parses a template with placeholders like `{appId}`.
+    ///
+    /// # Errors
+    /// Returns `PathError::MalformedTemplate` if a brace is not closed, a placeholder contains
+    /// another brace or a placeholder has no name.
+    pub fn parse(template: &'a str) -> Result<PathTemplate<'a>, PathError> {
+        let malformed = || PathError::MalformedTemplate(template.to_string());
+        let mut parts = Vec::new();
+        let mut rest = template;
+
+        while let Some(start) = rest.find(['{', '}']) {
+            if rest[start..].starts_with('}') {
+                return Err(malformed());
+            }
+            if start > 0 {
+                parts.push(Part::Literal(&rest[..start]));
+            }
+
+            let placeholder = &rest[start + 1..];
+            let end = placeholder.find('}').ok_or_else(malformed)?;
+            let name = &placeholder[..end];
+            if name.is_empty() || name.contains('{') {
+                return Err(malformed());
+            }
+            parts.push(Part::Placeholder(name));
+            rest = &placeholder[end + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(Part::Literal(rest));
+        }
+
+        Ok(PathTemplate { template, parts })
+    }
+
+    /// Returns the template as it was parsed.
+    pub fn as_str(&self) -> &'a str {
+        self.template
+    }
+
+    /// Returns the names of the placeholders, in the order they appear.
+    pub fn placeholders(&self) -> Vec<&'a str> {
+        self.parts
+            .iter()
+            .filter_map(|part| match part {
+                Part::Placeholder(name) => Some(*name),
+                Part::Literal(_) => None,
+            })
+            .collect()
+    }
+
+    /// Returns the path with every placeholder replaced by its percent-encoded value.
+    ///
+    /// # Errors
+    /// Returns an error if a placeholder has no value, a value is given for a placeholder the
+    /// template does not have, or a value is empty, `.` or `..`.
+    pub fn render(&self, params: &[(&str, &str)]) -> Result<String, PathError> {
+        let placeholders = self.placeholders();
+        if let Some((name, _)) = params.iter().find(|(name, _)| !placeholders.contains(name)) {
+            return Err(PathError::UnknownParameter(name.to_string()));
+        }
+
+        let mut path = String::with_capacity(self.template.len());
+        for part in &self.parts {
+            match part {
+                Part::Literal(literal) => path.push_str(literal),
+                Part::Placeholder(name) => {
+                    let value = params
+                        .iter()
+                        .find(|(param, _)| param == name)
+                        .map(|(_, value)| *value)
+                        .ok_or_else(|| PathError::MissingParameter(name.to_string()))?;
+                    if value.is_empty() || value == "." || value == ".." {
+                        return Err(PathError::InvalidValue(name.to_string()));
+                    }
+                    path.push_str(&encode_segment(value));
+                }
+            }
+        }
+
+        Ok(path)
+    }
+}
+
+/// Percent-encodes every character of the value except the unreserved ones of RFC 3986, so that
+/// it can be used as one path segment.
+pub fn encode_segment(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+
+    encoded
+}
+
+// Reverses `encode_segment()`. Invalid escapes and bytes that are not UTF-8 are kept as they are.
+#[cfg(all(any(test, feature = "testing"), not(target_arch = "wasm32")))]
+pub(crate) fn decode_segment(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let escaped = segment
+            .get(index + 1..index + 3)
+            .filter(|hex| bytes[index] == b'%' && hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
@@ -0,0 +1,740 @@
+//! Serialization of query parameters into the name and value pairs of a query string.
+//!
+//! The query of an endpoint is usually one of the `*QueryParameters` structs. Its fields are added
+//! in the order they are declared, under their serialized names, and `None` values are left out.
+//! Every element of a sequence is added under the name of its field, e.g. `messageId=a&messageId=b`.
+//! Values can be booleans, numbers, strings, unit enum variants or any type serialized as a string,
+//! like most date types. Maps and sequences of name and value pairs are added as they are.
+use serde::ser::{
+    self, Impossible, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeTuple,
+    Serializer,
+};
+
+type Error = serde_json::Error;
+
+// Returns the query parameters as name and value pairs, in a stable order.
+pub(crate) fn to_pairs<T: Serialize + ?Sized>(query: &T) -> Result<Vec<(String, String)>, Error> {
+    let mut pairs = Vec::new();
+    query.serialize(QuerySerializer { pairs: &mut pairs })?;
+
+    Ok(pairs)
+}
+
+fn unsupported_query() -> Error {
+    ser::Error::custom("query must be a struct, a map or a sequence of name and value pairs")
+}
+
+fn unsupported_value(name: &str) -> Error {
+    ser::Error::custom(format!("unsupported value for query parameter {}", name))
+}
+
+fn unsupported_name() -> Error {
+    ser::Error::custom("query parameter names must be strings, numbers or unit variants")
+}
+
+// Generates the methods of a serializer that fail for scalar values.
+macro_rules! reject_scalars {
+    ($ok:ty, $error:expr; $($method:ident($value:ty)),*) => {
+        $(
+            fn $method(self, _value: $value) -> Result<$ok, Error> {
+                Err($error)
+            }
+        )*
+    };
+}
+
+// Serializes the query itself, which has the parameters as its fields or entries.
+struct QuerySerializer<'a> {
+    pairs: &'a mut Vec<(String, String)>,
+}
+
+impl<'a> Serializer for QuerySerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = PairsSerializer<'a>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = MapSerializer<'a>;
+    type SerializeStruct = StructSerializer<'a>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    reject_scalars!((), unsupported_query();
+        serialize_bool(bool), serialize_i8(i8), serialize_i16(i16), serialize_i32(i32),
+        serialize_i64(i64), serialize_u8(u8), serialize_u16(u16), serialize_u32(u32),
+        serialize_u64(u64), serialize_f32(f32), serialize_f64(f64), serialize_char(char),
+        serialize_str(&str), serialize_bytes(&[u8]), serialize_unit_struct(&'static str));
+
+    fn serialize_none(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), Error> {
+        Err(unsupported_query())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), Error> {
+        Err(unsupported_query())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Ok(PairsSerializer { pairs: self.pairs })
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(unsupported_query())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(unsupported_query())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(unsupported_query())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Ok(MapSerializer {
+            pairs: self.pairs,
+            name: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        Ok(StructSerializer { pairs: self.pairs })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(unsupported_query())
+    }
+}
+
+struct StructSerializer<'a> {
+    pairs: &'a mut Vec<(String, String)>,
+}
+
+impl<'a> SerializeStruct for StructSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(ValueSerializer {
+            name,
+            pairs: &mut *self.pairs,
+        })
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+struct MapSerializer<'a> {
+    pairs: &'a mut Vec<(String, String)>,
+    name: Option<String>,
+}
+
+impl<'a> SerializeMap for MapSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.name = Some(key.serialize(NameSerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let name = self
+            .name
+            .take()
+            .ok_or_else(|| ser::Error::custom("query value serialized before its name"))?;
+
+        value.serialize(ValueSerializer {
+            name: &name,
+            pairs: &mut *self.pairs,
+        })
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+// Serializes a sequence of name and value pairs, like the query of a raw request.
+struct PairsSerializer<'a> {
+    pairs: &'a mut Vec<(String, String)>,
+}
+
+impl<'a> SerializeSeq for PairsSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, pair: &T) -> Result<(), Error> {
+        pair.serialize(PairSerializer {
+            pairs: &mut *self.pairs,
+        })
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+struct PairSerializer<'a> {
+    pairs: &'a mut Vec<(String, String)>,
+}
+
+impl<'a> Serializer for PairSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = PairTupleSerializer<'a>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = Impossible<(), Error>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    reject_scalars!((), unsupported_query();
+        serialize_bool(bool), serialize_i8(i8), serialize_i16(i16), serialize_i32(i32),
+        serialize_i64(i64), serialize_u8(u8), serialize_u16(u16), serialize_u32(u32),
+        serialize_u64(u64), serialize_f32(f32), serialize_f64(f64), serialize_char(char),
+        serialize_str(&str), serialize_bytes(&[u8]), serialize_unit_struct(&'static str));
+
+    fn serialize_none(self) -> Result<(), Error> {
+        Err(unsupported_query())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<(), Error> {
+        Err(unsupported_query())
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        Err(unsupported_query())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), Error> {
+        Err(unsupported_query())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), Error> {
+        Err(unsupported_query())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(unsupported_query())
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Error> {
+        if len != 2 {
+            return Err(unsupported_query());
+        }
+
+        Ok(PairTupleSerializer {
+            pairs: self.pairs,
+            name: None,
+        })
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(unsupported_query())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(unsupported_query())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(unsupported_query())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        Err(unsupported_query())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(unsupported_query())
+    }
+}
+
+struct PairTupleSerializer<'a> {
+    pairs: &'a mut Vec<(String, String)>,
+    name: Option<String>,
+}
+
+impl<'a> SerializeTuple for PairTupleSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, element: &T) -> Result<(), Error> {
+        match self.name.take() {
+            None => {
+                self.name = Some(element.serialize(NameSerializer)?);
+                Ok(())
+            }
+            Some(name) => element.serialize(ValueSerializer {
+                name: &name,
+                pairs: &mut *self.pairs,
+            }),
+        }
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+// Serializes the value of one parameter, adding a pair for it or for each of its elements.
+struct ValueSerializer<'a> {
+    name: &'a str,
+    pairs: &'a mut Vec<(String, String)>,
+}
+
+impl<'a> ValueSerializer<'a> {
+    fn push(self, value: String) -> Result<(), Error> {
+        self.pairs.push((self.name.to_string(), value));
+        Ok(())
+    }
+}
+
+impl<'a> Serializer for ValueSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = ValuesSerializer<'a>;
+    type SerializeTuple = ValuesSerializer<'a>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = Impossible<(), Error>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    fn serialize_bool(self, value: bool) -> Result<(), Error> {
+        self.push(value.to_string())
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<(), Error> {
+        self.push(value.to_string())
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<(), Error> {
+        self.push(value.to_string())
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<(), Error> {
+        self.push(value.to_string())
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<(), Error> {
+        self.push(value.to_string())
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<(), Error> {
+        self.push(value.to_string())
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<(), Error> {
+        self.push(value.to_string())
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<(), Error> {
+        self.push(value.to_string())
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<(), Error> {
+        self.push(value.to_string())
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<(), Error> {
+        self.push(value.to_string())
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<(), Error> {
+        self.push(value.to_string())
+    }
+
+    fn serialize_char(self, value: char) -> Result<(), Error> {
+        self.push(value.to_string())
+    }
+
+    fn serialize_str(self, value: &str) -> Result<(), Error> {
+        self.push(value.to_string())
+    }
+
+    fn serialize_bytes(self, _value: &[u8]) -> Result<(), Error> {
+        Err(unsupported_value(self.name))
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.push(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), Error> {
+        Err(unsupported_value(self.name))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Ok(ValuesSerializer {
+            name: self.name,
+            pairs: self.pairs,
+        })
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Ok(ValuesSerializer {
+            name: self.name,
+            pairs: self.pairs,
+        })
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(unsupported_value(self.name))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(unsupported_value(self.name))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(unsupported_value(self.name))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        Err(unsupported_value(self.name))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(unsupported_value(self.name))
+    }
+}
+
+// Serializes the elements of a sequence, each one as a value of the same parameter.
+struct ValuesSerializer<'a> {
+    name: &'a str,
+    pairs: &'a mut Vec<(String, String)>,
+}
+
+impl<'a> ValuesSerializer<'a> {
+    fn add<T: Serialize + ?Sized>(&mut self, element: &T) -> Result<(), Error> {
+        element.serialize(ValueSerializer {
+            name: self.name,
+            pairs: &mut *self.pairs,
+        })
+    }
+}
+
+impl<'a> SerializeSeq for ValuesSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, element: &T) -> Result<(), Error> {
+        self.add(element)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<'a> SerializeTuple for ValuesSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, element: &T) -> Result<(), Error> {
+        self.add(element)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+// Serializes the name of a parameter, which is a map key or the first element of a pair.
+struct NameSerializer;
+
+impl Serializer for NameSerializer {
+    type Ok = String;
+    type Error = Error;
+    type SerializeSeq = Impossible<String, Error>;
+    type SerializeTuple = Impossible<String, Error>;
+    type SerializeTupleStruct = Impossible<String, Error>;
+    type SerializeTupleVariant = Impossible<String, Error>;
+    type SerializeMap = Impossible<String, Error>;
+    type SerializeStruct = Impossible<String, Error>;
+    type SerializeStructVariant = Impossible<String, Error>;
+
+    reject_scalars!(String, unsupported_name();
+        serialize_bool(bool), serialize_f32(f32), serialize_f64(f64), serialize_bytes(&[u8]),
+        serialize_unit_struct(&'static str));
+
+    fn serialize_i8(self, value: i8) -> Result<String, Error> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<String, Error> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<String, Error> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<String, Error> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<String, Error> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<String, Error> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<String, Error> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<String, Error> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_char(self, value: char) -> Result<String, Error> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_str(self, value: &str) -> Result<String, Error> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_none(self) -> Result<String, Error> {
+        Err(unsupported_name())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<String, Error> {
+        Err(unsupported_name())
+    }
+
+    fn serialize_unit(self) -> Result<String, Error> {
+        Err(unsupported_name())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<String, Error> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, Error> {
+        Err(unsupported_name())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(unsupported_name())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(unsupported_name())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(unsupported_name())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(unsupported_name())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(unsupported_name())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        Err(unsupported_name())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(unsupported_name())
+    }
+}
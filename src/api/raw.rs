@@ -0,0 +1,217 @@
+//! Requests to any path of the API, for endpoints the SDK does not model.
+//!
+//! A `RawRequest` is sent with the `raw()` method of any client, with the same authentication,
+//! user agent, retries, middleware and error handling as the rest of the SDK. The response body is
+//! deserialized into the type chosen by the caller, like `serde_json::Value`. To reuse a request
+//! in several places, consider implementing `api::endpoint::Endpoint` instead.
+//!
+//! # Example
+//! ```no_run
+//! # use infobip_sdk::api::raw::RawRequest;
+//! # use infobip_sdk::api::sms::SmsClient;
+//! # use infobip_sdk::configuration::Configuration;
+//! # use serde_json::Value;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = SmsClient::with_configuration(Configuration::from_env_api_key()?);
+//!
+//! let request = RawRequest::get("/sms/1/logs")
+//!     .query("messageId", "first-message-id")
+//!     .query("messageId", "second-message-id");
+//! let response = client.raw::<Value>(request).await?;
+//!
+//! println!("{}", response.body["results"]);
+//! # Ok(())
+//! # }
+//! ```
+use std::marker::PhantomData;
+
+use reqwest::multipart::{Form, Part};
+use reqwest::{Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::api::endpoint::{Endpoint, RequestBody};
+use crate::api::SdkError;
+
+/// Request to a path of the API, with optional query parameters and a JSON or multipart body.
+#[derive(Clone, Debug)]
+pub struct RawRequest {
+    method: Method,
+    path: String,
+    path_params: Vec<(String, String)>,
+    query: Vec<(String, String)>,
+    body: RawBody,
+}
+
+#[derive(Clone, Debug)]
+enum RawBody {
+    Empty,
+    Json(serde_json::Value),
+    Multipart(Vec<RawPart>),
+}
+
+#[derive(Clone, Debug)]
+struct RawPart {
+    name: String,
+    contents: Vec<u8>,
+    file_name: Option<String>,
+}
+
+impl RawRequest {
+    /// Builds a request with the given method to the given path, e.g. `/sms/1/logs`. The path is
+    /// appended to the base URL of the configuration. It can have placeholders like `{bulkId}`,
+    /// whose values are set with `path_param()`.
+    pub fn new(method: Method, path: &str) -> RawRequest {
+        RawRequest {
+            method,
+            path: path.to_string(),
+            path_params: Vec::new(),
+            query: Vec::new(),
+            body: RawBody::Empty,
+        }
+    }
+
+    /// Builds a GET request to the given path.
+    pub fn get(path: &str) -> RawRequest {
+        RawRequest::new(Method::GET, path)
+    }
+
+    /// Builds a POST request to the given path.
+    pub fn post(path: &str) -> RawRequest {
+        RawRequest::new(Method::POST, path)
+    }
+
+    /// Builds a PUT request to the given path.
+    pub fn put(path: &str) -> RawRequest {
+        RawRequest::new(Method::PUT, path)
+    }
+
+    /// Builds a DELETE request to the given path.
+    pub fn delete(path: &str) -> RawRequest {
+        RawRequest::new(Method::DELETE, path)
+    }
+
+    /// Sets the value of a placeholder of the path. The value is percent-encoded, so it can contain
+    /// characters like `/` or `?`.
+    pub fn path_param(mut self, name: &str, value: impl ToString) -> RawRequest {
+        self.path_params.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Adds a query parameter. Parameters are sent in the order they were added, and a name can be
+    /// added more than once.
+    pub fn query(mut self, name: &str, value: impl ToString) -> RawRequest {
+        self.query.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Sets the body of the request to the given value, sent as JSON.
+    ///
+    /// # Errors
+    /// Returns `SdkError::Serde` if the value can not be serialized.
+    pub fn json<T: Serialize + ?Sized>(mut self, body: &T) -> Result<RawRequest, SdkError> {
+        self.body = RawBody::Json(serde_json::to_value(body)?);
+        Ok(self)
+    }
+
+    /// Adds a text field to the body of the request, which is sent as a multipart form.
+    pub fn multipart_text(self, name: &str, value: impl Into<String>) -> RawRequest {
+        self.add_part(RawPart {
+            name: name.to_string(),
+            contents: value.into().into_bytes(),
+            file_name: None,
+        })
+    }
+
+    /// Adds a file to the body of the request, which is sent as a multipart form.
+    pub fn multipart_file(self, name: &str, file_name: &str, contents: Vec<u8>) -> RawRequest {
+        self.add_part(RawPart {
+            name: name.to_string(),
+            contents,
+            file_name: Some(file_name.to_string()),
+        })
+    }
+
+    /// Returns the method of the request.
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    /// Returns the path of the request, with its placeholders.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    fn add_part(mut self, part: RawPart) -> RawRequest {
+        match &mut self.body {
+            RawBody::Multipart(parts) => parts.push(part),
+            _ => self.body = RawBody::Multipart(vec![part]),
+        }
+        self
+    }
+}
+
+impl RequestBody for RawRequest {
+    fn add_to(&self, builder: RequestBuilder) -> RequestBuilder {
+        match &self.body {
+            RawBody::Empty => builder,
+            RawBody::Json(value) => builder.json(value),
+            RawBody::Multipart(parts) => {
+                let form = parts.iter().fold(Form::new(), |form, part| {
+                    let mut form_part = Part::bytes(part.contents.clone());
+                    if let Some(file_name) = &part.file_name {
+                        form_part = form_part.file_name(file_name.clone());
+                    }
+                    form.part(part.name.clone(), form_part)
+                });
+                builder.multipart(form)
+            }
+        }
+    }
+}
+
+// Endpoint for a raw request, whose response deserializes into `T`.
+pub(crate) struct Raw<T> {
+    request: RawRequest,
+    response: PhantomData<fn() -> T>,
+}
+
+impl<T> Raw<T> {
+    pub(crate) fn new(request: RawRequest) -> Self {
+        Raw {
+            request,
+            response: PhantomData,
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Endpoint for Raw<T> {
+    type Query = Vec<(String, String)>;
+    type Body = RawRequest;
+    type Response = T;
+
+    fn method(&self) -> Method {
+        self.request.method.clone()
+    }
+
+    fn path_template(&self) -> &str {
+        &self.request.path
+    }
+
+    fn path_params(&self) -> Vec<(&str, &str)> {
+        self.request
+            .path_params
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect()
+    }
+
+    fn query(&self) -> Option<&Self::Query> {
+        Some(&self.request.query)
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.request)
+    }
+}
@@ -1,38 +1,53 @@
 //! Module with client and endpoint functions for the SMS channel.
 
-use std::collections::HashMap;
-
-use validator::Validate;
-
-use crate::api::{
-    build_api_error, send_blocking_valid_json_request, send_no_body_request,
-    send_valid_json_request, ApiError, SdkError, SdkResponse,
-};
+use std::sync::Arc;
+
+use futures_util::Stream;
+use serde::de::DeserializeOwned;
+
+use crate::api::endpoint::Endpoint;
+use crate::api::idempotency::{self, generate_id, message_id};
+use crate::api::pages::{self, Page, Window};
+use crate::api::raw::{Raw, RawRequest};
+use crate::api::sms::endpoints::*;
+use crate::api::transport::{default_transport, Transport};
+use crate::api::{execute_endpoint, SdkError, SdkResponse};
 use crate::model::sms::{
     CreateTfaApplicationRequestBody, CreateTfaApplicationResponseBody,
     CreateTfaMessageTemplateRequestBody, CreateTfaMessageTemplateResponseBody,
     DeliveryReportsQueryParameters, DeliveryReportsResponseBody, InboundReportsQueryParameters,
-    InboundReportsResponseBody, LogsQueryParameters, LogsResponseBody, RescheduleQueryParameters,
-    RescheduleRequestBody, RescheduleResponseBody, ResendPinOverSmsRequestBody,
-    ResendPinOverSmsResponseBody, ResendPinOverVoiceRequestBody, ResendPinOverVoiceResponseBody,
-    ScheduledQueryParameters, ScheduledResponseBody, ScheduledStatusQueryParameters,
-    ScheduledStatusResponseBody, SendBinaryRequestBody, SendBinaryResponseBody,
-    SendOverQueryParametersQueryParameters, SendOverQueryParametersResponseBody,
-    SendPinOverSmsQueryParameters, SendPinOverSmsRequestBody, SendPinOverSmsResponseBody,
-    SendPinOverVoiceRequestBody, SendPinOverVoiceResponseBody, SendRequestBody, SendResponseBody,
-    TfaApplicationResponseBody, TfaApplicationsResponseBody, TfaMessageTemplateResponseBody,
-    TfaMessageTemplatesResponseBody, TfaVerificationStatusQueryParameters,
-    TfaVerificationStatusResponseBody, UpdateScheduledStatusQueryParameters,
-    UpdateScheduledStatusRequestBody, UpdateScheduledStatusResponseBody,
-    UpdateTfaApplicationRequestBody, UpdateTfaApplicationResponseBody,
-    UpdateTfaMessageTemplateRequestBody, UpdateTfaMessageTemplateResponseBody,
-    VerifyPhoneNumberRequestBody, VerifyPhoneNumberResponseBody,
+    InboundReportsResponseBody, Log, LogsQueryParameters, LogsResponseBody, Report,
+    RescheduleQueryParameters, RescheduleRequestBody, RescheduleResponseBody,
+    ResendPinOverSmsRequestBody, ResendPinOverSmsResponseBody, ResendPinOverVoiceRequestBody,
+    ResendPinOverVoiceResponseBody, ScheduledQueryParameters, ScheduledResponseBody,
+    ScheduledStatusQueryParameters, ScheduledStatusResponseBody, SendBinaryRequestBody,
+    SendBinaryResponseBody, SendOverQueryParametersQueryParameters,
+    SendOverQueryParametersResponseBody, SendPinOverSmsQueryParameters, SendPinOverSmsRequestBody,
+    SendPinOverSmsResponseBody, SendPinOverVoiceRequestBody, SendPinOverVoiceResponseBody,
+    SendRequestBody, SendResponseBody, SentMessageDetails, TfaApplicationResponseBody,
+    TfaApplicationsResponseBody, TfaMessageTemplateResponseBody, TfaMessageTemplatesResponseBody,
+    TfaVerificationStatusQueryParameters, TfaVerificationStatusResponseBody,
+    UpdateScheduledStatusQueryParameters, UpdateScheduledStatusRequestBody,
+    UpdateScheduledStatusResponseBody, UpdateTfaApplicationRequestBody,
+    UpdateTfaApplicationResponseBody, UpdateTfaMessageTemplateRequestBody,
+    UpdateTfaMessageTemplateResponseBody, VerifyPhoneNumberRequestBody,
+    VerifyPhoneNumberResponseBody,
 };
 use crate::{
-    configuration::Configuration,
+    configuration::{Configuration, RetryPolicy},
     model::sms::{PreviewRequestBody, PreviewResponseBody},
 };
 
+#[cfg(tokio)]
+use tokio::runtime::Runtime;
+
+#[cfg(tokio)]
+use crate::api::blocking_runtime;
+#[cfg(tokio)]
+use crate::api::pages::BlockingIter;
+
+pub mod endpoints;
+
 pub const PATH_GET_DELIVERY_REPORTS: &str = "/sms/1/reports";
 pub const PATH_GET_INBOUND: &str = "/sms/1/inbox/reports";
 pub const PATH_GET_LOGS: &str = "/sms/1/logs";
@@ -59,20 +74,244 @@ pub const PATH_RESEND_PIN_OVER_VOICE: &str = "/2fa/2/pin/{pinId}/resend/voice";
 pub const PATH_VERIFY_PHONE_NUMBER: &str = "/2fa/2/pin/{pinId}/verify";
 pub const PATH_GET_TFA_VERIFICATION_STATUS: &str = "/2fa/2/applications/{appId}/verifications";
 
+// Fills in the bulk ID and the message ID of every destination that has none, and returns the
+// bulk ID.
+fn fill_send_ids(request_body: &mut SendRequestBody) -> String {
+    let bulk_id = match &request_body.bulk_id {
+        Some(bulk_id) => bulk_id.clone(),
+        None => generate_id(),
+    };
+
+    let destinations = request_body
+        .messages
+        .iter_mut()
+        .flat_map(|message| message.destinations.iter_mut().flatten());
+    for (index, destination) in destinations.enumerate() {
+        destination
+            .message_id
+            .get_or_insert_with(|| message_id(&bulk_id, index));
+    }
+
+    request_body.bulk_id = Some(bulk_id.clone());
+    bulk_id
+}
+
+// Same as `fill_send_ids()`, for binary messages.
+fn fill_binary_ids(request_body: &mut SendBinaryRequestBody) -> String {
+    let bulk_id = match &request_body.bulk_id {
+        Some(bulk_id) => bulk_id.clone(),
+        None => generate_id(),
+    };
+
+    let destinations = request_body
+        .messages
+        .iter_mut()
+        .flatten()
+        .flat_map(|message| message.destinations.iter_mut().flatten());
+    for (index, destination) in destinations.enumerate() {
+        destination
+            .message_id
+            .get_or_insert_with(|| message_id(&bulk_id, index));
+    }
+
+    request_body.bulk_id = Some(bulk_id.clone());
+    bulk_id
+}
+
 /// Main asynchronous client for the Infobip SMS channel.
 #[derive(Clone, Debug)]
 pub struct SmsClient {
     pub configuration: Configuration,
-    pub http_client: reqwest::Client,
+    pub transport: Arc<dyn Transport>,
 }
 
 impl SmsClient {
     /// Builds and returns a new asynchronous `SmsClient` with specified configuration.
     pub fn with_configuration(configuration: Configuration) -> Self {
+        let transport = default_transport(&configuration);
+        SmsClient::with_transport(configuration, transport)
+    }
+
+    /// Builds and returns a new asynchronous `SmsClient` with a specified configuration, sending
+    /// requests through a custom transport.
+    pub fn with_transport(configuration: Configuration, transport: Arc<dyn Transport>) -> Self {
         SmsClient {
             configuration,
-            http_client: reqwest::Client::new(),
+            transport,
+        }
+    }
+
+    /// Returns a copy of this client that sends every request exactly once, ignoring the retry
+    /// policy of its configuration. Use it for calls that must not be repeated.
+    pub fn without_retries(&self) -> Self {
+        let mut client = self.clone();
+        client
+            .configuration
+            .set_retry_policy(RetryPolicy::disabled());
+
+        client
+    }
+
+    /// Returns a copy of this client that sends the given correlation ID in the
+    /// `X-Correlation-Id` header of every request, e.g. to match a call with your own logs.
+    pub fn with_correlation_id(&self, correlation_id: impl Into<String>) -> Self {
+        let mut client = self.clone();
+        client.configuration.set_correlation_id(correlation_id);
+
+        client
+    }
+
+    /// Returns a copy of this client with idempotent sends enabled. See
+    /// `Configuration::set_idempotent_sends()`.
+    pub fn with_idempotent_sends(&self) -> Self {
+        let mut client = self.clone();
+        client.configuration.set_idempotent_sends(true);
+
+        client
+    }
+
+    /// Sends a request to an endpoint, which can be one of `api::sms::endpoints` or one defined in
+    /// your own code. See `api::endpoint`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use infobip_sdk::api::sms::endpoints::GetTfaApplications;
+    /// # use infobip_sdk::api::sms::SmsClient;
+    /// # use infobip_sdk::configuration::Configuration;
+    /// # use reqwest::StatusCode;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sms_client = SmsClient::with_configuration(Configuration::from_env_api_key()?);
+    ///
+    /// let response = sms_client.execute(GetTfaApplications).await?;
+    ///
+    /// assert_eq!(response.status, StatusCode::OK);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::execute",
+            skip_all,
+            fields(
+                path = endpoint.path_template(),
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
+    pub async fn execute<E: Endpoint>(
+        &self,
+        endpoint: E,
+    ) -> Result<SdkResponse<E::Response>, SdkError> {
+        self.request(endpoint).await
+    }
+
+    /// Sends a request to any path of the API, for endpoints the SDK does not model. The response
+    /// body is deserialized into `T`, e.g. `serde_json::Value`. See `api::raw`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use infobip_sdk::api::raw::RawRequest;
+    /// # use infobip_sdk::api::sms::SmsClient;
+    /// # use infobip_sdk::configuration::Configuration;
+    /// # use serde_json::Value;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sms_client = SmsClient::with_configuration(Configuration::from_env_api_key()?);
+    ///
+    /// let response = sms_client
+    ///     .raw::<Value>(RawRequest::get("/sms/1/logs").query("limit", 10))
+    ///     .await?;
+    ///
+    /// println!("{}", response.body);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::raw",
+            skip_all,
+            fields(
+                path = request.path(),
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
+    pub async fn raw<T: DeserializeOwned>(
+        &self,
+        request: RawRequest,
+    ) -> Result<SdkResponse<T>, SdkError> {
+        self.request(Raw::new(request)).await
+    }
+
+    // Sends a request to an endpoint, within the span of the calling method.
+    async fn request<E: Endpoint>(
+        &self,
+        endpoint: E,
+    ) -> Result<SdkResponse<E::Response>, SdkError> {
+        execute_endpoint(self.transport.as_ref(), &self.configuration, &endpoint).await
+    }
+
+    // Sends a message request whose IDs were filled in, looking its bulk up in the logs after a
+    // failure without a clear outcome.
+    async fn send_idempotent<E: Endpoint<Response = SendResponseBody>>(
+        &self,
+        endpoint: E,
+        bulk_id: &str,
+    ) -> Result<SdkResponse<SendResponseBody>, SdkError> {
+        let configuration = idempotency::without_retries(&self.configuration);
+        let configuration = &configuration;
+        let endpoint = &endpoint;
+
+        idempotency::send(
+            self.configuration.retry_policy(),
+            move || execute_endpoint(self.transport.as_ref(), configuration, endpoint),
+            move || self.logged_bulk(bulk_id),
+        )
+        .await
+    }
+
+    // Returns the response of a sent bulk, rebuilt from its message logs, or `None` if no message
+    // of the bulk was logged.
+    async fn logged_bulk(
+        &self,
+        bulk_id: &str,
+    ) -> Result<Option<SdkResponse<SendResponseBody>>, SdkError> {
+        let query_parameters = LogsQueryParameters {
+            bulk_id: Some(bulk_id.to_string()),
+            limit: Some(1000),
+            ..Default::default()
+        };
+        let response = self.logs(query_parameters).await?;
+
+        let logs = response.body.results.unwrap_or_default();
+        if logs.is_empty() {
+            return Ok(None);
         }
+
+        let messages = logs
+            .into_iter()
+            .map(|log| SentMessageDetails {
+                message_id: log.message_id,
+                status: log.status,
+                to: log.to,
+            })
+            .collect();
+
+        Ok(Some(SdkResponse {
+            body: SendResponseBody {
+                bulk_id: Some(bulk_id.to_string()),
+                messages: Some(messages),
+            },
+            status: response.status,
+            metadata: response.metadata,
+        }))
     }
 
     /// Check how different message configurations will affect your message text, number of
@@ -99,31 +338,22 @@ impl SmsClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::preview",
+            skip_all,
+            fields(
+                path = PATH_PREVIEW,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn preview(
         &self,
         request_body: PreviewRequestBody,
     ) -> Result<SdkResponse<PreviewResponseBody>, SdkError> {
-        let response = send_valid_json_request(
-            &self.http_client,
-            &self.configuration,
-            request_body,
-            HashMap::new(),
-            reqwest::Method::POST,
-            PATH_PREVIEW,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(Preview::new(request_body)).await
     }
 
     ///  delivery reports for recently sent SMS messages.
@@ -152,43 +382,80 @@ impl SmsClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::delivery_reports",
+            skip_all,
+            fields(
+                path = PATH_GET_DELIVERY_REPORTS,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn delivery_reports(
         &self,
         query_parameters: DeliveryReportsQueryParameters,
     ) -> Result<SdkResponse<DeliveryReportsResponseBody>, SdkError> {
-        query_parameters.validate()?;
-
-        let mut parameters_map = HashMap::<String, String>::new();
-        if let Some(bulk_id) = query_parameters.bulk_id {
-            parameters_map.insert("bulkId".to_string(), bulk_id);
-        }
-        if let Some(message_id) = query_parameters.message_id {
-            parameters_map.insert("messageId".to_string(), message_id);
-        }
-        if let Some(limit) = query_parameters.limit {
-            parameters_map.insert("limit".to_string(), limit.to_string());
-        }
+        self.request(GetDeliveryReports::new(query_parameters))
+            .await
+    }
 
-        let response = send_no_body_request(
-            &self.http_client,
-            &self.configuration,
-            parameters_map,
-            reqwest::Method::GET,
-            PATH_GET_DELIVERY_REPORTS,
+    /// Streams delivery reports, requesting batches until no new reports are returned.
+    ///
+    /// Every request is sent with the same query parameters, as the API returns each report only
+    /// once. Reports with a message ID that was already returned are skipped, and the stream ends
+    /// after `max_results` reports or the first error.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use futures_util::StreamExt;
+    /// # use infobip_sdk::api::sms::SmsClient;
+    /// # use infobip_sdk::configuration::Configuration;
+    /// # use infobip_sdk::model::sms::DeliveryReportsQueryParameters;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sms_client = SmsClient::with_configuration(Configuration::from_env_api_key()?);
+    ///
+    /// let reports = sms_client.delivery_reports_stream(DeliveryReportsQueryParameters::new(), 5000);
+    /// futures_util::pin_mut!(reports);
+    ///
+    /// while let Some(report) = reports.next().await {
+    ///     println!("{:?}", report?.status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn delivery_reports_stream(
+        &self,
+        query_parameters: DeliveryReportsQueryParameters,
+        max_results: usize,
+    ) -> impl Stream<Item = Result<Report, SdkError>> + '_ {
+        let key = |report: &Report| report.message_id.clone();
+
+        pages::walk(
+            query_parameters,
+            max_results,
+            key,
+            move |query_parameters| async move {
+                let next = query_parameters.clone();
+                let reports = self
+                    .delivery_reports(query_parameters)
+                    .await?
+                    .body
+                    .results
+                    .unwrap_or_default();
+
+                let full = next
+                    .limit
+                    .map_or(true, |limit| reports.len() >= limit as usize);
+                Ok(Page {
+                    next: (full && !reports.is_empty()).then_some(next),
+                    results: reports,
+                })
+            },
         )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
     }
 
     /// Send a single, or multiple SMS messages to one or many destinations.
@@ -224,31 +491,29 @@ impl SmsClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::send",
+            skip_all,
+            fields(
+                path = PATH_SEND,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn send(
         &self,
-        request_body: SendRequestBody,
+        mut request_body: SendRequestBody,
     ) -> Result<SdkResponse<SendResponseBody>, SdkError> {
-        let response = send_valid_json_request(
-            &self.http_client,
-            &self.configuration,
-            request_body,
-            HashMap::new(),
-            reqwest::Method::POST,
-            PATH_SEND,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
+        if self.configuration.idempotent_sends() {
+            let bulk_id = fill_send_ids(&mut request_body);
+            return self
+                .send_idempotent(SendSms::new(request_body), &bulk_id)
+                .await;
         }
+
+        self.request(SendSms::new(request_body)).await
     }
 
     /// Send single or multiple binary messages to one or more destination addresses.
@@ -278,31 +543,29 @@ impl SmsClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::send_binary",
+            skip_all,
+            fields(
+                path = PATH_SEND_BINARY,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn send_binary(
         &self,
-        request_body: SendBinaryRequestBody,
+        mut request_body: SendBinaryRequestBody,
     ) -> Result<SdkResponse<SendBinaryResponseBody>, SdkError> {
-        let response = send_valid_json_request(
-            &self.http_client,
-            &self.configuration,
-            request_body,
-            HashMap::new(),
-            reqwest::Method::POST,
-            PATH_SEND_BINARY,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
+        if self.configuration.idempotent_sends() {
+            let bulk_id = fill_binary_ids(&mut request_body);
+            return self
+                .send_idempotent(SendBinarySms::new(request_body), &bulk_id)
+                .await;
         }
+
+        self.request(SendBinarySms::new(request_body)).await
     }
 
     /// See all scheduled messages and their scheduled date and time. To schedule a message, use
@@ -327,35 +590,22 @@ impl SmsClient {
     /// # Ok(())
     /// }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::scheduled",
+            skip_all,
+            fields(
+                path = PATH_GET_SCHEDULED,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn scheduled(
         &self,
         query_parameters: ScheduledQueryParameters,
     ) -> Result<SdkResponse<ScheduledResponseBody>, SdkError> {
-        query_parameters.validate()?;
-
-        let parameters_map =
-            HashMap::<String, String>::from([("bulkId".to_string(), query_parameters.bulk_id)]);
-
-        let response = send_no_body_request(
-            &self.http_client,
-            &self.configuration,
-            parameters_map,
-            reqwest::Method::GET,
-            PATH_GET_SCHEDULED,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(GetScheduled::new(query_parameters)).await
     }
 
     /// Use this method for displaying logs for example in the user interface. Available are the
@@ -381,64 +631,103 @@ impl SmsClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::logs",
+            skip_all,
+            fields(
+                path = PATH_GET_LOGS,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn logs(
         &self,
         query_parameters: LogsQueryParameters,
     ) -> Result<SdkResponse<LogsResponseBody>, SdkError> {
-        query_parameters.validate()?;
-
-        let mut parameters_map = HashMap::<String, String>::new();
-        if let Some(from) = query_parameters.from {
-            parameters_map.insert("from".to_string(), from);
-        }
-        if let Some(to) = query_parameters.to {
-            parameters_map.insert("to".to_string(), to);
-        }
-        if let Some(bulk_id) = query_parameters.bulk_id {
-            parameters_map.insert("bulkId".to_string(), bulk_id);
-        }
-        if let Some(message_id) = query_parameters.message_id {
-            parameters_map.insert("messageId".to_string(), message_id);
-        }
-        if let Some(general_status) = query_parameters.general_status {
-            parameters_map.insert("generalStatus".to_string(), general_status);
-        }
-        if let Some(sent_since) = query_parameters.sent_since {
-            parameters_map.insert("sentSince".to_string(), sent_since);
-        }
-        if let Some(sent_until) = query_parameters.sent_until {
-            parameters_map.insert("sentUntil".to_string(), sent_until);
-        }
-        if let Some(limit) = query_parameters.limit {
-            parameters_map.insert("limit".to_string(), limit.to_string());
-        }
-        if let Some(mcc) = query_parameters.mcc {
-            parameters_map.insert("mcc".to_string(), mcc);
-        }
-        if let Some(mnc) = query_parameters.mnc {
-            parameters_map.insert("mnc".to_string(), mnc);
-        }
+        self.request(GetLogs::new(query_parameters)).await
+    }
 
-        let response = send_no_body_request(
-            &self.http_client,
-            &self.configuration,
-            parameters_map,
-            reqwest::Method::GET,
-            PATH_GET_LOGS,
+    /// Streams the logs of sent messages, walking back in time from the newest ones.
+    ///
+    /// The first request is sent with the given query parameters. Every next one asks for the logs
+    /// sent until the earliest log of the previous one, and logs with a message ID that was
+    /// already returned are skipped. If a request returns only logs sent in the same millisecond,
+    /// the logs of that millisecond are requested on their own, with a `limit` of 1000, the most
+    /// the API allows, before the stream moves on to the logs sent before them. Logs beyond the
+    /// first 1000 of one millisecond can not be listed by time, and are skipped. The stream ends
+    /// once a request returns fewer logs than `limit`, after `max_results` logs or after the first
+    /// error. Logs are only kept for the last 48 hours.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use futures_util::StreamExt;
+    /// # use infobip_sdk::api::sms::SmsClient;
+    /// # use infobip_sdk::configuration::Configuration;
+    /// # use infobip_sdk::model::sms::LogsQueryParameters;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sms_client = SmsClient::with_configuration(Configuration::from_env_api_key()?);
+    ///
+    /// let mut query_parameters = LogsQueryParameters::new();
+    /// query_parameters.limit = Some(1000);
+    ///
+    /// let logs = sms_client.logs_stream(query_parameters, 5000);
+    /// futures_util::pin_mut!(logs);
+    ///
+    /// while let Some(log) = logs.next().await {
+    ///     println!("{:?}", log?.message_id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn logs_stream(
+        &self,
+        query_parameters: LogsQueryParameters,
+        max_results: usize,
+    ) -> impl Stream<Item = Result<Log, SdkError>> + '_ {
+        let key = |log: &Log| log.message_id.clone();
+        let window = |query_parameters: &LogsQueryParameters| Window {
+            sent_since: query_parameters.sent_since.clone(),
+            sent_until: query_parameters.sent_until.clone(),
+            limit: query_parameters.limit,
+        };
+        let first = window(&query_parameters);
+
+        pages::walk(
+            query_parameters,
+            max_results,
+            key,
+            move |query_parameters| {
+                let first = first.clone();
+                async move {
+                    let current = window(&query_parameters);
+                    let next = query_parameters.clone();
+                    let logs = self
+                        .logs(query_parameters)
+                        .await?
+                        .body
+                        .results
+                        .unwrap_or_default();
+
+                    let sent_at = logs.iter().map(|log| log.sent_at.as_deref());
+                    let next = pages::next_window(&first, &current, sent_at).map(|window| {
+                        LogsQueryParameters {
+                            sent_since: window.sent_since,
+                            sent_until: window.sent_until,
+                            limit: window.limit,
+                            ..next
+                        }
+                    });
+                    Ok(Page {
+                        next,
+                        results: logs,
+                    })
+                }
+            },
         )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
     }
 
     /// If for some reason you are unable to receive incoming SMS to the endpoint of your choice
@@ -465,37 +754,22 @@ impl SmsClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::inbound_reports",
+            skip_all,
+            fields(
+                path = PATH_GET_INBOUND,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn inbound_reports(
         &self,
         query_parameters: InboundReportsQueryParameters,
     ) -> Result<SdkResponse<InboundReportsResponseBody>, SdkError> {
-        query_parameters.validate()?;
-
-        let mut parameters_map = HashMap::<String, String>::new();
-        if let Some(limit) = query_parameters.limit {
-            parameters_map.insert("limit".to_string(), limit.to_string());
-        }
-
-        let response = send_no_body_request(
-            &self.http_client,
-            &self.configuration,
-            parameters_map,
-            reqwest::Method::GET,
-            PATH_GET_INBOUND,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(GetInboundReports::new(query_parameters)).await
     }
 
     /// All message parameters of the message can be defined in the query string. Use this method
@@ -525,100 +799,23 @@ impl SmsClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::send_over_query_parameters",
+            skip_all,
+            fields(
+                path = PATH_SEND_OVER_QUERY_PARAMS,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn send_over_query_parameters(
         &self,
         query_parameters: SendOverQueryParametersQueryParameters,
     ) -> Result<SdkResponse<SendOverQueryParametersResponseBody>, SdkError> {
-        query_parameters.validate()?;
-
-        let mut parameters_map = HashMap::<String, String>::new();
-        parameters_map.insert("username".to_string(), query_parameters.username);
-        parameters_map.insert("password".to_string(), query_parameters.password);
-        parameters_map.insert("to".to_string(), query_parameters.to.join(","));
-
-        if let Some(bulk_id) = query_parameters.bulk_id {
-            parameters_map.insert("bulkId".to_string(), bulk_id);
-        }
-        if let Some(from) = query_parameters.from {
-            parameters_map.insert("from".to_string(), from);
-        }
-        if let Some(text) = query_parameters.text {
-            parameters_map.insert("text".to_string(), text);
-        }
-        if let Some(flash) = query_parameters.flash {
-            parameters_map.insert("flash".to_string(), flash.to_string());
-        }
-        if let Some(transliteration) = query_parameters.transliteration {
-            parameters_map.insert("transliteration".to_string(), transliteration);
-        }
-        if let Some(language_code) = query_parameters.language_code {
-            parameters_map.insert("languageCode".to_string(), language_code);
-        }
-        if let Some(intermediate_report) = query_parameters.intermediate_report {
-            parameters_map.insert(
-                "intermediateReport".to_string(),
-                intermediate_report.to_string(),
-            );
-        }
-        if let Some(notify_url) = query_parameters.notify_url {
-            parameters_map.insert("notifyUrl".to_string(), notify_url);
-        }
-        if let Some(notify_content_type) = query_parameters.notify_content_type {
-            parameters_map.insert("notifyContentType".to_string(), notify_content_type);
-        }
-        if let Some(callback_data) = query_parameters.callback_data {
-            parameters_map.insert("callbackData".to_string(), callback_data);
-        }
-        if let Some(validity_period) = query_parameters.validity_period {
-            parameters_map.insert("validityPeriod".to_string(), validity_period.to_string());
-        }
-        if let Some(send_at) = query_parameters.send_at {
-            parameters_map.insert("sendAt".to_string(), send_at);
-        }
-        if let Some(track) = query_parameters.track {
-            parameters_map.insert("track".to_string(), track);
-        }
-        if let Some(process_key) = query_parameters.process_key {
-            parameters_map.insert("processKey".to_string(), process_key);
-        }
-        if let Some(tracking_type) = query_parameters.tracking_type {
-            parameters_map.insert("trackingType".to_string(), tracking_type);
-        }
-        if let Some(india_dlt_content_template_id) = query_parameters.india_dlt_content_template_id
-        {
-            parameters_map.insert(
-                "indiaDltContentTemplateId".to_string(),
-                india_dlt_content_template_id,
-            );
-        }
-        if let Some(india_dlt_principal_entity_id) = query_parameters.india_dlt_principal_entity_id
-        {
-            parameters_map.insert(
-                "indiaDltPrincipalEntityId".to_string(),
-                india_dlt_principal_entity_id,
-            );
-        }
-
-        let response = send_no_body_request(
-            &self.http_client,
-            &self.configuration,
-            parameters_map,
-            reqwest::Method::GET,
-            PATH_SEND_OVER_QUERY_PARAMS,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(SendSmsOverQueryParameters::new(query_parameters))
+            .await
     }
 
     /// Change the date and time of already scheduled messages. To schedule a message, use the
@@ -644,37 +841,24 @@ impl SmsClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::reschedule",
+            skip_all,
+            fields(
+                path = PATH_RESCHEDULE,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn reschedule(
         &self,
         query_parameters: RescheduleQueryParameters,
         request_body: RescheduleRequestBody,
     ) -> Result<SdkResponse<RescheduleResponseBody>, SdkError> {
-        query_parameters.validate()?;
-
-        let parameters_map =
-            HashMap::<String, String>::from([("bulkId".to_string(), query_parameters.bulk_id)]);
-
-        let response = send_valid_json_request(
-            &self.http_client,
-            &self.configuration,
-            request_body,
-            parameters_map,
-            reqwest::Method::PUT,
-            PATH_RESCHEDULE,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(Reschedule::new(query_parameters, request_body))
+            .await
     }
 
     ///  the status of scheduled messages.
@@ -698,35 +882,23 @@ impl SmsClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::scheduled_status",
+            skip_all,
+            fields(
+                path = PATH_GET_SCHEDULED_STATUS,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn scheduled_status(
         &self,
         query_parameters: ScheduledStatusQueryParameters,
     ) -> Result<SdkResponse<ScheduledStatusResponseBody>, SdkError> {
-        query_parameters.validate()?;
-
-        let parameters_map =
-            HashMap::<String, String>::from([("bulkId".to_string(), query_parameters.bulk_id)]);
-
-        let response = send_no_body_request(
-            &self.http_client,
-            &self.configuration,
-            parameters_map,
-            reqwest::Method::GET,
-            PATH_GET_SCHEDULED_STATUS,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(GetScheduledStatus::new(query_parameters))
+            .await
     }
 
     /// Change status or completely cancel sending of scheduled messages. To schedule a message,
@@ -752,37 +924,24 @@ impl SmsClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::update_scheduled_status",
+            skip_all,
+            fields(
+                path = PATH_UPDATE_SCHEDULED_STATUS,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn update_scheduled_status(
         &self,
         query_parameters: UpdateScheduledStatusQueryParameters,
         request_body: UpdateScheduledStatusRequestBody,
     ) -> Result<SdkResponse<UpdateScheduledStatusResponseBody>, SdkError> {
-        query_parameters.validate()?;
-
-        let parameters_map =
-            HashMap::<String, String>::from([("bulkId".to_string(), query_parameters.bulk_id)]);
-
-        let response = send_valid_json_request(
-            &self.http_client,
-            &self.configuration,
-            request_body,
-            parameters_map,
-            reqwest::Method::PUT,
-            PATH_UPDATE_SCHEDULED_STATUS,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(UpdateScheduledStatus::new(query_parameters, request_body))
+            .await
     }
 
     ///  a list of your 2FA applications.
@@ -802,29 +961,21 @@ impl SmsClient {
     /// assert_eq!(response.status, StatusCode::OK);
     /// # Ok(())
     /// # }
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::tfa_applications",
+            skip_all,
+            fields(
+                path = PATH_GET_TFA_APPLICATIONS,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn tfa_applications(
         &self,
     ) -> Result<SdkResponse<TfaApplicationsResponseBody>, SdkError> {
-        let response = send_no_body_request(
-            &self.http_client,
-            &self.configuration,
-            HashMap::new(),
-            reqwest::Method::GET,
-            PATH_GET_TFA_APPLICATIONS,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(GetTfaApplications).await
     }
 
     /// Create and configure a new 2FA application.
@@ -846,31 +997,22 @@ impl SmsClient {
     /// assert_eq!(response.status, StatusCode::CREATED);
     /// # Ok(())
     /// # }
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::create_tfa_application",
+            skip_all,
+            fields(
+                path = PATH_CREATE_TFA_APPLICATION,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn create_tfa_application(
         &self,
         request_body: CreateTfaApplicationRequestBody,
     ) -> Result<SdkResponse<CreateTfaApplicationResponseBody>, SdkError> {
-        let response = send_valid_json_request(
-            &self.http_client,
-            &self.configuration,
-            request_body,
-            HashMap::new(),
-            reqwest::Method::POST,
-            PATH_CREATE_TFA_APPLICATION,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(CreateTfaApplication::new(request_body)).await
     }
 
     ///  a single 2FA application to see its configuration details.
@@ -891,32 +1033,22 @@ impl SmsClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::tfa_application",
+            skip_all,
+            fields(
+                path = PATH_GET_TFA_APPLICATION,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn tfa_application(
         &self,
         application_id: &str,
     ) -> Result<SdkResponse<TfaApplicationResponseBody>, SdkError> {
-        let path = &PATH_GET_TFA_APPLICATION.replace("{appId}", application_id);
-
-        let response = send_no_body_request(
-            &self.http_client,
-            &self.configuration,
-            HashMap::new(),
-            reqwest::Method::GET,
-            path,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(GetTfaApplication::new(application_id)).await
     }
 
     /// Change configuration options for your existing 2FA application.
@@ -939,34 +1071,24 @@ impl SmsClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::update_tfa_application",
+            skip_all,
+            fields(
+                path = PATH_UPDATE_TFA_APPLICATION,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn update_tfa_application(
         &self,
         application_id: &str,
         request_body: UpdateTfaApplicationRequestBody,
     ) -> Result<SdkResponse<UpdateTfaApplicationResponseBody>, SdkError> {
-        let path = &PATH_UPDATE_TFA_APPLICATION.replace("{appId}", application_id);
-
-        let response = send_valid_json_request(
-            &self.http_client,
-            &self.configuration,
-            request_body,
-            HashMap::new(),
-            reqwest::Method::PUT,
-            path,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(UpdateTfaApplication::new(application_id, request_body))
+            .await
     }
 
     ///  all message templates in a 2FA application.
@@ -987,32 +1109,23 @@ impl SmsClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::tfa_message_templates",
+            skip_all,
+            fields(
+                path = PATH_GET_TFA_MESSAGE_TEMPLATES,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn tfa_message_templates(
         &self,
         application_id: &str,
     ) -> Result<SdkResponse<TfaMessageTemplatesResponseBody>, SdkError> {
-        let path = &PATH_GET_TFA_MESSAGE_TEMPLATES.replace("{appId}", application_id);
-
-        let response = send_no_body_request(
-            &self.http_client,
-            &self.configuration,
-            HashMap::new(),
-            reqwest::Method::GET,
-            path,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(GetTfaMessageTemplates::new(application_id))
+            .await
     }
 
     /// Create one or more message templates where your PIN will be dynamically included when you send the PIN message.
@@ -1036,34 +1149,24 @@ impl SmsClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::create_tfa_message_template",
+            skip_all,
+            fields(
+                path = PATH_CREATE_TFA_MESSAGE_TEMPLATE,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn create_tfa_message_template(
         &self,
         application_id: &str,
         request_body: CreateTfaMessageTemplateRequestBody,
     ) -> Result<SdkResponse<CreateTfaMessageTemplateResponseBody>, SdkError> {
-        let path = &PATH_CREATE_TFA_MESSAGE_TEMPLATE.replace("{appId}", application_id);
-
-        let response = send_valid_json_request(
-            &self.http_client,
-            &self.configuration,
-            request_body,
-            HashMap::new(),
-            reqwest::Method::POST,
-            path,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(CreateTfaMessageTemplate::new(application_id, request_body))
+            .await
     }
 
     ///  a single 2FA message template from an application to see its configuration details.
@@ -1085,35 +1188,24 @@ impl SmsClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::tfa_message_template",
+            skip_all,
+            fields(
+                path = PATH_GET_TFA_MESSAGE_TEMPLATE,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn tfa_message_template(
         &self,
         application_id: &str,
         template_id: &str,
     ) -> Result<SdkResponse<TfaMessageTemplateResponseBody>, SdkError> {
-        let path = &PATH_GET_TFA_MESSAGE_TEMPLATE
-            .replace("{appId}", application_id)
-            .replace("{msgId}", template_id);
-
-        let response = send_no_body_request(
-            &self.http_client,
-            &self.configuration,
-            HashMap::new(),
-            reqwest::Method::GET,
-            path,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(GetTfaMessageTemplate::new(application_id, template_id))
+            .await
     }
 
     /// Change configuration options for your existing 2FA application message template.
@@ -1139,37 +1231,29 @@ impl SmsClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::update_tfa_message_template",
+            skip_all,
+            fields(
+                path = PATH_UPDATE_TFA_MESSAGE_TEMPLATE,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn update_tfa_message_template(
         &self,
         application_id: &str,
         template_id: &str,
         request_body: UpdateTfaMessageTemplateRequestBody,
     ) -> Result<SdkResponse<UpdateTfaMessageTemplateResponseBody>, SdkError> {
-        let path = &PATH_UPDATE_TFA_MESSAGE_TEMPLATE
-            .replace("{appId}", application_id)
-            .replace("{msgId}", template_id);
-
-        let response = send_valid_json_request(
-            &self.http_client,
-            &self.configuration,
+        self.request(UpdateTfaMessageTemplate::new(
+            application_id,
+            template_id,
             request_body,
-            HashMap::new(),
-            reqwest::Method::PUT,
-            path,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        ))
+        .await
     }
 
     /// Send a PIN code over SMS using a previously created message template.
@@ -1194,38 +1278,24 @@ impl SmsClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::send_pin_over_sms",
+            skip_all,
+            fields(
+                path = PATH_SEND_PIN_OVER_SMS,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn send_pin_over_sms(
         &self,
         query_parameters: SendPinOverSmsQueryParameters,
         request_body: SendPinOverSmsRequestBody,
     ) -> Result<SdkResponse<SendPinOverSmsResponseBody>, SdkError> {
-        query_parameters.validate()?;
-        let mut parameters_map = HashMap::new();
-        if let Some(nc_needed) = query_parameters.nc_needed {
-            parameters_map.insert("ncNeeded".to_string(), nc_needed.to_string());
-        }
-
-        let response = send_valid_json_request(
-            &self.http_client,
-            &self.configuration,
-            request_body,
-            parameters_map,
-            reqwest::Method::POST,
-            PATH_SEND_PIN_OVER_SMS,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(SendPinOverSms::new(query_parameters, request_body))
+            .await
     }
 
     /// Resend the same (previously sent) PIN code over SMS.
@@ -1249,34 +1319,24 @@ impl SmsClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::resend_pin_over_sms",
+            skip_all,
+            fields(
+                path = PATH_RESEND_PIN_OVER_SMS,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn resend_pin_over_sms(
         &self,
         pin_id: &str,
         request_body: ResendPinOverSmsRequestBody,
     ) -> Result<SdkResponse<ResendPinOverSmsResponseBody>, SdkError> {
-        let path = &PATH_RESEND_PIN_OVER_SMS.replace("{pinId}", pin_id);
-
-        let response = send_valid_json_request(
-            &self.http_client,
-            &self.configuration,
-            request_body,
-            HashMap::new(),
-            reqwest::Method::POST,
-            path,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(ResendPinOverSms::new(pin_id, request_body))
+            .await
     }
 
     /// Send a PIN code over Voice using previously created message template.
@@ -1299,31 +1359,22 @@ impl SmsClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::send_pin_over_voice",
+            skip_all,
+            fields(
+                path = PATH_SEND_PIN_OVER_VOICE,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn send_pin_over_voice(
         &self,
         request_body: SendPinOverVoiceRequestBody,
     ) -> Result<SdkResponse<SendPinOverVoiceResponseBody>, SdkError> {
-        let response = send_valid_json_request(
-            &self.http_client,
-            &self.configuration,
-            request_body,
-            HashMap::new(),
-            reqwest::Method::POST,
-            PATH_SEND_PIN_OVER_VOICE,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(SendPinOverVoice::new(request_body)).await
     }
 
     /// Resend the same (previously sent) PIN code over Voice.
@@ -1347,34 +1398,24 @@ impl SmsClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::resend_pin_over_voice",
+            skip_all,
+            fields(
+                path = PATH_RESEND_PIN_OVER_VOICE,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn resend_pin_over_voice(
         &self,
         pin_id: &str,
         request_body: ResendPinOverVoiceRequestBody,
     ) -> Result<SdkResponse<ResendPinOverVoiceResponseBody>, SdkError> {
-        let path = &PATH_RESEND_PIN_OVER_VOICE.replace("{pinId}", pin_id);
-
-        let response = send_valid_json_request(
-            &self.http_client,
-            &self.configuration,
-            request_body,
-            HashMap::new(),
-            reqwest::Method::POST,
-            path,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(ResendPinOverVoice::new(pin_id, request_body))
+            .await
     }
 
     /// Verify a phone number to confirm successful 2FA authentication.
@@ -1398,34 +1439,24 @@ impl SmsClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::verify_phone_number",
+            skip_all,
+            fields(
+                path = PATH_VERIFY_PHONE_NUMBER,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn verify_phone_number(
         &self,
         pin_id: &str,
         request_body: VerifyPhoneNumberRequestBody,
     ) -> Result<SdkResponse<VerifyPhoneNumberResponseBody>, SdkError> {
-        let path = &PATH_VERIFY_PHONE_NUMBER.replace("{pinId}", pin_id);
-
-        let response = send_valid_json_request(
-            &self.http_client,
-            &self.configuration,
-            request_body,
-            HashMap::new(),
-            reqwest::Method::POST,
-            path,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(VerifyPhoneNumber::new(pin_id, request_body))
+            .await
     }
 
     /// Check if a phone number is already verified for a specific 2FA application.
@@ -1448,90 +1479,368 @@ impl SmsClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SmsClient::tfa_verification_status",
+            skip_all,
+            fields(
+                path = PATH_GET_TFA_VERIFICATION_STATUS,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn tfa_verification_status(
         &self,
         app_id: &str,
         query_parameters: TfaVerificationStatusQueryParameters,
     ) -> Result<SdkResponse<TfaVerificationStatusResponseBody>, SdkError> {
-        let path = &PATH_GET_TFA_VERIFICATION_STATUS.replace("{appId}", app_id);
-
-        query_parameters.validate()?;
-        let mut parameters_map = HashMap::new();
-        parameters_map.insert("msisdn".to_string(), query_parameters.msisdn);
-        if let Some(verified) = query_parameters.verified {
-            parameters_map.insert("verified".to_string(), verified.to_string());
-        }
-        if let Some(sent) = query_parameters.sent {
-            parameters_map.insert("sent".to_string(), sent.to_string());
-        }
-
-        let response = send_no_body_request(
-            &self.http_client,
-            &self.configuration,
-            HashMap::new(),
-            reqwest::Method::GET,
-            path,
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(GetTfaVerificationStatus::new(app_id, query_parameters))
+            .await
     }
 }
 
 /// Blocking client for the Infobip SMS channel.
+///
+/// Calls the asynchronous `SmsClient` on a runtime owned by the client, so both share request
+/// building and response handling.
+#[cfg(tokio)]
+#[derive(Clone, Debug)]
 pub struct BlockingSmsClient {
-    configuration: Configuration,
-    client: reqwest::blocking::Client,
+    client: SmsClient,
+    runtime: Arc<Runtime>,
 }
 
+#[cfg(tokio)]
 impl BlockingSmsClient {
     /// Builds and returns a new `BlockingSmsClient` with a specified configuration.
     pub fn with_configuration(configuration: Configuration) -> BlockingSmsClient {
+        let transport = default_transport(&configuration);
+        BlockingSmsClient::with_transport(configuration, transport)
+    }
+
+    /// Builds and returns a new `BlockingSmsClient` with a specified configuration and a custom
+    /// transport to send requests.
+    ///
+    /// # Panics
+    /// Panics if the runtime used to drive requests can not be created.
+    pub fn with_transport(
+        configuration: Configuration,
+        transport: Arc<dyn Transport>,
+    ) -> BlockingSmsClient {
         BlockingSmsClient {
-            configuration,
-            client: reqwest::blocking::Client::new(),
+            client: SmsClient::with_transport(configuration, transport),
+            runtime: blocking_runtime(),
         }
     }
 
-    /// Check how different message configurations will affect your message text, number of
-    /// characters and message parts. This is the blocking version.
+    /// Returns a copy of this client that sends every request exactly once, ignoring the retry
+    /// policy of its configuration. Use it for calls that must not be repeated.
+    pub fn without_retries(&self) -> Self {
+        BlockingSmsClient {
+            client: self.client.without_retries(),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// Returns a copy of this client that sends the given correlation ID in the
+    /// `X-Correlation-Id` header of every request, e.g. to match a call with your own logs.
+    pub fn with_correlation_id(&self, correlation_id: impl Into<String>) -> Self {
+        BlockingSmsClient {
+            client: self.client.with_correlation_id(correlation_id),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// Returns a copy of this client with idempotent sends enabled. See
+    /// `Configuration::set_idempotent_sends()`.
+    pub fn with_idempotent_sends(&self) -> Self {
+        BlockingSmsClient {
+            client: self.client.with_idempotent_sends(),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// Blocking version of `SmsClient::execute()`.
+    pub fn execute<E: Endpoint>(&self, endpoint: E) -> Result<SdkResponse<E::Response>, SdkError> {
+        self.runtime.block_on(self.client.execute(endpoint))
+    }
+
+    /// Blocking version of `SmsClient::raw()`.
+    pub fn raw<T: DeserializeOwned>(
+        &self,
+        request: RawRequest,
+    ) -> Result<SdkResponse<T>, SdkError> {
+        self.runtime.block_on(self.client.raw(request))
+    }
+
+    /// Blocking version of `SmsClient::preview()`.
     pub fn preview(
         &self,
         request_body: PreviewRequestBody,
     ) -> Result<SdkResponse<PreviewResponseBody>, SdkError> {
-        let response = send_blocking_valid_json_request(
-            &self.client,
-            &self.configuration,
-            request_body,
-            reqwest::Method::POST,
-            PATH_PREVIEW,
-        )?;
+        self.runtime.block_on(self.client.preview(request_body))
+    }
 
-        let status = response.status();
-        let text = response.text()?;
+    /// Blocking version of `SmsClient::delivery_reports()`.
+    pub fn delivery_reports(
+        &self,
+        query_parameters: DeliveryReportsQueryParameters,
+    ) -> Result<SdkResponse<DeliveryReportsResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.delivery_reports(query_parameters))
+    }
 
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            let api_error = ApiError {
-                details: serde_json::from_str(&text)?,
-                status,
-            };
+    /// Blocking version of `SmsClient::delivery_reports_stream()`.
+    pub fn delivery_reports_iter(
+        &self,
+        query_parameters: DeliveryReportsQueryParameters,
+        max_results: usize,
+    ) -> impl Iterator<Item = Result<Report, SdkError>> + '_ {
+        BlockingIter::new(
+            &self.runtime,
+            self.client
+                .delivery_reports_stream(query_parameters, max_results),
+        )
+    }
 
-            Err(SdkError::ApiRequestError(api_error))
-        }
+    /// Blocking version of `SmsClient::send()`.
+    pub fn send(
+        &self,
+        request_body: SendRequestBody,
+    ) -> Result<SdkResponse<SendResponseBody>, SdkError> {
+        self.runtime.block_on(self.client.send(request_body))
+    }
+
+    /// Blocking version of `SmsClient::send_binary()`.
+    pub fn send_binary(
+        &self,
+        request_body: SendBinaryRequestBody,
+    ) -> Result<SdkResponse<SendBinaryResponseBody>, SdkError> {
+        self.runtime.block_on(self.client.send_binary(request_body))
+    }
+
+    /// Blocking version of `SmsClient::scheduled()`.
+    pub fn scheduled(
+        &self,
+        query_parameters: ScheduledQueryParameters,
+    ) -> Result<SdkResponse<ScheduledResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.scheduled(query_parameters))
+    }
+
+    /// Blocking version of `SmsClient::logs()`.
+    pub fn logs(
+        &self,
+        query_parameters: LogsQueryParameters,
+    ) -> Result<SdkResponse<LogsResponseBody>, SdkError> {
+        self.runtime.block_on(self.client.logs(query_parameters))
+    }
+
+    /// Blocking version of `SmsClient::logs_stream()`.
+    pub fn logs_iter(
+        &self,
+        query_parameters: LogsQueryParameters,
+        max_results: usize,
+    ) -> impl Iterator<Item = Result<Log, SdkError>> + '_ {
+        BlockingIter::new(
+            &self.runtime,
+            self.client.logs_stream(query_parameters, max_results),
+        )
+    }
+
+    /// Blocking version of `SmsClient::inbound_reports()`.
+    pub fn inbound_reports(
+        &self,
+        query_parameters: InboundReportsQueryParameters,
+    ) -> Result<SdkResponse<InboundReportsResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.inbound_reports(query_parameters))
+    }
+
+    /// Blocking version of `SmsClient::send_over_query_parameters()`.
+    pub fn send_over_query_parameters(
+        &self,
+        query_parameters: SendOverQueryParametersQueryParameters,
+    ) -> Result<SdkResponse<SendOverQueryParametersResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.send_over_query_parameters(query_parameters))
+    }
+
+    /// Blocking version of `SmsClient::reschedule()`.
+    pub fn reschedule(
+        &self,
+        query_parameters: RescheduleQueryParameters,
+        request_body: RescheduleRequestBody,
+    ) -> Result<SdkResponse<RescheduleResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.reschedule(query_parameters, request_body))
+    }
+
+    /// Blocking version of `SmsClient::scheduled_status()`.
+    pub fn scheduled_status(
+        &self,
+        query_parameters: ScheduledStatusQueryParameters,
+    ) -> Result<SdkResponse<ScheduledStatusResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.scheduled_status(query_parameters))
+    }
+
+    /// Blocking version of `SmsClient::update_scheduled_status()`.
+    pub fn update_scheduled_status(
+        &self,
+        query_parameters: UpdateScheduledStatusQueryParameters,
+        request_body: UpdateScheduledStatusRequestBody,
+    ) -> Result<SdkResponse<UpdateScheduledStatusResponseBody>, SdkError> {
+        self.runtime.block_on(
+            self.client
+                .update_scheduled_status(query_parameters, request_body),
+        )
+    }
+
+    /// Blocking version of `SmsClient::tfa_applications()`.
+    pub fn tfa_applications(&self) -> Result<SdkResponse<TfaApplicationsResponseBody>, SdkError> {
+        self.runtime.block_on(self.client.tfa_applications())
+    }
+
+    /// Blocking version of `SmsClient::create_tfa_application()`.
+    pub fn create_tfa_application(
+        &self,
+        request_body: CreateTfaApplicationRequestBody,
+    ) -> Result<SdkResponse<CreateTfaApplicationResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.create_tfa_application(request_body))
+    }
+
+    /// Blocking version of `SmsClient::tfa_application()`.
+    pub fn tfa_application(
+        &self,
+        application_id: &str,
+    ) -> Result<SdkResponse<TfaApplicationResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.tfa_application(application_id))
+    }
+
+    /// Blocking version of `SmsClient::update_tfa_application()`.
+    pub fn update_tfa_application(
+        &self,
+        application_id: &str,
+        request_body: UpdateTfaApplicationRequestBody,
+    ) -> Result<SdkResponse<UpdateTfaApplicationResponseBody>, SdkError> {
+        self.runtime.block_on(
+            self.client
+                .update_tfa_application(application_id, request_body),
+        )
+    }
+
+    /// Blocking version of `SmsClient::tfa_message_templates()`.
+    pub fn tfa_message_templates(
+        &self,
+        application_id: &str,
+    ) -> Result<SdkResponse<TfaMessageTemplatesResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.tfa_message_templates(application_id))
+    }
+
+    /// Blocking version of `SmsClient::create_tfa_message_template()`.
+    pub fn create_tfa_message_template(
+        &self,
+        application_id: &str,
+        request_body: CreateTfaMessageTemplateRequestBody,
+    ) -> Result<SdkResponse<CreateTfaMessageTemplateResponseBody>, SdkError> {
+        self.runtime.block_on(
+            self.client
+                .create_tfa_message_template(application_id, request_body),
+        )
+    }
+
+    /// Blocking version of `SmsClient::tfa_message_template()`.
+    pub fn tfa_message_template(
+        &self,
+        application_id: &str,
+        template_id: &str,
+    ) -> Result<SdkResponse<TfaMessageTemplateResponseBody>, SdkError> {
+        self.runtime.block_on(
+            self.client
+                .tfa_message_template(application_id, template_id),
+        )
+    }
+
+    /// Blocking version of `SmsClient::update_tfa_message_template()`.
+    pub fn update_tfa_message_template(
+        &self,
+        application_id: &str,
+        template_id: &str,
+        request_body: UpdateTfaMessageTemplateRequestBody,
+    ) -> Result<SdkResponse<UpdateTfaMessageTemplateResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.update_tfa_message_template(
+                application_id,
+                template_id,
+                request_body,
+            ))
+    }
+
+    /// Blocking version of `SmsClient::send_pin_over_sms()`.
+    pub fn send_pin_over_sms(
+        &self,
+        query_parameters: SendPinOverSmsQueryParameters,
+        request_body: SendPinOverSmsRequestBody,
+    ) -> Result<SdkResponse<SendPinOverSmsResponseBody>, SdkError> {
+        self.runtime.block_on(
+            self.client
+                .send_pin_over_sms(query_parameters, request_body),
+        )
+    }
+
+    /// Blocking version of `SmsClient::resend_pin_over_sms()`.
+    pub fn resend_pin_over_sms(
+        &self,
+        pin_id: &str,
+        request_body: ResendPinOverSmsRequestBody,
+    ) -> Result<SdkResponse<ResendPinOverSmsResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.resend_pin_over_sms(pin_id, request_body))
+    }
+
+    /// Blocking version of `SmsClient::send_pin_over_voice()`.
+    pub fn send_pin_over_voice(
+        &self,
+        request_body: SendPinOverVoiceRequestBody,
+    ) -> Result<SdkResponse<SendPinOverVoiceResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.send_pin_over_voice(request_body))
+    }
+
+    /// Blocking version of `SmsClient::resend_pin_over_voice()`.
+    pub fn resend_pin_over_voice(
+        &self,
+        pin_id: &str,
+        request_body: ResendPinOverVoiceRequestBody,
+    ) -> Result<SdkResponse<ResendPinOverVoiceResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.resend_pin_over_voice(pin_id, request_body))
+    }
+
+    /// Blocking version of `SmsClient::verify_phone_number()`.
+    pub fn verify_phone_number(
+        &self,
+        pin_id: &str,
+        request_body: VerifyPhoneNumberRequestBody,
+    ) -> Result<SdkResponse<VerifyPhoneNumberResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.verify_phone_number(pin_id, request_body))
+    }
+
+    /// Blocking version of `SmsClient::tfa_verification_status()`.
+    pub fn tfa_verification_status(
+        &self,
+        app_id: &str,
+        query_parameters: TfaVerificationStatusQueryParameters,
+    ) -> Result<SdkResponse<TfaVerificationStatusResponseBody>, SdkError> {
+        self.runtime.block_on(
+            self.client
+                .tfa_verification_status(app_id, query_parameters),
+        )
     }
 }
@@ -0,0 +1,979 @@
+//! Endpoints of the SMS channel, which `SmsClient` calls. Each one can also be passed to
+//! `SmsClient::execute()`.
+
+use reqwest::Method;
+use validator::Validate;
+
+use crate::api::endpoint::{Endpoint, Json, NoBody};
+use crate::api::sms::*;
+use crate::api::SdkError;
+use crate::model::sms::{
+    CreateTfaApplicationRequestBody, CreateTfaApplicationResponseBody,
+    CreateTfaMessageTemplateRequestBody, CreateTfaMessageTemplateResponseBody,
+    DeliveryReportsQueryParameters, DeliveryReportsResponseBody, InboundReportsQueryParameters,
+    InboundReportsResponseBody, LogsQueryParameters, LogsResponseBody, PreviewRequestBody,
+    PreviewResponseBody, RescheduleQueryParameters, RescheduleRequestBody, RescheduleResponseBody,
+    ResendPinOverSmsRequestBody, ResendPinOverSmsResponseBody, ResendPinOverVoiceRequestBody,
+    ResendPinOverVoiceResponseBody, ScheduledQueryParameters, ScheduledResponseBody,
+    ScheduledStatusQueryParameters, ScheduledStatusResponseBody, SendBinaryRequestBody,
+    SendBinaryResponseBody, SendOverQueryParametersQueryParameters,
+    SendOverQueryParametersResponseBody, SendPinOverSmsQueryParameters, SendPinOverSmsRequestBody,
+    SendPinOverSmsResponseBody, SendPinOverVoiceRequestBody, SendPinOverVoiceResponseBody,
+    SendRequestBody, SendResponseBody, TfaApplicationResponseBody, TfaApplicationsResponseBody,
+    TfaMessageTemplateResponseBody, TfaMessageTemplatesResponseBody,
+    TfaVerificationStatusQueryParameters, TfaVerificationStatusResponseBody,
+    UpdateScheduledStatusQueryParameters, UpdateScheduledStatusRequestBody,
+    UpdateScheduledStatusResponseBody, UpdateTfaApplicationRequestBody,
+    UpdateTfaApplicationResponseBody, UpdateTfaMessageTemplateRequestBody,
+    UpdateTfaMessageTemplateResponseBody, VerifyPhoneNumberRequestBody,
+    VerifyPhoneNumberResponseBody,
+};
+
+/// Previews a message. See `SmsClient::preview()`.
+#[derive(Clone, Debug)]
+pub struct Preview {
+    body: Json<PreviewRequestBody>,
+}
+
+impl Preview {
+    pub fn new(request_body: PreviewRequestBody) -> Self {
+        Preview {
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for Preview {
+    type Query = ();
+    type Body = Json<PreviewRequestBody>;
+    type Response = PreviewResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_PREVIEW
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Gets delivery reports of sent messages. See `SmsClient::delivery_reports()`.
+#[derive(Clone, Debug)]
+pub struct GetDeliveryReports {
+    query_parameters: DeliveryReportsQueryParameters,
+}
+
+impl GetDeliveryReports {
+    pub fn new(query_parameters: DeliveryReportsQueryParameters) -> Self {
+        GetDeliveryReports { query_parameters }
+    }
+}
+
+impl Endpoint for GetDeliveryReports {
+    type Query = DeliveryReportsQueryParameters;
+    type Body = NoBody;
+    type Response = DeliveryReportsResponseBody;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_GET_DELIVERY_REPORTS
+    }
+
+    fn query(&self) -> Option<&Self::Query> {
+        Some(&self.query_parameters)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.query_parameters.validate()?)
+    }
+}
+
+/// Sends text messages. See `SmsClient::send()`.
+#[derive(Clone, Debug)]
+pub struct SendSms {
+    body: Json<SendRequestBody>,
+}
+
+impl SendSms {
+    pub fn new(request_body: SendRequestBody) -> Self {
+        SendSms {
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for SendSms {
+    type Query = ();
+    type Body = Json<SendRequestBody>;
+    type Response = SendResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_SEND
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Sends binary messages. See `SmsClient::send_binary()`.
+#[derive(Clone, Debug)]
+pub struct SendBinarySms {
+    body: Json<SendBinaryRequestBody>,
+}
+
+impl SendBinarySms {
+    pub fn new(request_body: SendBinaryRequestBody) -> Self {
+        SendBinarySms {
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for SendBinarySms {
+    type Query = ();
+    type Body = Json<SendBinaryRequestBody>;
+    type Response = SendBinaryResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_SEND_BINARY
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Gets the scheduled messages of a bulk. See `SmsClient::scheduled()`.
+#[derive(Clone, Debug)]
+pub struct GetScheduled {
+    query_parameters: ScheduledQueryParameters,
+}
+
+impl GetScheduled {
+    pub fn new(query_parameters: ScheduledQueryParameters) -> Self {
+        GetScheduled { query_parameters }
+    }
+}
+
+impl Endpoint for GetScheduled {
+    type Query = ScheduledQueryParameters;
+    type Body = NoBody;
+    type Response = ScheduledResponseBody;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_GET_SCHEDULED
+    }
+
+    fn query(&self) -> Option<&Self::Query> {
+        Some(&self.query_parameters)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.query_parameters.validate()?)
+    }
+}
+
+/// Gets the logs of sent messages. See `SmsClient::logs()`.
+#[derive(Clone, Debug)]
+pub struct GetLogs {
+    query_parameters: LogsQueryParameters,
+}
+
+impl GetLogs {
+    pub fn new(query_parameters: LogsQueryParameters) -> Self {
+        GetLogs { query_parameters }
+    }
+}
+
+impl Endpoint for GetLogs {
+    type Query = LogsQueryParameters;
+    type Body = NoBody;
+    type Response = LogsResponseBody;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_GET_LOGS
+    }
+
+    fn query(&self) -> Option<&Self::Query> {
+        Some(&self.query_parameters)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.query_parameters.validate()?)
+    }
+}
+
+/// Gets received messages. See `SmsClient::inbound_reports()`.
+#[derive(Clone, Debug)]
+pub struct GetInboundReports {
+    query_parameters: InboundReportsQueryParameters,
+}
+
+impl GetInboundReports {
+    pub fn new(query_parameters: InboundReportsQueryParameters) -> Self {
+        GetInboundReports { query_parameters }
+    }
+}
+
+impl Endpoint for GetInboundReports {
+    type Query = InboundReportsQueryParameters;
+    type Body = NoBody;
+    type Response = InboundReportsResponseBody;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_GET_INBOUND
+    }
+
+    fn query(&self) -> Option<&Self::Query> {
+        Some(&self.query_parameters)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.query_parameters.validate()?)
+    }
+}
+
+/// Sends a message with query parameters only. See `SmsClient::send_over_query_parameters()`.
+#[derive(Clone, Debug)]
+pub struct SendSmsOverQueryParameters {
+    query_parameters: SendOverQueryParametersQueryParameters,
+}
+
+impl SendSmsOverQueryParameters {
+    pub fn new(query_parameters: SendOverQueryParametersQueryParameters) -> Self {
+        SendSmsOverQueryParameters { query_parameters }
+    }
+}
+
+impl Endpoint for SendSmsOverQueryParameters {
+    type Query = SendOverQueryParametersQueryParameters;
+    type Body = NoBody;
+    type Response = SendOverQueryParametersResponseBody;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_SEND_OVER_QUERY_PARAMS
+    }
+
+    fn query(&self) -> Option<&Self::Query> {
+        Some(&self.query_parameters)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.query_parameters.validate()?)
+    }
+}
+
+/// Changes the date and time a bulk is sent at. See `SmsClient::reschedule()`.
+#[derive(Clone, Debug)]
+pub struct Reschedule {
+    query_parameters: RescheduleQueryParameters,
+    body: Json<RescheduleRequestBody>,
+}
+
+impl Reschedule {
+    pub fn new(
+        query_parameters: RescheduleQueryParameters,
+        request_body: RescheduleRequestBody,
+    ) -> Self {
+        Reschedule {
+            query_parameters,
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for Reschedule {
+    type Query = RescheduleQueryParameters;
+    type Body = Json<RescheduleRequestBody>;
+    type Response = RescheduleResponseBody;
+
+    fn method(&self) -> Method {
+        Method::PUT
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_RESCHEDULE
+    }
+
+    fn query(&self) -> Option<&Self::Query> {
+        Some(&self.query_parameters)
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        self.query_parameters.validate()?;
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Gets the status of a scheduled bulk. See `SmsClient::scheduled_status()`.
+#[derive(Clone, Debug)]
+pub struct GetScheduledStatus {
+    query_parameters: ScheduledStatusQueryParameters,
+}
+
+impl GetScheduledStatus {
+    pub fn new(query_parameters: ScheduledStatusQueryParameters) -> Self {
+        GetScheduledStatus { query_parameters }
+    }
+}
+
+impl Endpoint for GetScheduledStatus {
+    type Query = ScheduledStatusQueryParameters;
+    type Body = NoBody;
+    type Response = ScheduledStatusResponseBody;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_GET_SCHEDULED_STATUS
+    }
+
+    fn query(&self) -> Option<&Self::Query> {
+        Some(&self.query_parameters)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.query_parameters.validate()?)
+    }
+}
+
+/// Pauses, resumes or cancels a scheduled bulk. See `SmsClient::update_scheduled_status()`.
+#[derive(Clone, Debug)]
+pub struct UpdateScheduledStatus {
+    query_parameters: UpdateScheduledStatusQueryParameters,
+    body: Json<UpdateScheduledStatusRequestBody>,
+}
+
+impl UpdateScheduledStatus {
+    pub fn new(
+        query_parameters: UpdateScheduledStatusQueryParameters,
+        request_body: UpdateScheduledStatusRequestBody,
+    ) -> Self {
+        UpdateScheduledStatus {
+            query_parameters,
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for UpdateScheduledStatus {
+    type Query = UpdateScheduledStatusQueryParameters;
+    type Body = Json<UpdateScheduledStatusRequestBody>;
+    type Response = UpdateScheduledStatusResponseBody;
+
+    fn method(&self) -> Method {
+        Method::PUT
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_UPDATE_SCHEDULED_STATUS
+    }
+
+    fn query(&self) -> Option<&Self::Query> {
+        Some(&self.query_parameters)
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        self.query_parameters.validate()?;
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Gets all 2FA applications. See `SmsClient::tfa_applications()`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GetTfaApplications;
+
+impl Endpoint for GetTfaApplications {
+    type Query = ();
+    type Body = NoBody;
+    type Response = TfaApplicationsResponseBody;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_GET_TFA_APPLICATIONS
+    }
+}
+
+/// Creates a 2FA application. See `SmsClient::create_tfa_application()`.
+#[derive(Clone, Debug)]
+pub struct CreateTfaApplication {
+    body: Json<CreateTfaApplicationRequestBody>,
+}
+
+impl CreateTfaApplication {
+    pub fn new(request_body: CreateTfaApplicationRequestBody) -> Self {
+        CreateTfaApplication {
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for CreateTfaApplication {
+    type Query = ();
+    type Body = Json<CreateTfaApplicationRequestBody>;
+    type Response = CreateTfaApplicationResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_CREATE_TFA_APPLICATION
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Gets a 2FA application. See `SmsClient::tfa_application()`.
+#[derive(Clone, Debug)]
+pub struct GetTfaApplication {
+    application_id: String,
+}
+
+impl GetTfaApplication {
+    pub fn new(application_id: &str) -> Self {
+        GetTfaApplication {
+            application_id: application_id.to_string(),
+        }
+    }
+}
+
+impl Endpoint for GetTfaApplication {
+    type Query = ();
+    type Body = NoBody;
+    type Response = TfaApplicationResponseBody;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_GET_TFA_APPLICATION
+    }
+
+    fn path_params(&self) -> Vec<(&str, &str)> {
+        vec![("appId", &self.application_id)]
+    }
+}
+
+/// Updates a 2FA application. See `SmsClient::update_tfa_application()`.
+#[derive(Clone, Debug)]
+pub struct UpdateTfaApplication {
+    application_id: String,
+    body: Json<UpdateTfaApplicationRequestBody>,
+}
+
+impl UpdateTfaApplication {
+    pub fn new(application_id: &str, request_body: UpdateTfaApplicationRequestBody) -> Self {
+        UpdateTfaApplication {
+            application_id: application_id.to_string(),
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for UpdateTfaApplication {
+    type Query = ();
+    type Body = Json<UpdateTfaApplicationRequestBody>;
+    type Response = UpdateTfaApplicationResponseBody;
+
+    fn method(&self) -> Method {
+        Method::PUT
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_UPDATE_TFA_APPLICATION
+    }
+
+    fn path_params(&self) -> Vec<(&str, &str)> {
+        vec![("appId", &self.application_id)]
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Gets the message templates of a 2FA application. See `SmsClient::tfa_message_templates()`.
+#[derive(Clone, Debug)]
+pub struct GetTfaMessageTemplates {
+    application_id: String,
+}
+
+impl GetTfaMessageTemplates {
+    pub fn new(application_id: &str) -> Self {
+        GetTfaMessageTemplates {
+            application_id: application_id.to_string(),
+        }
+    }
+}
+
+impl Endpoint for GetTfaMessageTemplates {
+    type Query = ();
+    type Body = NoBody;
+    type Response = TfaMessageTemplatesResponseBody;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_GET_TFA_MESSAGE_TEMPLATES
+    }
+
+    fn path_params(&self) -> Vec<(&str, &str)> {
+        vec![("appId", &self.application_id)]
+    }
+}
+
+/// Creates a message template for a 2FA application. See
+/// `SmsClient::create_tfa_message_template()`.
+#[derive(Clone, Debug)]
+pub struct CreateTfaMessageTemplate {
+    application_id: String,
+    body: Json<CreateTfaMessageTemplateRequestBody>,
+}
+
+impl CreateTfaMessageTemplate {
+    pub fn new(application_id: &str, request_body: CreateTfaMessageTemplateRequestBody) -> Self {
+        CreateTfaMessageTemplate {
+            application_id: application_id.to_string(),
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for CreateTfaMessageTemplate {
+    type Query = ();
+    type Body = Json<CreateTfaMessageTemplateRequestBody>;
+    type Response = CreateTfaMessageTemplateResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_CREATE_TFA_MESSAGE_TEMPLATE
+    }
+
+    fn path_params(&self) -> Vec<(&str, &str)> {
+        vec![("appId", &self.application_id)]
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Gets a message template of a 2FA application. See `SmsClient::tfa_message_template()`.
+#[derive(Clone, Debug)]
+pub struct GetTfaMessageTemplate {
+    application_id: String,
+    template_id: String,
+}
+
+impl GetTfaMessageTemplate {
+    pub fn new(application_id: &str, template_id: &str) -> Self {
+        GetTfaMessageTemplate {
+            application_id: application_id.to_string(),
+            template_id: template_id.to_string(),
+        }
+    }
+}
+
+impl Endpoint for GetTfaMessageTemplate {
+    type Query = ();
+    type Body = NoBody;
+    type Response = TfaMessageTemplateResponseBody;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_GET_TFA_MESSAGE_TEMPLATE
+    }
+
+    fn path_params(&self) -> Vec<(&str, &str)> {
+        vec![
+            ("appId", &self.application_id),
+            ("msgId", &self.template_id),
+        ]
+    }
+}
+
+/// Updates a message template of a 2FA application. See
+/// `SmsClient::update_tfa_message_template()`.
+#[derive(Clone, Debug)]
+pub struct UpdateTfaMessageTemplate {
+    application_id: String,
+    template_id: String,
+    body: Json<UpdateTfaMessageTemplateRequestBody>,
+}
+
+impl UpdateTfaMessageTemplate {
+    pub fn new(
+        application_id: &str,
+        template_id: &str,
+        request_body: UpdateTfaMessageTemplateRequestBody,
+    ) -> Self {
+        UpdateTfaMessageTemplate {
+            application_id: application_id.to_string(),
+            template_id: template_id.to_string(),
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for UpdateTfaMessageTemplate {
+    type Query = ();
+    type Body = Json<UpdateTfaMessageTemplateRequestBody>;
+    type Response = UpdateTfaMessageTemplateResponseBody;
+
+    fn method(&self) -> Method {
+        Method::PUT
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_UPDATE_TFA_MESSAGE_TEMPLATE
+    }
+
+    fn path_params(&self) -> Vec<(&str, &str)> {
+        vec![
+            ("appId", &self.application_id),
+            ("msgId", &self.template_id),
+        ]
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Sends a 2FA PIN over SMS. See `SmsClient::send_pin_over_sms()`.
+#[derive(Clone, Debug)]
+pub struct SendPinOverSms {
+    query_parameters: SendPinOverSmsQueryParameters,
+    body: Json<SendPinOverSmsRequestBody>,
+}
+
+impl SendPinOverSms {
+    pub fn new(
+        query_parameters: SendPinOverSmsQueryParameters,
+        request_body: SendPinOverSmsRequestBody,
+    ) -> Self {
+        SendPinOverSms {
+            query_parameters,
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for SendPinOverSms {
+    type Query = SendPinOverSmsQueryParameters;
+    type Body = Json<SendPinOverSmsRequestBody>;
+    type Response = SendPinOverSmsResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_SEND_PIN_OVER_SMS
+    }
+
+    fn query(&self) -> Option<&Self::Query> {
+        Some(&self.query_parameters)
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        self.query_parameters.validate()?;
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Sends a 2FA PIN over SMS again. See `SmsClient::resend_pin_over_sms()`.
+#[derive(Clone, Debug)]
+pub struct ResendPinOverSms {
+    pin_id: String,
+    body: Json<ResendPinOverSmsRequestBody>,
+}
+
+impl ResendPinOverSms {
+    pub fn new(pin_id: &str, request_body: ResendPinOverSmsRequestBody) -> Self {
+        ResendPinOverSms {
+            pin_id: pin_id.to_string(),
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for ResendPinOverSms {
+    type Query = ();
+    type Body = Json<ResendPinOverSmsRequestBody>;
+    type Response = ResendPinOverSmsResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_RESEND_PIN_OVER_SMS
+    }
+
+    fn path_params(&self) -> Vec<(&str, &str)> {
+        vec![("pinId", &self.pin_id)]
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Sends a 2FA PIN over a voice call. See `SmsClient::send_pin_over_voice()`.
+#[derive(Clone, Debug)]
+pub struct SendPinOverVoice {
+    body: Json<SendPinOverVoiceRequestBody>,
+}
+
+impl SendPinOverVoice {
+    pub fn new(request_body: SendPinOverVoiceRequestBody) -> Self {
+        SendPinOverVoice {
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for SendPinOverVoice {
+    type Query = ();
+    type Body = Json<SendPinOverVoiceRequestBody>;
+    type Response = SendPinOverVoiceResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_SEND_PIN_OVER_VOICE
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Sends a 2FA PIN over a voice call again. See `SmsClient::resend_pin_over_voice()`.
+#[derive(Clone, Debug)]
+pub struct ResendPinOverVoice {
+    pin_id: String,
+    body: Json<ResendPinOverVoiceRequestBody>,
+}
+
+impl ResendPinOverVoice {
+    pub fn new(pin_id: &str, request_body: ResendPinOverVoiceRequestBody) -> Self {
+        ResendPinOverVoice {
+            pin_id: pin_id.to_string(),
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for ResendPinOverVoice {
+    type Query = ();
+    type Body = Json<ResendPinOverVoiceRequestBody>;
+    type Response = ResendPinOverVoiceResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_RESEND_PIN_OVER_VOICE
+    }
+
+    fn path_params(&self) -> Vec<(&str, &str)> {
+        vec![("pinId", &self.pin_id)]
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Verifies a phone number with a 2FA PIN. See `SmsClient::verify_phone_number()`.
+#[derive(Clone, Debug)]
+pub struct VerifyPhoneNumber {
+    pin_id: String,
+    body: Json<VerifyPhoneNumberRequestBody>,
+}
+
+impl VerifyPhoneNumber {
+    pub fn new(pin_id: &str, request_body: VerifyPhoneNumberRequestBody) -> Self {
+        VerifyPhoneNumber {
+            pin_id: pin_id.to_string(),
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for VerifyPhoneNumber {
+    type Query = ();
+    type Body = Json<VerifyPhoneNumberRequestBody>;
+    type Response = VerifyPhoneNumberResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_VERIFY_PHONE_NUMBER
+    }
+
+    fn path_params(&self) -> Vec<(&str, &str)> {
+        vec![("pinId", &self.pin_id)]
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Gets the verification status of a phone number. See `SmsClient::tfa_verification_status()`.
+#[derive(Clone, Debug)]
+pub struct GetTfaVerificationStatus {
+    application_id: String,
+    query_parameters: TfaVerificationStatusQueryParameters,
+}
+
+impl GetTfaVerificationStatus {
+    pub fn new(
+        application_id: &str,
+        query_parameters: TfaVerificationStatusQueryParameters,
+    ) -> Self {
+        GetTfaVerificationStatus {
+            application_id: application_id.to_string(),
+            query_parameters,
+        }
+    }
+}
+
+impl Endpoint for GetTfaVerificationStatus {
+    type Query = TfaVerificationStatusQueryParameters;
+    type Body = NoBody;
+    type Response = TfaVerificationStatusResponseBody;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_GET_TFA_VERIFICATION_STATUS
+    }
+
+    fn path_params(&self) -> Vec<(&str, &str)> {
+        vec![("appId", &self.application_id)]
+    }
+
+    fn query(&self) -> Option<&Self::Query> {
+        Some(&self.query_parameters)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.query_parameters.validate()?)
+    }
+}
@@ -0,0 +1,534 @@
+//! Email endpoints of the fake.
+
+use reqwest::{Method, StatusCode};
+use serde_json::Value;
+
+use crate::api::email::*;
+use crate::api::testing::{
+    now, now_millis, Core, DeliveryStatus, FakeRequest, FakeServer, Handled, Reply,
+};
+use crate::api::timestamp::parse_timestamp;
+use crate::model::email::*;
+
+#[derive(Debug, Default)]
+pub(super) struct EmailState {
+    messages: Vec<EmailMessage>,
+    bulks: Vec<EmailBulk>,
+    domains: Vec<Domain>,
+}
+
+#[derive(Debug)]
+struct EmailMessage {
+    bulk_id: String,
+    message_id: String,
+    from: Option<String>,
+    to: String,
+    text: Option<String>,
+    sent_at: String,
+    done_at: Option<String>,
+    status: DeliveryStatus,
+    reported: bool,
+}
+
+#[derive(Debug)]
+struct EmailBulk {
+    bulk_id: String,
+    send_at: i64,
+    status: BulkStatus,
+}
+
+impl FakeServer {
+    /// Adds a verified email domain, as if it had been set up in the account.
+    pub fn add_verified_domain(&self, domain_name: &str) {
+        let mut state = self.lock();
+        let state = &mut *state;
+
+        let mut domain = new_domain(&mut state.core, domain_name);
+        verify(&mut domain);
+        state.email.domains.push(domain);
+    }
+}
+
+impl EmailState {
+    pub(super) fn deliver_scheduled(&mut self, core: &Core) {
+        for bulk in &mut self.bulks {
+            if bulk.status != BulkStatus::Pending {
+                continue;
+            }
+            bulk.status = BulkStatus::Finished;
+
+            for message in &mut self.messages {
+                if message.bulk_id == bulk.bulk_id {
+                    message.status = core.delivery_status(&message.to);
+                    message.sent_at = now();
+                    message.done_at = Some(now());
+                }
+            }
+        }
+    }
+
+    // Returns the reply to the request, or `None` if the path is not one of Email.
+    pub(super) fn handle(&mut self, core: &mut Core, request: &FakeRequest) -> Option<Handled> {
+        let handled = if request.route(Method::POST, PATH_SEND).is_some() {
+            self.send(core, request)
+        } else if request.route(Method::GET, PATH_GET_BULKS).is_some() {
+            self.bulks(request)
+        } else if request.route(Method::PUT, PATH_RESCHEDULE).is_some() {
+            self.reschedule(request)
+        } else if request
+            .route(Method::GET, PATH_GET_SCHEDULED_STATUS)
+            .is_some()
+        {
+            self.scheduled_status(request)
+        } else if request
+            .route(Method::PUT, PATH_UPDATE_SCHEDULED_STATUS)
+            .is_some()
+        {
+            self.update_scheduled_status(request)
+        } else if request
+            .route(Method::GET, PATH_GET_DELIVERY_REPORTS)
+            .is_some()
+        {
+            Ok(self.delivery_reports(request))
+        } else if request.route(Method::GET, PATH_GET_LOGS).is_some() {
+            Ok(self.logs(request))
+        } else if request.route(Method::POST, PATH_VALIDATE).is_some() {
+            validate_address(request)
+        } else if request.route(Method::GET, PATH_GET_DOMAINS).is_some() {
+            Ok(self.domains(request))
+        } else if request.route(Method::POST, PATH_ADD_DOMAIN).is_some() {
+            self.add_domain(core, request)
+        } else if let Some(params) = request.route(Method::GET, PATH_GET_DOMAIN) {
+            self.domain(&params[0]).map(|domain| Reply::ok(domain))
+        } else if let Some(params) = request.route(Method::DELETE, PATH_DELETE_DOMAIN) {
+            self.delete_domain(&params[0])
+        } else if let Some(params) = request.route(Method::PUT, PATH_UPDATE_TRACKING) {
+            self.update_tracking(&params[0], request)
+        } else if let Some(params) = request.route(Method::POST, PATH_VERIFY_DOMAIN) {
+            self.domain(&params[0]).map(|domain| {
+                verify(domain);
+                Reply::empty(StatusCode::ACCEPTED)
+            })
+        } else {
+            return None;
+        };
+
+        Some(handled)
+    }
+
+    fn send(&mut self, core: &mut Core, request: &FakeRequest) -> Handled {
+        let form = multipart_fields(request)?;
+        let field = |name: &str| {
+            form.iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, value)| value.clone())
+        };
+
+        let to: Vec<String> = form
+            .iter()
+            .filter(|(field, _)| field == "to")
+            .flat_map(|(_, value)| value.split(','))
+            .map(|to| to.trim().to_string())
+            .filter(|to| !to.is_empty())
+            .collect();
+        if to.is_empty() {
+            return Err(Reply::bad_request("Missing recipient"));
+        }
+        let send_at = match field("sendAt") {
+            Some(send_at) => Some(
+                parse_timestamp(&send_at).ok_or_else(|| Reply::bad_request("Invalid sendAt"))?,
+            )
+            .filter(|send_at| *send_at > now_millis()),
+            None => None,
+        };
+
+        let bulk_id = field("bulkId").unwrap_or_else(|| core.next_id("fake-bulk"));
+        if let Some(send_at) = send_at {
+            self.bulks.push(EmailBulk {
+                bulk_id: bulk_id.clone(),
+                send_at,
+                status: BulkStatus::Pending,
+            });
+        }
+
+        let mut message_id = field("messageId");
+        let mut sent = Vec::new();
+        for to in to {
+            let message_id = message_id
+                .take()
+                .unwrap_or_else(|| core.next_id("fake-message"));
+            let status = match send_at {
+                Some(_) => DeliveryStatus::Pending,
+                None => core.delivery_status(&to),
+            };
+
+            sent.push(SentMessageDetails {
+                to: Some(to.clone()),
+                message_id: Some(message_id.clone()),
+                status: Some(status_of(DeliveryStatus::Pending)),
+            });
+            self.messages.push(EmailMessage {
+                bulk_id: bulk_id.clone(),
+                message_id,
+                from: field("from"),
+                to,
+                text: field("text").or_else(|| field("html")),
+                sent_at: now(),
+                done_at: Some(now()).filter(|_| status != DeliveryStatus::Pending),
+                status,
+                reported: false,
+            });
+        }
+
+        Ok(Reply::ok(&SendResponseBody {
+            bulk_id: Some(bulk_id),
+            messages: Some(sent),
+        }))
+    }
+
+    fn bulk(&mut self, request: &FakeRequest) -> Result<&mut EmailBulk, Reply> {
+        let bulk_id = request.required_query("bulkId")?;
+
+        self.bulks
+            .iter_mut()
+            .find(|bulk| bulk.bulk_id == bulk_id)
+            .ok_or_else(|| Reply::not_found("Bulk not found"))
+    }
+
+    fn bulks(&mut self, request: &FakeRequest) -> Handled {
+        let bulk = self.bulk(request)?;
+
+        Ok(Reply::ok(&BulksResponseBody {
+            external_bulk_id: Some(bulk.bulk_id.clone()),
+            bulks: Some(vec![bulk_info(bulk)]),
+        }))
+    }
+
+    fn reschedule(&mut self, request: &FakeRequest) -> Handled {
+        let body: RescheduleRequestBody = request.json()?;
+        let send_at =
+            parse_timestamp(&body.send_at).ok_or_else(|| Reply::bad_request("Invalid sendAt"))?;
+        let bulk = self.bulk(request)?;
+        if bulk.status != BulkStatus::Pending && bulk.status != BulkStatus::Paused {
+            return Err(Reply::bad_request("Bulk can not be rescheduled"));
+        }
+        bulk.send_at = send_at;
+
+        Ok(Reply::ok(&bulk_info(bulk)))
+    }
+
+    fn scheduled_status(&mut self, request: &FakeRequest) -> Handled {
+        let bulk = self.bulk(request)?;
+
+        Ok(Reply::ok(&ScheduledStatusResponseBody {
+            external_bulk_id: Some(bulk.bulk_id.clone()),
+            bulks: Some(vec![BulkStatusInfo {
+                bulk_id: Some(bulk.bulk_id.clone()),
+                status: Some(bulk.status),
+            }]),
+        }))
+    }
+
+    fn update_scheduled_status(&mut self, request: &FakeRequest) -> Handled {
+        let body: UpdateScheduledStatusRequestBody = request.json()?;
+        let bulk = self.bulk(request)?;
+        let allowed = matches!(
+            (bulk.status, body.status),
+            (BulkStatus::Pending, BulkStatus::Paused)
+                | (BulkStatus::Paused, BulkStatus::Pending)
+                | (BulkStatus::Pending, BulkStatus::Canceled)
+                | (BulkStatus::Paused, BulkStatus::Canceled)
+        );
+        if !allowed {
+            return Err(Reply::bad_request("Bulk status can not be updated"));
+        }
+        bulk.status = body.status;
+
+        Ok(Reply::ok(&BulkStatusInfo {
+            bulk_id: Some(bulk.bulk_id.clone()),
+            status: Some(bulk.status),
+        }))
+    }
+
+    fn delivery_reports(&mut self, request: &FakeRequest) -> Reply {
+        let bulk_id = request.query("bulkId");
+        let message_id = request.query("messageId");
+        let limit = request.limit(50);
+
+        let results = self
+            .messages
+            .iter_mut()
+            .filter(|message| {
+                !message.reported
+                    && message.status != DeliveryStatus::Pending
+                    && bulk_id.map_or(true, |bulk_id| message.bulk_id == bulk_id)
+                    && message_id.map_or(true, |message_id| message.message_id == message_id)
+            })
+            .take(limit)
+            .map(|message| {
+                // Each report is returned only once, like in the API.
+                message.reported = true;
+
+                Report {
+                    bulk_id: Some(message.bulk_id.clone()),
+                    message_id: Some(message.message_id.clone()),
+                    to: Some(message.to.clone()),
+                    sent_at: Some(message.sent_at.clone()),
+                    done_at: message.done_at.clone(),
+                    message_count: Some(1),
+                    status: Some(status_of(message.status)),
+                    error: Some(error_of(message.status)),
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        Reply::ok(&DeliveryReportsResponseBody {
+            results: Some(results),
+        })
+    }
+
+    fn logs(&self, request: &FakeRequest) -> Reply {
+        let filter =
+            |key: &str, value: &str| request.query(key).map_or(true, |query| query == value);
+
+        let results = self
+            .messages
+            .iter()
+            .filter(|message| {
+                filter("from", message.from.as_deref().unwrap_or_default())
+                    && filter("to", &message.to)
+                    && filter("bulkId", &message.bulk_id)
+                    && filter("messageId", &message.message_id)
+                    && filter("generalStatus", message.status.group_name())
+            })
+            .take(request.limit(50))
+            .map(|message| Log {
+                message_id: Some(message.message_id.clone()),
+                to: Some(message.to.clone()),
+                from: message.from.clone(),
+                text: message.text.clone(),
+                sent_at: Some(message.sent_at.clone()),
+                done_at: message.done_at.clone(),
+                message_count: Some(1),
+                status: Some(status_of(message.status)),
+                bulk_id: Some(message.bulk_id.clone()),
+                ..Default::default()
+            })
+            .collect();
+
+        Reply::ok(&LogsResponseBody {
+            results: Some(results),
+        })
+    }
+
+    fn domain(&mut self, domain_name: &str) -> Result<&mut Domain, Reply> {
+        self.domains
+            .iter_mut()
+            .find(|domain| domain.domain_name.as_deref() == Some(domain_name))
+            .ok_or_else(|| Reply::not_found("Domain not found"))
+    }
+
+    fn domains(&self, request: &FakeRequest) -> Reply {
+        let size = request
+            .query("size")
+            .and_then(|size| size.parse::<usize>().ok())
+            .unwrap_or(10)
+            .max(1);
+        let page = request
+            .query("page")
+            .and_then(|page| page.parse::<usize>().ok())
+            .unwrap_or(0);
+        let total_results = self.domains.len();
+
+        Reply::ok(&DomainsResponseBody {
+            paging: Some(Paging {
+                page: Some(page as i32),
+                size: Some(size as i32),
+                total_pages: Some(((total_results + size - 1) / size) as i32),
+                total_results: Some(total_results as i32),
+            }),
+            results: Some(
+                self.domains
+                    .iter()
+                    .skip(page * size)
+                    .take(size)
+                    .cloned()
+                    .collect(),
+            ),
+        })
+    }
+
+    fn add_domain(&mut self, core: &mut Core, request: &FakeRequest) -> Handled {
+        let body: AddDomainRequestBody = request.json()?;
+        if self.domain(&body.domain_name).is_ok() {
+            return Err(Reply::bad_request("Domain already exists"));
+        }
+
+        let domain = new_domain(core, &body.domain_name);
+        self.domains.push(domain.clone());
+
+        Ok(Reply::ok(&domain))
+    }
+
+    fn delete_domain(&mut self, domain_name: &str) -> Handled {
+        self.domain(domain_name)?;
+        self.domains
+            .retain(|domain| domain.domain_name.as_deref() != Some(domain_name));
+
+        Ok(Reply::empty(StatusCode::NO_CONTENT))
+    }
+
+    fn update_tracking(&mut self, domain_name: &str, request: &FakeRequest) -> Handled {
+        let body: UpdateTrackingRequestBody = request.json()?;
+        let domain = self.domain(domain_name)?;
+        let tracking = domain.tracking.get_or_insert_with(Default::default);
+        tracking.opens = body.opens.or(tracking.opens);
+        tracking.clicks = body.clicks.or(tracking.clicks);
+        tracking.unsubscribe = body.unsubscribe.or(tracking.unsubscribe);
+
+        Ok(Reply::ok(domain))
+    }
+}
+
+// Returns the fields of a multipart form, skipping file parts.
+fn multipart_fields(request: &FakeRequest) -> Result<Vec<(String, String)>, Reply> {
+    let boundary = request
+        .content_type
+        .as_deref()
+        .filter(|content_type| content_type.starts_with("multipart/form-data"))
+        .and_then(|content_type| content_type.split("boundary=").nth(1))
+        .map(|boundary| format!("--{}", boundary.trim_matches('"')))
+        .ok_or_else(|| Reply::bad_request("Expected a multipart form"))?;
+    let body = String::from_utf8_lossy(&request.body);
+
+    let mut fields = Vec::new();
+    for part in body.split(boundary.as_str()) {
+        let (headers, value) = match part.split_once("\r\n\r\n") {
+            Some(split) => split,
+            None => continue,
+        };
+        if headers.contains("filename=") {
+            continue;
+        }
+
+        let name = headers
+            .split("name=\"")
+            .nth(1)
+            .and_then(|rest| rest.split('"').next());
+        if let Some(name) = name {
+            let value = value.strip_suffix("\r\n").unwrap_or(value);
+            fields.push((name.to_string(), value.to_string()));
+        }
+    }
+
+    Ok(fields)
+}
+
+fn validate_address(request: &FakeRequest) -> Handled {
+    let body: Value = request.json()?;
+    let to = body
+        .get("to")
+        .and_then(Value::as_str)
+        .ok_or_else(|| Reply::bad_request("Missing recipient"))?;
+    let valid_syntax = match to.split_once('@') {
+        Some((local, domain)) => !local.is_empty() && domain.contains('.'),
+        None => false,
+    };
+    let local = to.split('@').next().unwrap_or_default();
+
+    Ok(Reply::ok(&ValidateAddressResponseBody {
+        to: Some(to.to_string()),
+        valid_mailbox: Some(valid_syntax.to_string()),
+        valid_syntax: Some(valid_syntax),
+        catch_all: Some(false),
+        disposable: Some(false),
+        role_based: Some(matches!(local, "admin" | "info" | "support" | "sales")),
+        ..Default::default()
+    }))
+}
+
+fn new_domain(core: &mut Core, domain_name: &str) -> Domain {
+    let record = |record_type: &str, name: &str, expected_value: &str| DnsRecord {
+        record_type: Some(record_type.to_string()),
+        name: Some(name.to_string()),
+        expected_value: Some(expected_value.to_string()),
+        verified: Some(false),
+    };
+    core.last_id += 1;
+
+    Domain {
+        domain_id: Some(core.last_id as i64),
+        domain_name: Some(domain_name.to_string()),
+        active: Some(false),
+        tracking: Some(Tracking {
+            clicks: Some(true),
+            opens: Some(true),
+            unsubscribe: Some(true),
+        }),
+        dns_records: Some(vec![
+            record("string", domain_name, "v=spf1 include:spf.infobip.com ~all"),
+            record(
+                "string",
+                &format!("bounce.{}", domain_name),
+                "mx.infobip.com",
+            ),
+        ]),
+        blocked: Some(false),
+        created_at: Some(now()),
+    }
+}
+
+fn verify(domain: &mut Domain) {
+    domain.active = Some(true);
+    for record in domain.dns_records.iter_mut().flatten() {
+        record.verified = Some(true);
+    }
+}
+
+fn bulk_info(bulk: &EmailBulk) -> BulkInfo {
+    BulkInfo {
+        bulk_id: Some(bulk.bulk_id.clone()),
+        send_at: Some(bulk.send_at as u64),
+    }
+}
+
+fn status_of(status: DeliveryStatus) -> Status {
+    let (group_id, group_name, id, name, description) = status.fields();
+
+    Status {
+        group_id: Some(group_id),
+        group_name: Some(group_name.to_string()),
+        id: Some(id),
+        name: Some(name.to_string()),
+        description: Some(description.to_string()),
+        action: None,
+    }
+}
+
+fn error_of(status: DeliveryStatus) -> ReportError {
+    let permanent = status == DeliveryStatus::Undeliverable;
+
+    ReportError {
+        group_id: Some(if permanent { 2 } else { 0 }),
+        group_name: Some(if permanent { "USER_ERRORS" } else { "OK" }.to_string()),
+        id: Some(if permanent { 6 } else { 0 }),
+        name: Some(
+            if permanent {
+                "EC_INVALID_EMAIL"
+            } else {
+                "NO_ERROR"
+            }
+            .to_string(),
+        ),
+        description: Some(
+            if permanent {
+                "Invalid email"
+            } else {
+                "No Error"
+            }
+            .to_string(),
+        ),
+        permanent: Some(permanent),
+    }
+}
@@ -0,0 +1,542 @@
+//! In-process fake of the Infobip API, to test code that uses the clients without an account.
+//!
+//! `FakeServer` is a `Transport` that answers the requests of the clients from memory, like the
+//! API would. It keeps state between requests: sends get message IDs and produce delivery reports
+//! and logs, scheduled bulks can be rescheduled, paused and canceled, 2FA PINs can be verified,
+//! and email domains and WhatsApp templates can be managed. Only the channels enabled as cargo
+//! features are available. Faults and latency can be injected to test error handling.
+//!
+//! IDs are generated from a counter, so that tests get the same IDs on every run. Unknown paths
+//! return `404 Not Found`, and requests without authentication return `401 Unauthorized`.
+//!
+//! # Example
+//! ```
+//! # use infobip_sdk::api::testing::FakeServer;
+//! # use infobip_sdk::model::sms::{Destination, LogsQueryParameters, Message, SendRequestBody};
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let server = FakeServer::new();
+//! let sms_client = server.client().sms();
+//!
+//! let mut message = Message::new(vec![Destination::new("41793026727")]);
+//! message.text = Some("Hello!".to_string());
+//! sms_client.send(SendRequestBody::new(vec![message])).await?;
+//!
+//! let logs = sms_client.logs(LogsQueryParameters::new()).await?;
+//! assert_eq!(logs.body.results.unwrap().len(), 1);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+#[cfg(any(feature = "sms", feature = "email"))]
+use std::time::UNIX_EPOCH;
+
+use http_body_util::BodyExt;
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
+use reqwest::{Method, Request, ResponseBuilderExt, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::api::client::InfobipClient;
+use crate::api::path::decode_segment;
+use crate::api::timer;
+#[cfg(any(feature = "sms", feature = "email"))]
+use crate::api::timer::SystemTime;
+#[cfg(any(feature = "sms", feature = "email"))]
+use crate::api::timestamp::format_timestamp;
+use crate::api::transport::{Transport, TransportFuture};
+use crate::api::{ApiErrorDetails, RequestError, SdkError, ServiceException};
+use crate::configuration::{ApiKey, Configuration};
+
+#[cfg(feature = "email")]
+mod email;
+
+#[cfg(feature = "sms")]
+mod sms;
+
+#[cfg(feature = "whatsapp")]
+mod whatsapp;
+
+#[cfg(feature = "whatsapp")]
+pub use whatsapp::WhatsAppMessage;
+
+/// Base URL of the configuration returned by `FakeServer::configuration()`.
+pub const FAKE_BASE_URL: &str = "https://fake.api.infobip.com";
+
+/// Failure returned instead of handling a request, set with `FakeServer::inject_fault()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fault {
+    /// Responds with the given status and an error body in the format of the API.
+    Status(StatusCode),
+
+    /// Responds with `429 Too Many Requests` and a `Retry-After` header with the given delay.
+    RateLimited(Duration),
+
+    /// Fails without a response, like a dropped connection.
+    Disconnect,
+}
+
+/// Stateful fake of the Infobip API, answering requests in-process.
+///
+/// Clones share the same state, so a clone can be given to the code under test while the
+/// original is used to inspect and control the fake.
+#[derive(Clone, Default)]
+pub struct FakeServer {
+    state: Arc<Mutex<State>>,
+}
+
+impl fmt::Debug for FakeServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FakeServer").finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    core: Core,
+    latency: Duration,
+    path_latencies: Vec<(String, Duration)>,
+    faults: Vec<InjectedFault>,
+    requests: Vec<(Method, String)>,
+    #[cfg(feature = "email")]
+    email: email::EmailState,
+    #[cfg(feature = "sms")]
+    sms: sms::SmsState,
+    #[cfg(feature = "whatsapp")]
+    whatsapp: whatsapp::WhatsAppState,
+}
+
+#[derive(Debug)]
+struct InjectedFault {
+    path: String,
+    fault: Fault,
+    remaining: usize,
+}
+
+// State shared by the channels.
+#[derive(Debug, Default)]
+struct Core {
+    last_id: u64,
+    undeliverable: HashSet<String>,
+}
+
+impl Core {
+    // Returns a new ID with the given prefix, unique within the server.
+    fn next_id(&mut self, prefix: &str) -> String {
+        self.last_id += 1;
+        format!("{}-{}", prefix, self.last_id)
+    }
+
+    // Returns a new ID in the format of 2FA IDs.
+    #[cfg(feature = "sms")]
+    fn next_hex_id(&mut self) -> String {
+        self.last_id += 1;
+        format!("{:032X}", self.last_id)
+    }
+
+    // Returns the status a message to the destination ends up with once delivered.
+    #[cfg(any(feature = "sms", feature = "email"))]
+    fn delivery_status(&self, to: &str) -> DeliveryStatus {
+        if self.undeliverable.contains(to) {
+            DeliveryStatus::Undeliverable
+        } else {
+            DeliveryStatus::Delivered
+        }
+    }
+}
+
+impl FakeServer {
+    /// Creates a new `FakeServer` without any state.
+    pub fn new() -> FakeServer {
+        Default::default()
+    }
+
+    /// Returns a configuration with the base URL and an API key the fake accepts.
+    pub fn configuration(&self) -> Configuration {
+        Configuration::with_api_key(
+            FAKE_BASE_URL.to_string(),
+            ApiKey::new("fake-api-key".to_string()),
+        )
+    }
+
+    /// Returns a client that sends its requests to this fake.
+    pub fn client(&self) -> InfobipClient {
+        InfobipClient::with_transport(self.configuration(), Arc::new(self.clone()))
+    }
+
+    /// Fails the next `times` requests to the path with the given fault, instead of handling
+    /// them. The path is either a path constant like `api::sms::PATH_SEND`, where placeholders
+    /// match any segment, or a path with the placeholders filled in.
+    pub fn inject_fault(&self, path: &str, fault: Fault, times: usize) {
+        self.lock().faults.push(InjectedFault {
+            path: path.to_string(),
+            fault,
+            remaining: times,
+        });
+    }
+
+    /// Delays every response by the given duration.
+    pub fn set_latency(&self, latency: Duration) {
+        self.lock().latency = latency;
+    }
+
+    /// Delays the responses to the path by the given duration, on top of the latency of every
+    /// response. The path is matched like in `inject_fault()`.
+    pub fn set_path_latency(&self, path: &str, latency: Duration) {
+        self.lock().path_latencies.push((path.to_string(), latency));
+    }
+
+    /// Makes messages sent to the destination, a phone number or email address, end up as
+    /// undeliverable in delivery reports and logs.
+    pub fn set_undeliverable(&self, to: &str) {
+        self.lock().core.undeliverable.insert(to.to_string());
+    }
+
+    /// Delivers the messages of scheduled SMS and email bulks that are pending, as if their time
+    /// had come. Paused and canceled bulks are left as they are.
+    #[cfg(any(feature = "sms", feature = "email"))]
+    pub fn deliver_scheduled(&self) {
+        let mut state = self.lock();
+        let state = &mut *state;
+
+        #[cfg(feature = "sms")]
+        state.sms.deliver_scheduled(&state.core);
+        #[cfg(feature = "email")]
+        state.email.deliver_scheduled(&state.core);
+    }
+
+    /// Returns the method and path of every request received, in order.
+    pub fn requests(&self) -> Vec<(Method, String)> {
+        self.lock().requests.clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap()
+    }
+
+    // Returns the latency of the path and the fault to fail the request with, if any.
+    fn prepare(&self, method: &Method, path: &str) -> (Duration, Option<Fault>) {
+        let mut state = self.lock();
+        state.requests.push((method.clone(), path.to_string()));
+
+        let latency = state.latency
+            + state
+                .path_latencies
+                .iter()
+                .filter(|(template, _)| path_params(template, path).is_some())
+                .map(|(_, latency)| *latency)
+                .sum::<Duration>();
+
+        let fault = state
+            .faults
+            .iter_mut()
+            .find(|fault| fault.remaining > 0 && path_params(&fault.path, path).is_some())
+            .map(|fault| {
+                fault.remaining -= 1;
+                fault.fault.clone()
+            });
+        state.faults.retain(|fault| fault.remaining > 0);
+
+        (latency, fault)
+    }
+
+    fn handle(&self, request: &FakeRequest) -> Reply {
+        let mut state = self.lock();
+        let state = &mut *state;
+
+        #[cfg(feature = "sms")]
+        if let Some(result) = state.sms.handle(&mut state.core, request) {
+            return result.unwrap_or_else(|error| error);
+        }
+        #[cfg(feature = "email")]
+        if let Some(result) = state.email.handle(&mut state.core, request) {
+            return result.unwrap_or_else(|error| error);
+        }
+        #[cfg(feature = "whatsapp")]
+        if let Some(result) = state.whatsapp.handle(&mut state.core, request) {
+            return result.unwrap_or_else(|error| error);
+        }
+
+        Reply::error(
+            StatusCode::NOT_FOUND,
+            "NOT_FOUND",
+            "Requested URL not found.",
+        )
+    }
+}
+
+impl Transport for FakeServer {
+    fn execute(&self, mut request: Request) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let url = request.url().clone();
+            let method = request.method().clone();
+            let (latency, fault) = self.prepare(&method, url.path());
+            timer::sleep(latency).await;
+
+            let reply = match fault {
+                Some(Fault::Disconnect) => {
+                    return Err(SdkError::transport("fake server dropped the connection"))
+                }
+                Some(Fault::RateLimited(retry_after)) => Reply::error(
+                    StatusCode::TOO_MANY_REQUESTS,
+                    "TOO_MANY_REQUESTS",
+                    "Too many requests",
+                )
+                .with_retry_after(retry_after),
+                Some(Fault::Status(status)) => Reply::error(
+                    status,
+                    status.as_str(),
+                    status.canonical_reason().unwrap_or("Injected fault"),
+                ),
+                None if !request.headers().contains_key(AUTHORIZATION) => Reply::error(
+                    StatusCode::UNAUTHORIZED,
+                    "UNAUTHORIZED",
+                    "Invalid login details",
+                ),
+                None => {
+                    let body = match request.body_mut().take() {
+                        Some(body) => body.collect().await?.to_bytes().to_vec(),
+                        None => Vec::new(),
+                    };
+                    let fake_request = FakeRequest {
+                        method,
+                        path: url.path().to_string(),
+                        #[cfg(any(feature = "sms", feature = "email"))]
+                        query: url.query_pairs().into_owned().collect(),
+                        #[cfg(feature = "email")]
+                        content_type: request
+                            .headers()
+                            .get(CONTENT_TYPE)
+                            .and_then(|value| value.to_str().ok())
+                            .map(str::to_string),
+                        body,
+                    };
+
+                    self.handle(&fake_request)
+                }
+            };
+
+            let mut builder = http::Response::builder()
+                .status(reply.status)
+                .url(url)
+                .header(CONTENT_TYPE, "application/json");
+            if let Some(retry_after) = reply.retry_after {
+                builder = builder.header("Retry-After", retry_after.as_secs().to_string());
+            }
+
+            Ok(builder
+                .body(reply.body)
+                .expect("fake response could not be built")
+                .into())
+        })
+    }
+}
+
+// Request as seen by the handlers of the channels.
+#[derive(Debug)]
+struct FakeRequest {
+    method: Method,
+    path: String,
+    #[cfg(any(feature = "sms", feature = "email"))]
+    query: Vec<(String, String)>,
+    #[cfg(feature = "email")]
+    content_type: Option<String>,
+    body: Vec<u8>,
+}
+
+impl FakeRequest {
+    // Returns the values of the path placeholders if the request is for the method and path.
+    fn route(&self, method: Method, template: &str) -> Option<Vec<String>> {
+        if self.method != method {
+            return None;
+        }
+
+        path_params(template, &self.path)
+    }
+
+    #[cfg(any(feature = "sms", feature = "email"))]
+    fn query(&self, key: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    // Returns every value of a query parameter that can be repeated, like `messageId`.
+    #[cfg(feature = "sms")]
+    fn query_all(&self, key: &str) -> Vec<&str> {
+        self.query
+            .iter()
+            .filter(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+
+    // Returns the value of a query parameter, or an error reply if it is missing.
+    #[cfg(any(feature = "sms", feature = "email"))]
+    fn required_query(&self, key: &str) -> Result<&str, Reply> {
+        self.query(key)
+            .ok_or_else(|| Reply::bad_request(&format!("Missing query parameter {}", key)))
+    }
+
+    #[cfg(any(feature = "sms", feature = "email"))]
+    fn limit(&self, default: usize) -> usize {
+        self.query("limit")
+            .and_then(|limit| limit.parse().ok())
+            .unwrap_or(default)
+    }
+
+    fn json<T: DeserializeOwned>(&self) -> Result<T, Reply> {
+        serde_json::from_slice(&self.body)
+            .map_err(|error| Reply::bad_request(&format!("Invalid request body: {}", error)))
+    }
+}
+
+// Result of handling a request. Errors are replies too, to return them with `?`.
+type Handled = Result<Reply, Reply>;
+
+#[derive(Debug)]
+struct Reply {
+    status: StatusCode,
+    body: Vec<u8>,
+    retry_after: Option<Duration>,
+}
+
+impl Reply {
+    fn json<T: Serialize>(status: StatusCode, body: &T) -> Reply {
+        Reply {
+            status,
+            body: serde_json::to_vec(body).expect("fake response body could not be serialized"),
+            retry_after: None,
+        }
+    }
+
+    fn ok<T: Serialize>(body: &T) -> Reply {
+        Reply::json(StatusCode::OK, body)
+    }
+
+    #[cfg(any(feature = "email", feature = "whatsapp"))]
+    fn empty(status: StatusCode) -> Reply {
+        Reply {
+            status,
+            body: Vec::new(),
+            retry_after: None,
+        }
+    }
+
+    fn error(status: StatusCode, message_id: &str, text: &str) -> Reply {
+        let details = ApiErrorDetails {
+            request_error: RequestError {
+                service_exception: ServiceException {
+                    message_id: Some(message_id.to_string()),
+                    text: Some(text.to_string()),
+                    validation_errors: None,
+                },
+            },
+        };
+
+        Reply::json(status, &details)
+    }
+
+    fn bad_request(text: &str) -> Reply {
+        Reply::error(StatusCode::BAD_REQUEST, "BAD_REQUEST", text)
+    }
+
+    fn not_found(text: &str) -> Reply {
+        Reply::error(StatusCode::NOT_FOUND, "NOT_FOUND", text)
+    }
+
+    fn with_retry_after(mut self, retry_after: Duration) -> Reply {
+        self.retry_after = Some(retry_after);
+        self
+    }
+}
+
+// Returns the decoded values of the placeholders of a path template like
+// `/2fa/2/pin/{pinId}/verify` if the path matches it.
+fn path_params(template: &str, path: &str) -> Option<Vec<String>> {
+    let template_segments: Vec<&str> = template.trim_end_matches('/').split('/').collect();
+    let path_segments: Vec<&str> = path.trim_end_matches('/').split('/').collect();
+    if template_segments.len() != path_segments.len() {
+        return None;
+    }
+
+    let mut params = Vec::new();
+    for (template_segment, path_segment) in template_segments.iter().zip(&path_segments) {
+        if template_segment.starts_with('{') && template_segment.ends_with('}') {
+            params.push(decode_segment(path_segment));
+        } else if template_segment != path_segment {
+            return None;
+        }
+    }
+
+    Some(params)
+}
+
+// Outcome of a message, shared by the status models of the channels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DeliveryStatus {
+    Pending,
+    #[cfg(any(feature = "sms", feature = "email"))]
+    Delivered,
+    #[cfg(any(feature = "sms", feature = "email"))]
+    Undeliverable,
+}
+
+impl DeliveryStatus {
+    // Returns the group ID, group name, ID, name and description of the status.
+    fn fields(self) -> (i32, &'static str, i32, &'static str, &'static str) {
+        match self {
+            DeliveryStatus::Pending => (
+                1,
+                "PENDING",
+                26,
+                "PENDING_ACCEPTED",
+                "Message sent to next instance",
+            ),
+            #[cfg(any(feature = "sms", feature = "email"))]
+            DeliveryStatus::Delivered => (
+                3,
+                "DELIVERED",
+                5,
+                "DELIVERED_TO_HANDSET",
+                "Message delivered to handset",
+            ),
+            #[cfg(any(feature = "sms", feature = "email"))]
+            DeliveryStatus::Undeliverable => (
+                2,
+                "UNDELIVERABLE",
+                9,
+                "UNDELIVERABLE_NOT_DELIVERED",
+                "Message sent not delivered",
+            ),
+        }
+    }
+
+    #[cfg(any(feature = "sms", feature = "email"))]
+    fn group_name(self) -> &'static str {
+        self.fields().1
+    }
+}
+
+// Returns the current time in the format of the API, e.g. `2022-10-05T16:28:52.000+0000`.
+#[cfg(any(feature = "sms", feature = "email"))]
+fn now() -> String {
+    let since_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+
+    format_timestamp(since_epoch.as_millis() as i64)
+}
+
+#[cfg(any(feature = "sms", feature = "email"))]
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
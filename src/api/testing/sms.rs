@@ -0,0 +1,823 @@
+//! SMS and 2FA endpoints of the fake.
+
+use reqwest::Method;
+
+use crate::api::sms::*;
+use crate::api::testing::{
+    now, now_millis, Core, DeliveryStatus, FakeRequest, FakeServer, Handled, Reply,
+};
+use crate::api::timestamp::{format_timestamp, parse_timestamp};
+use crate::model::sms::*;
+
+const DEFAULT_PIN_ATTEMPTS: i32 = 10;
+const DEFAULT_PIN_PLACEHOLDER: &str = "{{pin}}";
+
+// Characters of the GSM 7-bit alphabet, and the ones of its extension table, which take two.
+const GSM_CHARACTERS: &str = "@£$¥èéùìòÇ\nØø\rÅåΔ_ΦΓΛΩΠΨΣΘΞÆæßÉ !\"#¤%&'()*+,-./0123456789:;<=>?\
+    ¡ABCDEFGHIJKLMNOPQRSTUVWXYZÄÖÑÜ§¿abcdefghijklmnopqrstuvwxyzäöñüà";
+const GSM_EXTENSION_CHARACTERS: &str = "^{}\\[~]|€\x0C";
+
+#[derive(Debug, Default)]
+pub(super) struct SmsState {
+    messages: Vec<SmsMessage>,
+    bulks: Vec<SmsBulk>,
+    inbound: Vec<InboundSmsReport>,
+    applications: Vec<TfaApplication>,
+    templates: Vec<TfaMessageTemplate>,
+    pins: Vec<Pin>,
+}
+
+#[derive(Debug)]
+struct SmsMessage {
+    bulk_id: String,
+    message_id: String,
+    from: Option<String>,
+    to: String,
+    text: Option<String>,
+    sms_count: i32,
+    sent_at: String,
+    done_at: Option<String>,
+    status: DeliveryStatus,
+    reported: bool,
+}
+
+#[derive(Debug)]
+struct SmsBulk {
+    bulk_id: String,
+    send_at: String,
+    status: ScheduledStatus,
+}
+
+#[derive(Debug)]
+struct Pin {
+    pin_id: String,
+    application_id: String,
+    message_id: String,
+    to: String,
+    code: String,
+    attempts_remaining: i32,
+    sent_at: i64,
+    verified_at: Option<i64>,
+}
+
+// Message of a send request, whatever the endpoint it was sent to.
+struct OutgoingMessage {
+    from: Option<String>,
+    destinations: Vec<Destination>,
+    text: Option<String>,
+    send_at: Option<String>,
+}
+
+impl FakeServer {
+    /// Queues an SMS as received, to be returned by the next inbound reports request.
+    pub fn receive_sms(&self, from: &str, to: &str, text: &str) {
+        let mut state = self.lock();
+        let message_id = state.core.next_id("fake-inbound");
+        let keyword = text.split_whitespace().next().map(str::to_uppercase);
+
+        state.sms.inbound.push(InboundSmsReport {
+            clean_text: Some(text.to_string()),
+            from: Some(from.to_string()),
+            keyword,
+            message_id: Some(message_id),
+            received_at: Some(now()),
+            sms_count: Some(1),
+            text: Some(text.to_string()),
+            to: Some(to.to_string()),
+            ..Default::default()
+        });
+    }
+
+    /// Returns the code of a 2FA PIN that was sent, to verify it like its recipient would.
+    pub fn pin_code(&self, pin_id: &str) -> Option<String> {
+        self.lock()
+            .sms
+            .pins
+            .iter()
+            .find(|pin| pin.pin_id == pin_id)
+            .map(|pin| pin.code.clone())
+    }
+}
+
+impl SmsState {
+    pub(super) fn deliver_scheduled(&mut self, core: &Core) {
+        for bulk in &mut self.bulks {
+            if bulk.status != ScheduledStatus::Pending {
+                continue;
+            }
+            bulk.status = ScheduledStatus::Finished;
+
+            for message in &mut self.messages {
+                if message.bulk_id == bulk.bulk_id {
+                    message.status = core.delivery_status(&message.to);
+                    message.sent_at = now();
+                    message.done_at = Some(now());
+                }
+            }
+        }
+    }
+
+    // Returns the reply to the request, or `None` if the path is not one of SMS or 2FA.
+    pub(super) fn handle(&mut self, core: &mut Core, request: &FakeRequest) -> Option<Handled> {
+        let handled = if request.route(Method::POST, PATH_PREVIEW).is_some() {
+            preview(request)
+        } else if request.route(Method::POST, PATH_SEND).is_some() {
+            self.send(core, request)
+        } else if request.route(Method::POST, PATH_SEND_BINARY).is_some() {
+            self.send_binary(core, request)
+        } else if request
+            .route(Method::GET, PATH_SEND_OVER_QUERY_PARAMS)
+            .is_some()
+        {
+            self.send_over_query_parameters(core, request)
+        } else if request
+            .route(Method::GET, PATH_GET_DELIVERY_REPORTS)
+            .is_some()
+        {
+            Ok(self.delivery_reports(request))
+        } else if request.route(Method::GET, PATH_GET_LOGS).is_some() {
+            Ok(self.logs(request))
+        } else if request.route(Method::GET, PATH_GET_INBOUND).is_some() {
+            Ok(self.inbound_reports(request))
+        } else if request.route(Method::GET, PATH_GET_SCHEDULED).is_some() {
+            self.scheduled(request)
+        } else if request.route(Method::PUT, PATH_RESCHEDULE).is_some() {
+            self.reschedule(request)
+        } else if request
+            .route(Method::GET, PATH_GET_SCHEDULED_STATUS)
+            .is_some()
+        {
+            self.scheduled_status(request)
+        } else if request
+            .route(Method::PUT, PATH_UPDATE_SCHEDULED_STATUS)
+            .is_some()
+        {
+            self.update_scheduled_status(request)
+        } else {
+            return self.handle_tfa(core, request);
+        };
+
+        Some(handled)
+    }
+
+    fn handle_tfa(&mut self, core: &mut Core, request: &FakeRequest) -> Option<Handled> {
+        let handled = if request
+            .route(Method::GET, PATH_GET_TFA_APPLICATIONS)
+            .is_some()
+        {
+            Ok(Reply::ok(&self.applications))
+        } else if request
+            .route(Method::POST, PATH_CREATE_TFA_APPLICATION)
+            .is_some()
+        {
+            self.create_tfa_application(core, request)
+        } else if let Some(params) = request.route(Method::GET, PATH_GET_TFA_APPLICATION) {
+            self.application(&params[0]).map(Reply::ok)
+        } else if let Some(params) = request.route(Method::PUT, PATH_UPDATE_TFA_APPLICATION) {
+            self.update_tfa_application(&params[0], request)
+        } else if let Some(params) = request.route(Method::GET, PATH_GET_TFA_MESSAGE_TEMPLATES) {
+            self.tfa_message_templates(&params[0])
+        } else if let Some(params) = request.route(Method::POST, PATH_CREATE_TFA_MESSAGE_TEMPLATE) {
+            self.create_tfa_message_template(core, &params[0], request)
+        } else if let Some(params) = request.route(Method::GET, PATH_GET_TFA_MESSAGE_TEMPLATE) {
+            self.template(&params[0], &params[1]).map(Reply::ok)
+        } else if let Some(params) = request.route(Method::PUT, PATH_UPDATE_TFA_MESSAGE_TEMPLATE) {
+            self.update_tfa_message_template(&params[0], &params[1], request)
+        } else if request
+            .route(Method::POST, PATH_SEND_PIN_OVER_SMS)
+            .is_some()
+        {
+            self.send_pin(core, request, false)
+        } else if request
+            .route(Method::POST, PATH_SEND_PIN_OVER_VOICE)
+            .is_some()
+        {
+            self.send_pin(core, request, true)
+        } else if let Some(params) = request.route(Method::POST, PATH_RESEND_PIN_OVER_SMS) {
+            self.resend_pin(core, &params[0], request, false)
+        } else if let Some(params) = request.route(Method::POST, PATH_RESEND_PIN_OVER_VOICE) {
+            self.resend_pin(core, &params[0], request, true)
+        } else if let Some(params) = request.route(Method::POST, PATH_VERIFY_PHONE_NUMBER) {
+            self.verify_phone_number(&params[0], request)
+        } else if let Some(params) = request.route(Method::GET, PATH_GET_TFA_VERIFICATION_STATUS) {
+            self.tfa_verification_status(&params[0], request)
+        } else {
+            return None;
+        };
+
+        Some(handled)
+    }
+
+    fn send(&mut self, core: &mut Core, request: &FakeRequest) -> Handled {
+        let body: SendRequestBody = request.json()?;
+        let messages = body
+            .messages
+            .into_iter()
+            .map(|message| OutgoingMessage {
+                from: message.from,
+                destinations: message.destinations.unwrap_or_default(),
+                text: message.text,
+                send_at: message.send_at,
+            })
+            .collect();
+
+        self.accept(core, body.bulk_id, messages)
+    }
+
+    fn send_binary(&mut self, core: &mut Core, request: &FakeRequest) -> Handled {
+        let body: SendBinaryRequestBody = request.json()?;
+        let messages = body
+            .messages
+            .unwrap_or_default()
+            .into_iter()
+            .map(|message| OutgoingMessage {
+                from: message.from,
+                destinations: message.destinations.unwrap_or_default(),
+                text: message.binary.map(|binary| binary.hex),
+                send_at: message.send_at,
+            })
+            .collect();
+
+        self.accept(core, body.bulk_id, messages)
+    }
+
+    fn send_over_query_parameters(&mut self, core: &mut Core, request: &FakeRequest) -> Handled {
+        let destinations = request
+            .required_query("to")?
+            .split(',')
+            .filter(|to| !to.is_empty())
+            .map(Destination::new)
+            .collect();
+        let message = OutgoingMessage {
+            from: request.query("from").map(str::to_string),
+            destinations,
+            text: request.query("text").map(str::to_string),
+            send_at: request.query("sendAt").map(str::to_string),
+        };
+
+        self.accept(
+            core,
+            request.query("bulkId").map(str::to_string),
+            vec![message],
+        )
+    }
+
+    // Stores the messages of a send request, delivering them right away unless scheduled.
+    fn accept(
+        &mut self,
+        core: &mut Core,
+        bulk_id: Option<String>,
+        messages: Vec<OutgoingMessage>,
+    ) -> Handled {
+        if messages
+            .iter()
+            .all(|message| message.destinations.is_empty())
+        {
+            return Err(Reply::bad_request("Invalid destination address"));
+        }
+
+        let bulk_id = bulk_id.unwrap_or_else(|| core.next_id("fake-bulk"));
+        let mut sent = Vec::new();
+        for message in messages {
+            let send_at = match &message.send_at {
+                Some(send_at) => {
+                    let millis = parse_timestamp(send_at)
+                        .ok_or_else(|| Reply::bad_request("Invalid sendAt"))?;
+                    Some(millis).filter(|millis| *millis > now_millis())
+                }
+                None => None,
+            };
+            if let Some(send_at) = send_at {
+                if !self.bulks.iter().any(|bulk| bulk.bulk_id == bulk_id) {
+                    self.bulks.push(SmsBulk {
+                        bulk_id: bulk_id.clone(),
+                        send_at: format_timestamp(send_at),
+                        status: ScheduledStatus::Pending,
+                    });
+                }
+            }
+
+            for destination in message.destinations {
+                let message_id = destination
+                    .message_id
+                    .unwrap_or_else(|| core.next_id("fake-message"));
+                let status = match send_at {
+                    Some(_) => DeliveryStatus::Pending,
+                    None => core.delivery_status(&destination.to),
+                };
+
+                sent.push(SentMessageDetails {
+                    message_id: Some(message_id.clone()),
+                    status: Some(status_of(DeliveryStatus::Pending)),
+                    to: Some(destination.to.clone()),
+                });
+                self.messages.push(SmsMessage {
+                    bulk_id: bulk_id.clone(),
+                    message_id,
+                    from: message.from.clone(),
+                    sms_count: sms_count(message.text.as_deref().unwrap_or_default()),
+                    to: destination.to,
+                    text: message.text.clone(),
+                    sent_at: now(),
+                    done_at: Some(now()).filter(|_| status != DeliveryStatus::Pending),
+                    status,
+                    reported: false,
+                });
+            }
+        }
+
+        Ok(Reply::ok(&SendResponseBody {
+            bulk_id: Some(bulk_id),
+            messages: Some(sent),
+        }))
+    }
+
+    fn delivery_reports(&mut self, request: &FakeRequest) -> Reply {
+        let bulk_id = request.query("bulkId");
+        let message_id = request.query("messageId");
+        let limit = request.limit(50);
+
+        let results = self
+            .messages
+            .iter_mut()
+            .filter(|message| {
+                !message.reported
+                    && message.status != DeliveryStatus::Pending
+                    && bulk_id.map_or(true, |bulk_id| message.bulk_id == bulk_id)
+                    && message_id.map_or(true, |message_id| message.message_id == message_id)
+            })
+            .take(limit)
+            .map(|message| {
+                // Each report is returned only once, like in the API.
+                message.reported = true;
+
+                Report {
+                    bulk_id: Some(message.bulk_id.clone()),
+                    done_at: message.done_at.clone(),
+                    error: Some(error_of(message.status)),
+                    from: message.from.clone(),
+                    message_id: Some(message.message_id.clone()),
+                    sent_at: Some(message.sent_at.clone()),
+                    sms_count: Some(message.sms_count),
+                    status: Some(status_of(message.status)),
+                    to: Some(message.to.clone()),
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        Reply::ok(&DeliveryReportsResponseBody {
+            results: Some(results),
+        })
+    }
+
+    fn logs(&self, request: &FakeRequest) -> Reply {
+        let filter = |key: &str, value: &str| {
+            let values = request.query_all(key);
+            values.is_empty() || values.contains(&value)
+        };
+
+        let results = self
+            .messages
+            .iter()
+            .filter(|message| {
+                filter("from", message.from.as_deref().unwrap_or_default())
+                    && filter("to", &message.to)
+                    && filter("bulkId", &message.bulk_id)
+                    && filter("messageId", &message.message_id)
+                    && filter("generalStatus", message.status.group_name())
+            })
+            .take(request.limit(50))
+            .map(|message| Log {
+                bulk_id: Some(message.bulk_id.clone()),
+                done_at: message.done_at.clone(),
+                error: Some(error_of(message.status)),
+                from: message.from.clone(),
+                message_id: Some(message.message_id.clone()),
+                sent_at: Some(message.sent_at.clone()),
+                sms_count: Some(message.sms_count),
+                status: Some(status_of(message.status)),
+                text: message.text.clone(),
+                to: Some(message.to.clone()),
+                ..Default::default()
+            })
+            .collect();
+
+        Reply::ok(&LogsResponseBody {
+            results: Some(results),
+        })
+    }
+
+    fn inbound_reports(&mut self, request: &FakeRequest) -> Reply {
+        let count = request.limit(1000).min(self.inbound.len());
+        let results: Vec<InboundSmsReport> = self.inbound.drain(..count).collect();
+
+        Reply::ok(&InboundReportsResponseBody {
+            message_count: Some(results.len() as i32),
+            pending_message_count: Some(self.inbound.len() as i32),
+            results: Some(results),
+        })
+    }
+
+    fn bulk(&mut self, request: &FakeRequest) -> Result<&mut SmsBulk, Reply> {
+        let bulk_id = request.required_query("bulkId")?;
+
+        self.bulks
+            .iter_mut()
+            .find(|bulk| bulk.bulk_id == bulk_id)
+            .ok_or_else(|| Reply::not_found("Bulk not found"))
+    }
+
+    fn scheduled(&mut self, request: &FakeRequest) -> Handled {
+        let bulk = self.bulk(request)?;
+
+        Ok(Reply::ok(&ScheduledResponseBody {
+            bulk_id: bulk.bulk_id.clone(),
+            send_at: bulk.send_at.clone(),
+        }))
+    }
+
+    fn reschedule(&mut self, request: &FakeRequest) -> Handled {
+        let body: RescheduleRequestBody = request.json()?;
+        let send_at =
+            parse_timestamp(&body.send_at).ok_or_else(|| Reply::bad_request("Invalid sendAt"))?;
+        let bulk = self.bulk(request)?;
+        if bulk.status != ScheduledStatus::Pending && bulk.status != ScheduledStatus::Paused {
+            return Err(Reply::bad_request("Bulk can not be rescheduled"));
+        }
+        bulk.send_at = format_timestamp(send_at);
+
+        Ok(Reply::ok(&ScheduledResponseBody {
+            bulk_id: bulk.bulk_id.clone(),
+            send_at: bulk.send_at.clone(),
+        }))
+    }
+
+    fn scheduled_status(&mut self, request: &FakeRequest) -> Handled {
+        let bulk = self.bulk(request)?;
+
+        Ok(Reply::ok(&ScheduledStatusResponseBody {
+            bulk_id: Some(bulk.bulk_id.clone()),
+            status: Some(bulk.status),
+        }))
+    }
+
+    fn update_scheduled_status(&mut self, request: &FakeRequest) -> Handled {
+        let body: UpdateScheduledStatusRequestBody = request.json()?;
+        let bulk = self.bulk(request)?;
+        let allowed = matches!(
+            (bulk.status, body.status),
+            (ScheduledStatus::Pending, ScheduledStatus::Paused)
+                | (ScheduledStatus::Paused, ScheduledStatus::Pending)
+                | (ScheduledStatus::Pending, ScheduledStatus::Canceled)
+                | (ScheduledStatus::Paused, ScheduledStatus::Canceled)
+        );
+        if !allowed {
+            return Err(Reply::bad_request("Bulk status can not be updated"));
+        }
+        bulk.status = body.status;
+
+        Ok(Reply::ok(&ScheduledStatusResponseBody {
+            bulk_id: Some(bulk.bulk_id.clone()),
+            status: Some(bulk.status),
+        }))
+    }
+
+    fn application(&self, app_id: &str) -> Result<&TfaApplication, Reply> {
+        self.applications
+            .iter()
+            .find(|application| application.application_id.as_deref() == Some(app_id))
+            .ok_or_else(|| Reply::not_found("Application not found"))
+    }
+
+    fn create_tfa_application(&mut self, core: &mut Core, request: &FakeRequest) -> Handled {
+        let mut application: TfaApplication = request.json()?;
+        application.application_id = Some(core.next_hex_id());
+        application.enabled = Some(application.enabled.unwrap_or(true));
+        self.applications.push(application.clone());
+
+        Ok(Reply::ok(&application))
+    }
+
+    fn update_tfa_application(&mut self, app_id: &str, request: &FakeRequest) -> Handled {
+        let mut update: TfaApplication = request.json()?;
+        self.application(app_id)?;
+        let application = self
+            .applications
+            .iter_mut()
+            .find(|application| application.application_id.as_deref() == Some(app_id))
+            .expect("application was found");
+        update.application_id = Some(app_id.to_string());
+        update.enabled = update.enabled.or(application.enabled);
+        *application = update.clone();
+
+        Ok(Reply::ok(&update))
+    }
+
+    fn template(&self, app_id: &str, msg_id: &str) -> Result<&TfaMessageTemplate, Reply> {
+        self.application(app_id)?;
+
+        self.templates
+            .iter()
+            .find(|template| {
+                template.application_id.as_deref() == Some(app_id)
+                    && template.message_id.as_deref() == Some(msg_id)
+            })
+            .ok_or_else(|| Reply::not_found("Message template not found"))
+    }
+
+    fn tfa_message_templates(&self, app_id: &str) -> Handled {
+        self.application(app_id)?;
+        let templates: Vec<&TfaMessageTemplate> = self
+            .templates
+            .iter()
+            .filter(|template| template.application_id.as_deref() == Some(app_id))
+            .collect();
+
+        Ok(Reply::ok(&templates))
+    }
+
+    fn create_tfa_message_template(
+        &mut self,
+        core: &mut Core,
+        app_id: &str,
+        request: &FakeRequest,
+    ) -> Handled {
+        let mut template: TfaMessageTemplate = request.json()?;
+        self.application(app_id)?;
+        template.application_id = Some(app_id.to_string());
+        template.message_id = Some(core.next_hex_id());
+        self.templates.push(template.clone());
+
+        Ok(Reply::ok(&template))
+    }
+
+    fn update_tfa_message_template(
+        &mut self,
+        app_id: &str,
+        msg_id: &str,
+        request: &FakeRequest,
+    ) -> Handled {
+        let mut update: TfaMessageTemplate = request.json()?;
+        self.template(app_id, msg_id)?;
+        update.application_id = Some(app_id.to_string());
+        update.message_id = Some(msg_id.to_string());
+        for template in &mut self.templates {
+            if template.message_id.as_deref() == Some(msg_id) {
+                *template = update.clone();
+            }
+        }
+
+        Ok(Reply::ok(&update))
+    }
+
+    fn send_pin(&mut self, core: &mut Core, request: &FakeRequest, voice: bool) -> Handled {
+        let body: SendPinOverSmsRequestBody = request.json()?;
+        let template = self
+            .template(&body.application_id, &body.message_id)?
+            .clone();
+        let pin_attempts = self
+            .application(&body.application_id)?
+            .configuration
+            .as_ref()
+            .and_then(|configuration| configuration.pin_attempts)
+            .unwrap_or(DEFAULT_PIN_ATTEMPTS);
+
+        let pin = Pin {
+            pin_id: core.next_hex_id(),
+            application_id: body.application_id,
+            message_id: body.message_id,
+            to: body.to,
+            code: pin_code(template.pin_type, template.pin_length),
+            attempts_remaining: pin_attempts,
+            sent_at: now_millis(),
+            verified_at: None,
+        };
+        let reply = self.deliver_pin(core, &pin, &template, body.from, voice);
+        self.pins.push(pin);
+
+        Ok(reply)
+    }
+
+    fn resend_pin(
+        &mut self,
+        core: &mut Core,
+        pin_id: &str,
+        request: &FakeRequest,
+        voice: bool,
+    ) -> Handled {
+        let _: ResendPinRequestBody = request.json()?;
+        let index = self
+            .pins
+            .iter()
+            .position(|pin| pin.pin_id == pin_id)
+            .ok_or_else(|| Reply::not_found("PIN not found"))?;
+        let pin = self.pins.remove(index);
+        let reply = self
+            .template(&pin.application_id, &pin.message_id)
+            .cloned()
+            .map(|template| self.deliver_pin(core, &pin, &template, None, voice));
+        self.pins.insert(index, pin);
+
+        reply
+    }
+
+    // Sends the PIN to its recipient, over SMS or a voice call.
+    fn deliver_pin(
+        &mut self,
+        core: &mut Core,
+        pin: &Pin,
+        template: &TfaMessageTemplate,
+        from: Option<String>,
+        voice: bool,
+    ) -> Reply {
+        let placeholder = template
+            .pin_placeholder
+            .as_deref()
+            .unwrap_or(DEFAULT_PIN_PLACEHOLDER);
+        let text = template.message_text.replace(placeholder, &pin.code);
+        let from = from.or_else(|| template.sender_id.clone());
+        let status = core.delivery_status(&pin.to);
+
+        let mut response = SendPinResponseBody {
+            nc_status: Some("NC_NOT_CONFIGURED".to_string()),
+            pin_id: Some(pin.pin_id.clone()),
+            to: Some(pin.to.clone()),
+            ..Default::default()
+        };
+        if voice {
+            response.call_status = Some("PENDING_ACCEPTED".to_string());
+        } else {
+            response.sms_status = Some("MESSAGE_SENT".to_string());
+
+            self.messages.push(SmsMessage {
+                bulk_id: core.next_id("fake-bulk"),
+                message_id: core.next_id("fake-message"),
+                from,
+                to: pin.to.clone(),
+                sms_count: sms_count(&text),
+                text: Some(text),
+                sent_at: now(),
+                done_at: Some(now()),
+                status,
+                reported: false,
+            });
+        }
+
+        Reply::ok(&response)
+    }
+
+    fn verify_phone_number(&mut self, pin_id: &str, request: &FakeRequest) -> Handled {
+        let body: VerifyPhoneNumberRequestBody = request.json()?;
+        let pin = self
+            .pins
+            .iter_mut()
+            .find(|pin| pin.pin_id == pin_id)
+            .ok_or_else(|| Reply::not_found("PIN not found"))?;
+
+        let pin_error = if pin.verified_at.is_some() {
+            Some("PIN_ALREADY_VERIFIED")
+        } else if pin.attempts_remaining == 0 {
+            Some("NO_MORE_PIN_ATTEMPTS")
+        } else {
+            pin.attempts_remaining -= 1;
+            if body.pin == pin.code {
+                pin.verified_at = Some(now_millis());
+                None
+            } else if pin.attempts_remaining == 0 {
+                Some("NO_MORE_PIN_ATTEMPTS")
+            } else {
+                Some("WRONG_PIN")
+            }
+        };
+
+        Ok(Reply::ok(&VerifyPhoneNumberResponseBody {
+            attempts_remaining: Some(pin.attempts_remaining),
+            msisdn: Some(pin.to.clone()),
+            pin_error: pin_error.map(str::to_string),
+            pin_id: Some(pin.pin_id.clone()),
+            verified: Some(pin.verified_at.is_some()),
+        }))
+    }
+
+    fn tfa_verification_status(&self, app_id: &str, request: &FakeRequest) -> Handled {
+        self.application(app_id)?;
+        let msisdn = request.query("msisdn");
+        let verified = request.query("verified").map(|verified| verified == "true");
+
+        let verifications = self
+            .pins
+            .iter()
+            .filter(|pin| {
+                pin.application_id == app_id
+                    && msisdn.map_or(true, |msisdn| pin.to == msisdn)
+                    && verified.map_or(true, |verified| pin.verified_at.is_some() == verified)
+            })
+            .map(|pin| TfaVerification {
+                msisdn: Some(pin.to.clone()),
+                sent_at: Some(pin.sent_at),
+                verified: Some(pin.verified_at.is_some()),
+                verified_at: pin.verified_at,
+            })
+            .collect();
+
+        Ok(Reply::ok(&TfaVerificationStatusResponseBody {
+            verifications: Some(verifications),
+        }))
+    }
+}
+
+fn preview(request: &FakeRequest) -> Handled {
+    let body: PreviewRequestBody = request.json()?;
+    let (length, single, multiple) = segmentation(&body.text);
+    let message_count = sms_count(&body.text) as usize;
+    let capacity = if message_count == 1 {
+        single
+    } else {
+        message_count * multiple
+    };
+
+    Ok(Reply::ok(&PreviewResponseBody {
+        original_text: Some(body.text.clone()),
+        previews: Some(vec![Preview {
+            characters_remaining: Some((capacity - length) as i32),
+            configuration: Some(PreviewLanguageConfiguration::default()),
+            message_count: Some(message_count as i32),
+            text_preview: Some(body.text),
+        }]),
+    }))
+}
+
+// Returns the length of the text in GSM 7-bit characters, or `None` if it needs Unicode.
+fn gsm_length(text: &str) -> Option<usize> {
+    text.chars().try_fold(0, |length, character| {
+        if GSM_CHARACTERS.contains(character) {
+            Some(length + 1)
+        } else if GSM_EXTENSION_CHARACTERS.contains(character) {
+            Some(length + 2)
+        } else {
+            None
+        }
+    })
+}
+
+// Returns the length of the text, and how many characters fit in a single SMS and in each part
+// of a multipart one.
+fn segmentation(text: &str) -> (usize, usize, usize) {
+    match gsm_length(text) {
+        Some(length) => (length, 160, 153),
+        None => (text.encode_utf16().count(), 70, 67),
+    }
+}
+
+fn sms_count(text: &str) -> i32 {
+    let (length, single, multiple) = segmentation(text);
+
+    if length <= single {
+        1
+    } else {
+        ((length + multiple - 1) / multiple) as i32
+    }
+}
+
+fn pin_code(pin_type: PinType, pin_length: i32) -> String {
+    let alphabet: &[u8] = match pin_type {
+        PinType::Numeric => b"0123456789",
+        PinType::Alpha => b"ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+        PinType::Hex => b"0123456789ABCDEF",
+        PinType::Alphanumeric => b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+    };
+
+    (0..pin_length.max(1))
+        .map(|_| alphabet[fastrand::usize(..alphabet.len())] as char)
+        .collect()
+}
+
+fn status_of(status: DeliveryStatus) -> Status {
+    let (group_id, group_name, id, name, description) = status.fields();
+
+    Status {
+        action: None,
+        description: Some(description.to_string()),
+        group_id: Some(group_id),
+        group_name: Some(group_name.to_string()),
+        id: Some(id),
+        name: Some(name.to_string()),
+    }
+}
+
+fn error_of(status: DeliveryStatus) -> Error {
+    let (id, name, description, permanent) = match status {
+        DeliveryStatus::Undeliverable => (1, "EC_UNKNOWN_SUBSCRIBER", "Unknown Subscriber", true),
+        _ => (0, "NO_ERROR", "No Error", false),
+    };
+
+    Error {
+        description: Some(description.to_string()),
+        group_id: Some(if permanent { 1 } else { 0 }),
+        group_name: Some(if permanent { "HANDSET_ERRORS" } else { "OK" }.to_string()),
+        id: Some(id),
+        name: Some(name.to_string()),
+        permanent: Some(permanent),
+    }
+}
@@ -0,0 +1,216 @@
+//! WhatsApp endpoints of the fake.
+
+use reqwest::{Method, StatusCode};
+use serde_json::Value;
+
+use crate::api::testing::{Core, DeliveryStatus, FakeRequest, FakeServer, Handled, Reply};
+use crate::api::whatsapp::*;
+use crate::model::whatsapp::*;
+
+const MESSAGE_PATH_PREFIX: &str = "/whatsapp/1/message/";
+const BUSINESS_ACCOUNT_ID: i64 = 100_000_000;
+
+#[derive(Debug, Default)]
+pub(super) struct WhatsAppState {
+    messages: Vec<WhatsAppMessage>,
+    // Templates with the sender they were created for.
+    templates: Vec<(String, Template)>,
+}
+
+/// WhatsApp message accepted by the fake.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WhatsAppMessage {
+    pub message_id: String,
+    pub from: String,
+    pub to: String,
+
+    /// Kind of the message, from the path it was sent to, e.g. `text` or `interactive/buttons`.
+    pub kind: String,
+
+    /// Content of the message, as sent.
+    pub content: Value,
+}
+
+impl FakeServer {
+    /// Returns the WhatsApp messages that were sent, in order.
+    pub fn whatsapp_messages(&self) -> Vec<WhatsAppMessage> {
+        self.lock().whatsapp.messages.clone()
+    }
+}
+
+impl WhatsAppState {
+    // Returns the reply to the request, or `None` if the path is not one of WhatsApp.
+    pub(super) fn handle(&mut self, core: &mut Core, request: &FakeRequest) -> Option<Handled> {
+        let handled = if request.route(Method::POST, PATH_SEND_TEMPLATE).is_some() {
+            self.send_template(core, request)
+        } else if request.method == Method::POST
+            && request.path.starts_with(MESSAGE_PATH_PREFIX)
+            && is_message_path(&request.path)
+        {
+            self.send(core, request)
+        } else if let Some(params) = request.route(Method::POST, PATH_CREATE_TEMPLATE) {
+            self.create_template(core, &params[0], request)
+        } else if let Some(params) = request.route(Method::GET, PATH_GET_TEMPLATES) {
+            Ok(self.templates(&params[0]))
+        } else if let Some(params) = request.route(Method::DELETE, PATH_DELETE_TEMPLATE) {
+            self.delete_template(&params[0], &params[1])
+        } else {
+            return None;
+        };
+
+        Some(handled)
+    }
+
+    fn send(&mut self, core: &mut Core, request: &FakeRequest) -> Handled {
+        let body: Value = request.json()?;
+        let kind = request.path[MESSAGE_PATH_PREFIX.len()..].to_string();
+        let message = self.accept(core, &body, kind)?;
+
+        Ok(Reply::ok(&message))
+    }
+
+    fn send_template(&mut self, core: &mut Core, request: &FakeRequest) -> Handled {
+        let body: Value = request.json()?;
+        let messages = body
+            .get("messages")
+            .and_then(Value::as_array)
+            .filter(|messages| !messages.is_empty())
+            .ok_or_else(|| Reply::bad_request("Missing messages"))?;
+
+        let mut sent = Vec::new();
+        for message in messages {
+            sent.push(self.accept(core, message, "template".to_string())?);
+        }
+        let bulk_id = body
+            .get("bulkId")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| core.next_id("fake-bulk"));
+
+        Ok(Reply::ok(&SendTemplateResponseBody {
+            messages: Some(sent),
+            bulk_id: Some(bulk_id),
+        }))
+    }
+
+    fn accept(
+        &mut self,
+        core: &mut Core,
+        message: &Value,
+        kind: String,
+    ) -> Result<SendContentResponseBody, Reply> {
+        let field = |name: &str| {
+            message
+                .get(name)
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        };
+        let from = field("from").ok_or_else(|| Reply::bad_request("Missing sender"))?;
+        let to = field("to").ok_or_else(|| Reply::bad_request("Missing recipient"))?;
+        let message_id = field("messageId").unwrap_or_else(|| core.next_id("fake-message"));
+
+        self.messages.push(WhatsAppMessage {
+            message_id: message_id.clone(),
+            from,
+            to: to.clone(),
+            kind,
+            content: message.get("content").cloned().unwrap_or(Value::Null),
+        });
+
+        let (group_id, group_name, id, name, description) = DeliveryStatus::Pending.fields();
+        Ok(SendContentResponseBody {
+            to: Some(to),
+            message_count: Some(1),
+            message_id: Some(message_id),
+            status: Some(Status {
+                group_id: Some(group_id),
+                group_name: Some(group_name.to_string()),
+                action: None,
+                id: Some(id),
+                name: Some(name.to_string()),
+                description: Some(description.to_string()),
+            }),
+        })
+    }
+
+    fn create_template(&mut self, core: &mut Core, sender: &str, request: &FakeRequest) -> Handled {
+        let body: CreateTemplateRequestBody = request.json()?;
+        let exists = self.templates.iter().any(|(template_sender, template)| {
+            template_sender == sender
+                && template.name.as_deref() == Some(body.name.as_str())
+                && template.language == Some(body.language)
+        });
+        if exists {
+            return Err(Reply::bad_request("Template already exists"));
+        }
+
+        core.last_id += 1;
+        let template = Template {
+            id: Some(core.last_id.to_string()),
+            business_account_id: Some(BUSINESS_ACCOUNT_ID),
+            name: Some(body.name),
+            language: Some(body.language),
+            status: Some(TemplateStatus::Approved),
+            category: Some(body.category),
+            structure: Some(body.structure),
+        };
+        self.templates.push((sender.to_string(), template.clone()));
+
+        Ok(Reply::json(
+            StatusCode::CREATED,
+            &CreateTemplateResponseBody {
+                id: template.id,
+                business_account_id: template.business_account_id,
+                name: template.name,
+                language: template.language,
+                status: template.status,
+                category: template.category,
+                structure: template.structure,
+            },
+        ))
+    }
+
+    fn templates(&self, sender: &str) -> Reply {
+        let templates = self
+            .templates
+            .iter()
+            .filter(|(template_sender, _)| template_sender == sender)
+            .map(|(_, template)| template.clone())
+            .collect();
+
+        Reply::ok(&TemplatesResponseBody {
+            templates: Some(templates),
+        })
+    }
+
+    fn delete_template(&mut self, sender: &str, template_name: &str) -> Handled {
+        let matches = |(template_sender, template): &(String, Template)| {
+            template_sender == sender && template.name.as_deref() == Some(template_name)
+        };
+        if !self.templates.iter().any(matches) {
+            return Err(Reply::not_found("Template not found"));
+        }
+        self.templates.retain(|template| !matches(template));
+
+        Ok(Reply::empty(StatusCode::NO_CONTENT))
+    }
+}
+
+// Returns whether the path is one of the paths to send a message to.
+fn is_message_path(path: &str) -> bool {
+    [
+        PATH_SEND_AUDIO,
+        PATH_SEND_CONTACT,
+        PATH_SEND_DOCUMENT,
+        PATH_SEND_IMAGE,
+        PATH_SEND_INTERACTIVE_BUTTONS,
+        PATH_SEND_INTERACTIVE_LIST,
+        PATH_SEND_INTERACTIVE_MULTIPRODUCT,
+        PATH_SEND_INTERACTIVE_PRODUCT,
+        PATH_SEND_LOCATION,
+        PATH_SEND_STICKER,
+        PATH_SEND_TEXT,
+        PATH_SEND_VIDEO,
+    ]
+    .contains(&path)
+}
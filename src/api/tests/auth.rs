@@ -0,0 +1,269 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use httpmock::prelude::*;
+use reqwest::StatusCode;
+
+use crate::api::auth::{OAuth2TokenProvider, TokenFuture, TokenProvider, PATH_TOKEN};
+use crate::api::sms::{SmsClient, PATH_PREVIEW};
+use crate::api::tests::{test_configuration, DUMMY_TEXT};
+use crate::api::SdkError;
+use crate::configuration::Configuration;
+use crate::model::sms::PreviewRequestBody;
+
+#[cfg(tokio)]
+use crate::api::sms::BlockingSmsClient;
+
+// Returns `token-<n>`, where `n` grows every time a token is invalidated.
+#[derive(Debug, Default)]
+struct CountingTokenProvider {
+    generation: AtomicUsize,
+}
+
+impl TokenProvider for CountingTokenProvider {
+    fn token(&self) -> TokenFuture<'_> {
+        Box::pin(
+            async move { Ok(format!("token-{}", self.generation.load(Ordering::SeqCst)).into()) },
+        )
+    }
+
+    fn invalidate(&self, _token: &str) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn mock_token_endpoint(server: &MockServer, expires_in: u64) -> httpmock::Mock<'_> {
+    server.mock(|when, then| {
+        when.method(POST)
+            .path(PATH_TOKEN)
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body("client_id=some-id&client_secret=some-secret&grant_type=client_credentials");
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(format!(
+                r#"{{"access_token": "some-token", "expires_in": {}, "token_type": "bearer"}}"#,
+                expires_in
+            ));
+    })
+}
+
+fn mock_preview_endpoint<'a>(server: &'a MockServer, token: &str) -> httpmock::Mock<'a> {
+    server.mock(|when, then| {
+        when.method(POST)
+            .path(PATH_PREVIEW)
+            .header("authorization", format!("Bearer {}", token));
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body("{}");
+    })
+}
+
+fn oauth2_configuration(server: &MockServer, refresh_margin_secs: u64) -> Configuration {
+    let mut configuration = Configuration::builder(server.base_url()).build().unwrap();
+    let token_provider = OAuth2TokenProvider::new(&configuration, "some-id", "some-secret")
+        .with_refresh_margin(std::time::Duration::from_secs(refresh_margin_secs));
+    configuration.set_token_provider(Arc::new(token_provider));
+
+    configuration
+}
+
+#[tokio::test]
+async fn test_oauth2_token_is_cached() {
+    let server = MockServer::start_async().await;
+    let token_mock = mock_token_endpoint(&server, 3600);
+    let preview_mock = mock_preview_endpoint(&server, "some-token");
+    let client = SmsClient::with_configuration(oauth2_configuration(&server, 60));
+
+    for _ in 0..3 {
+        let response = client
+            .preview(PreviewRequestBody::new(DUMMY_TEXT))
+            .await
+            .unwrap();
+        assert_eq!(response.status, StatusCode::OK);
+    }
+
+    assert_eq!(token_mock.hits(), 1);
+    assert_eq!(preview_mock.hits(), 3);
+}
+
+#[tokio::test]
+async fn test_oauth2_token_is_refreshed_before_expiry() {
+    let server = MockServer::start_async().await;
+    let token_mock = mock_token_endpoint(&server, 0);
+    mock_preview_endpoint(&server, "some-token");
+    let client = SmsClient::with_configuration(oauth2_configuration(&server, 60));
+
+    for _ in 0..2 {
+        client
+            .preview(PreviewRequestBody::new(DUMMY_TEXT))
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(token_mock.hits(), 2);
+}
+
+#[tokio::test]
+async fn test_oauth2_token_endpoint_error() {
+    let server = MockServer::start_async().await;
+    server.mock(|when, then| {
+        when.method(POST).path(PATH_TOKEN);
+        then.status(StatusCode::UNAUTHORIZED.as_u16())
+            .body(r#"{"error": "invalid_client", "error_description": "Unknown client"}"#);
+    });
+    let preview_mock = mock_preview_endpoint(&server, "some-token");
+    let client = SmsClient::with_configuration(oauth2_configuration(&server, 60));
+
+    let error = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap_err();
+
+    match error {
+        SdkError::Token(message) => assert_eq!(
+            message,
+            "token endpoint returned 401 Unauthorized: invalid_client: Unknown client"
+        ),
+        error => panic!("unexpected error: {error:?}"),
+    }
+    assert_eq!(preview_mock.hits(), 0);
+}
+
+#[tokio::test]
+async fn test_oauth2_token_endpoint_error_without_oauth2_body() {
+    let server = MockServer::start_async().await;
+    server.mock(|when, then| {
+        when.method(POST).path(PATH_TOKEN);
+        then.status(StatusCode::BAD_GATEWAY.as_u16())
+            .body("upstream unavailable\n");
+    });
+    let client = SmsClient::with_configuration(oauth2_configuration(&server, 60));
+
+    let error = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap_err();
+
+    match error {
+        SdkError::Token(message) => assert_eq!(
+            message,
+            "token endpoint returned 502 Bad Gateway: upstream unavailable"
+        ),
+        error => panic!("unexpected error: {error:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_oauth2_token_with_huge_lifetime_does_not_expire() {
+    let server = MockServer::start_async().await;
+    let token_mock = mock_token_endpoint(&server, u64::MAX);
+    let preview_mock = mock_preview_endpoint(&server, "some-token");
+    let client = SmsClient::with_configuration(oauth2_configuration(&server, 60));
+
+    for _ in 0..2 {
+        client
+            .preview(PreviewRequestBody::new(DUMMY_TEXT))
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(token_mock.hits(), 1);
+    assert_eq!(preview_mock.hits(), 2);
+}
+
+#[tokio::test]
+async fn test_unauthorized_refreshes_token_once() {
+    let server = MockServer::start_async().await;
+    let rejected_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path(PATH_PREVIEW)
+            .header("authorization", "Bearer token-0");
+        then.status(StatusCode::UNAUTHORIZED.as_u16())
+            .header("content-type", "application/json")
+            .body(r#"{"requestError": {"serviceException": {"messageId": "UNAUTHORIZED"}}}"#);
+    });
+    let accepted_mock = mock_preview_endpoint(&server, "token-1");
+
+    let mut configuration = test_configuration(&server.base_url());
+    configuration.set_token_provider(Arc::new(CountingTokenProvider::default()));
+    let client = SmsClient::with_configuration(configuration);
+
+    let response = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(rejected_mock.hits(), 1);
+    assert_eq!(accepted_mock.hits(), 1);
+}
+
+#[tokio::test]
+async fn test_unauthorized_after_refresh_is_returned() {
+    let server = MockServer::start_async().await;
+    let mock = server.mock(|when, then| {
+        when.method(POST).path(PATH_PREVIEW);
+        then.status(StatusCode::UNAUTHORIZED.as_u16())
+            .header("content-type", "application/json")
+            .body(r#"{"requestError": {"serviceException": {"messageId": "UNAUTHORIZED"}}}"#);
+    });
+
+    let mut configuration = test_configuration(&server.base_url());
+    configuration.set_token_provider(Arc::new(CountingTokenProvider::default()));
+    let client = SmsClient::with_configuration(configuration);
+
+    let error = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap_err();
+
+    match error {
+        SdkError::Unauthorized(error) => assert_eq!(error.status, StatusCode::UNAUTHORIZED),
+        error => panic!("unexpected error: {error:?}"),
+    }
+    assert_eq!(mock.hits(), 2);
+}
+
+#[cfg(tokio)]
+#[test]
+fn test_blocking_oauth2_token() {
+    let server = MockServer::start();
+    let token_mock = mock_token_endpoint(&server, 3600);
+    let preview_mock = mock_preview_endpoint(&server, "some-token");
+    let client = BlockingSmsClient::with_configuration(oauth2_configuration(&server, 60));
+
+    let response = client.preview(PreviewRequestBody::new(DUMMY_TEXT)).unwrap();
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(token_mock.hits(), 1);
+    assert_eq!(preview_mock.hits(), 1);
+}
+
+#[cfg(any(tokio, not(async_io)))]
+#[tokio::test]
+async fn test_oauth2_token_requests_use_configuration_transport() {
+    let server = MockServer::start_async().await;
+    let token_mock = mock_token_endpoint(&server, 3600);
+    // The base URL does not resolve, so the token is only obtained through the proxy.
+    let configuration = Configuration::builder("http://some.api.infobip.invalid")
+        .proxy(reqwest::Proxy::http(server.base_url()).unwrap())
+        .build()
+        .unwrap();
+    let token_provider = OAuth2TokenProvider::new(&configuration, "some-id", "some-secret");
+
+    let token = token_provider.token().await.unwrap();
+
+    assert_eq!(token.expose_secret(), "some-token");
+    token_mock.assert();
+}
+
+#[test]
+fn test_oauth2_token_provider_is_redacted() {
+    let configuration = test_configuration("https://some.api.infobip.com");
+    let token_provider = OAuth2TokenProvider::new(&configuration, "some-id", "some-secret");
+
+    let debug = format!("{:?}", token_provider);
+
+    assert!(debug.contains("some-id"));
+    assert!(!debug.contains("some-secret"));
+}
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use httpmock::prelude::*;
+use reqwest::StatusCode;
+
+use crate::api::{
+    client::InfobipClient,
+    email::PATH_GET_DOMAIN,
+    sms::PATH_PREVIEW,
+    tests::{test_configuration, DUMMY_TEXT},
+    whatsapp::PATH_GET_TEMPLATES,
+};
+use crate::model::sms::PreviewRequestBody;
+
+#[test]
+fn test_channel_clients_share_transport() {
+    let client = InfobipClient::with_configuration(test_configuration("https://some.url"));
+
+    assert!(Arc::ptr_eq(&client.sms().transport, client.transport()));
+    assert!(Arc::ptr_eq(&client.email().transport, client.transport()));
+    assert!(Arc::ptr_eq(
+        &client.whatsapp().transport,
+        client.transport()
+    ));
+    assert_eq!(client.sms().configuration.base_url(), "https://some.url");
+}
+
+#[tokio::test]
+async fn test_channel_clients_send_requests() {
+    let server = MockServer::start_async().await;
+    let sms_mock = server.mock(|when, then| {
+        when.method(POST).path(PATH_PREVIEW);
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body("{}");
+    });
+    let email_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path(PATH_GET_DOMAIN.replace("{domainName}", "example.com"));
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(r#"{"domainName": "example.com"}"#);
+    });
+    let whatsapp_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path(PATH_GET_TEMPLATES.replace("{sender}", "44444444444"));
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(r#"{"templates": []}"#);
+    });
+
+    let client = InfobipClient::with_configuration(test_configuration(&server.base_url()));
+
+    client
+        .sms()
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap();
+    client.email().domain("example.com").await.unwrap();
+    client.whatsapp().templates("44444444444").await.unwrap();
+
+    sms_mock.assert();
+    email_mock.assert();
+    whatsapp_mock.assert();
+}
@@ -0,0 +1,192 @@
+use std::time::Duration;
+
+use httpmock::prelude::*;
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::StatusCode;
+
+use crate::api::sms::{SmsClient, PATH_PREVIEW};
+use crate::api::testing::{FakeServer, Fault};
+use crate::api::tests::{test_configuration, DUMMY_TEXT};
+use crate::api::{SdkError, CORRELATION_ID_HEADER};
+use crate::configuration::{ApiKey, Configuration, RetryPolicy};
+use crate::model::sms::PreviewRequestBody;
+
+#[cfg(tokio)]
+use crate::api::sms::BlockingSmsClient;
+
+const ERROR_RESPONSE: &str = r#"
+    {
+      "requestError": {
+        "serviceException": {
+          "messageId": "SERVICE_UNAVAILABLE",
+          "text": "Service unavailable"
+        }
+      }
+    }
+"#;
+
+#[tokio::test]
+async fn test_generated_correlation_id() {
+    let server = MockServer::start_async().await;
+    let preview_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path(PATH_PREVIEW)
+            .header_exists(CORRELATION_ID_HEADER);
+        then.status(StatusCode::OK.as_u16()).body("{}");
+    });
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let first = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap();
+    let second = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap();
+
+    preview_mock.assert_hits(2);
+    let correlation_id = &first.metadata.correlation_id;
+    assert_eq!(correlation_id.len(), 36);
+    assert_eq!(&correlation_id[14..15], "4");
+    assert_ne!(correlation_id, &second.metadata.correlation_id);
+}
+
+#[tokio::test]
+async fn test_client_correlation_id() {
+    let server = MockServer::start_async().await;
+    let preview_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path(PATH_PREVIEW)
+            .header(CORRELATION_ID_HEADER, "some-correlation-id");
+        then.status(StatusCode::OK.as_u16()).body("{}");
+    });
+    let configuration = Configuration::builder(server.base_url())
+        .api_key(ApiKey::new("some-api-key".into()))
+        .correlation_id("some-correlation-id")
+        .default_header(
+            HeaderName::from_static("x-correlation-id"),
+            HeaderValue::from_static("other-correlation-id"),
+        )
+        .build()
+        .unwrap();
+    let client = SmsClient::with_configuration(configuration);
+
+    let response = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap();
+
+    preview_mock.assert();
+    assert_eq!(response.metadata.correlation_id, "some-correlation-id");
+}
+
+#[tokio::test]
+async fn test_call_correlation_id_on_error() {
+    let server = MockServer::start_async().await;
+    let preview_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path(PATH_PREVIEW)
+            .header(CORRELATION_ID_HEADER, "some-correlation-id");
+        then.status(StatusCode::SERVICE_UNAVAILABLE.as_u16())
+            .header("content-type", "application/json")
+            .body(ERROR_RESPONSE);
+    });
+    let mut configuration = test_configuration(&server.base_url());
+    configuration.set_retry_policy(RetryPolicy {
+        base_delay: Duration::from_millis(1),
+        jitter: false,
+        ..Default::default()
+    });
+    let client = SmsClient::with_configuration(configuration);
+
+    let error = client
+        .with_correlation_id("some-correlation-id")
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap_err();
+
+    // Every retry carries the same ID.
+    preview_mock.assert_hits(3);
+    assert_eq!(error.correlation_id(), Some("some-correlation-id"));
+}
+
+#[tokio::test]
+async fn test_correlation_id_without_response() {
+    let client = SmsClient::with_configuration(test_configuration("http://127.0.0.1:9"));
+
+    let error = client
+        .with_correlation_id("some-correlation-id")
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        SdkError::Reqwest { .. } | SdkError::Connect { .. }
+    ));
+    assert_eq!(error.correlation_id(), Some("some-correlation-id"));
+}
+
+#[tokio::test]
+async fn test_generated_correlation_id_on_timeout() {
+    let server = MockServer::start_async().await;
+    let preview_mock = server.mock(|when, then| {
+        when.method(POST).path(PATH_PREVIEW);
+        then.status(StatusCode::OK.as_u16())
+            .body("{}")
+            .delay(Duration::from_secs(2));
+    });
+    let configuration = Configuration::builder(server.base_url())
+        .api_key(ApiKey::new("some-api-key".into()))
+        .timeout(Duration::from_millis(100))
+        .build()
+        .unwrap();
+    let client = SmsClient::with_configuration(configuration);
+
+    let error = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap_err();
+
+    preview_mock.assert();
+    assert!(error.is_timeout());
+    assert_eq!(error.correlation_id().map(str::len), Some(36));
+}
+
+#[tokio::test]
+async fn test_correlation_id_on_transport_error() {
+    let server = FakeServer::new();
+    server.inject_fault(PATH_PREVIEW, Fault::Disconnect, 1);
+    let client = server
+        .client()
+        .sms()
+        .with_correlation_id("some-correlation-id");
+
+    let error = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, SdkError::Transport { .. }));
+    assert_eq!(error.correlation_id(), Some("some-correlation-id"));
+}
+
+#[cfg(tokio)]
+#[test]
+fn test_blocking_correlation_id() {
+    let server = MockServer::start();
+    let preview_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path(PATH_PREVIEW)
+            .header(CORRELATION_ID_HEADER, "some-correlation-id");
+        then.status(StatusCode::OK.as_u16()).body("{}");
+    });
+    let client = BlockingSmsClient::with_configuration(test_configuration(&server.base_url()))
+        .with_correlation_id("some-correlation-id");
+
+    let response = client.preview(PreviewRequestBody::new(DUMMY_TEXT)).unwrap();
+
+    preview_mock.assert();
+    assert_eq!(response.metadata.correlation_id, "some-correlation-id");
+}
@@ -3,6 +3,9 @@ use crate::api::tests::{mock_json_endpoint, test_configuration};
 use crate::api::SdkError;
 use crate::model::email::*;
 
+#[cfg(tokio)]
+use crate::api::tests::mock_blocking_json_endpoint;
+
 const DUMMY_BASE_URL: &str = "https://some.url";
 
 #[tokio::test]
@@ -55,6 +58,33 @@ async fn test_send_valid() {
     assert!(!response.body.messages.unwrap().is_empty());
 }
 
+#[tokio::test]
+async fn test_send_attachment_contents() {
+    let server = httpmock::MockServer::start_async().await;
+    let mock = server.mock(|when, then| {
+        when.method(httpmock::Method::POST)
+            .path(PATH_SEND)
+            .body_contains(r#"name="attachment"; filename="notes.txt""#)
+            .body_contains("Some notes.")
+            .body_contains(r#"name="inlineImage"; filename="logo.png""#);
+
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(r#"{"bulkId": "some-bulk-id", "messages": []}"#);
+    });
+
+    let client = EmailClient::with_configuration(test_configuration(&server.base_url()));
+
+    let mut request_body = SendRequestBody::new("some@mail.com");
+    request_body.attachment_contents = Some(vec![Attachment::new("notes.txt", "Some notes.")]);
+    request_body.inline_image_contents = Some(vec![Attachment::new("logo.png", vec![0x89, 0x50])]);
+
+    let response = client.send(request_body).await.unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(response.body.bulk_id.unwrap(), "some-bulk-id");
+}
+
 #[tokio::test]
 async fn test_send_invalid_request() {
     let client = EmailClient::with_configuration(test_configuration(DUMMY_BASE_URL));
@@ -584,3 +614,60 @@ async fn verify_domain_valid() {
 
     assert_eq!(status, reqwest::StatusCode::ACCEPTED);
 }
+
+#[cfg(tokio)]
+#[test]
+fn blocking_send_valid() {
+    let expected_response = r#"
+    {
+      "bulkId": "4pk1xihiy4rln2f1g2se",
+      "messages": [
+        {
+          "to": "some@mail.com",
+          "messageId": "tu5k6tdo7df1bpgk7ggs",
+          "status": {
+            "groupId": 1,
+            "groupName": "PENDING",
+            "id": 26,
+            "name": "PENDING_ACCEPTED",
+            "description": "Message accepted, pending for delivery."
+          }
+        }
+      ]
+    }
+    "#;
+
+    let server = mock_blocking_json_endpoint(
+        httpmock::Method::POST,
+        PATH_SEND,
+        expected_response,
+        reqwest::StatusCode::OK,
+    );
+
+    let client = BlockingEmailClient::with_configuration(test_configuration(&server.base_url()));
+
+    let response = client.send(SendRequestBody::new("some@mail.com")).unwrap();
+
+    assert_eq!(response.status, reqwest::StatusCode::OK);
+    assert_eq!(response.body.bulk_id.unwrap(), "4pk1xihiy4rln2f1g2se");
+}
+
+#[cfg(tokio)]
+#[test]
+fn blocking_delete_domain_valid() {
+    let domain_name = "newDomain.com";
+    let path = PATH_DELETE_DOMAIN.replace("{domainName}", domain_name);
+
+    let server = mock_blocking_json_endpoint(
+        httpmock::Method::DELETE,
+        path.as_str(),
+        "",
+        reqwest::StatusCode::NO_CONTENT,
+    );
+
+    let client = BlockingEmailClient::with_configuration(test_configuration(&server.base_url()));
+
+    let status = client.delete_domain(domain_name).unwrap();
+
+    assert_eq!(status, reqwest::StatusCode::NO_CONTENT);
+}
@@ -0,0 +1,253 @@
+use httpmock::prelude::*;
+use reqwest::{Method, StatusCode};
+use serde::de::IgnoredAny;
+use serde_derive::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::api::endpoint::{Endpoint, Json, NoBody};
+use crate::api::sms::endpoints::{GetTfaApplication, Preview};
+use crate::api::sms::{SmsClient, PATH_GET_TFA_VERIFICATION_STATUS, PATH_PREVIEW};
+use crate::api::tests::{test_configuration, DUMMY_TEXT};
+use crate::api::SdkError;
+use crate::model::sms::{PreviewRequestBody, TfaVerificationStatusQueryParameters};
+
+#[cfg(tokio)]
+use crate::api::sms::BlockingSmsClient;
+
+const PATH_UPDATE_SENDER: &str = "/custom/1/senders/{sender}";
+
+#[derive(Debug, Serialize)]
+struct SenderQuery {
+    force: bool,
+}
+
+#[derive(Debug, Serialize, Validate)]
+struct SenderBody {
+    #[validate(length(min = 1))]
+    name: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Sender {
+    id: String,
+    name: String,
+}
+
+// Endpoint the SDK does not cover, defined as a user of the crate would.
+struct UpdateSender {
+    sender: String,
+    query: SenderQuery,
+    body: Json<SenderBody>,
+}
+
+impl UpdateSender {
+    fn new(sender: &str, name: &str) -> Self {
+        UpdateSender {
+            sender: sender.to_string(),
+            query: SenderQuery { force: true },
+            body: Json(SenderBody {
+                name: name.to_string(),
+            }),
+        }
+    }
+}
+
+impl Endpoint for UpdateSender {
+    type Query = SenderQuery;
+    type Body = Json<SenderBody>;
+    type Response = Sender;
+
+    fn method(&self) -> Method {
+        Method::PUT
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_UPDATE_SENDER
+    }
+
+    fn path_params(&self) -> Vec<(&str, &str)> {
+        vec![("sender", &self.sender)]
+    }
+
+    fn query(&self) -> Option<&Self::Query> {
+        Some(&self.query)
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+struct DeleteSender;
+
+impl Endpoint for DeleteSender {
+    type Query = ();
+    type Body = NoBody;
+    type Response = IgnoredAny;
+
+    fn method(&self) -> Method {
+        Method::DELETE
+    }
+
+    fn path_template(&self) -> &str {
+        "/custom/1/senders/some-sender"
+    }
+}
+
+#[tokio::test]
+async fn test_execute_custom_endpoint() {
+    let server = MockServer::start_async().await;
+    let sender_mock = server.mock(|when, then| {
+        when.method(PUT)
+            .path("/custom/1/senders/some-sender")
+            .query_param("force", "true")
+            .header("authorization", "App some-api-key")
+            .json_body(serde_json::json!({"name": "Some name"}));
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(r#"{"id": "some-sender", "name": "Some name"}"#);
+    });
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let response = client
+        .execute(UpdateSender::new("some-sender", "Some name"))
+        .await
+        .unwrap();
+
+    sender_mock.assert_async().await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(
+        response.body,
+        Sender {
+            id: "some-sender".to_string(),
+            name: "Some name".to_string(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_execute_validates_before_sending() {
+    let server = MockServer::start_async().await;
+    let sender_mock = server.mock(|when, then| {
+        when.any_request();
+        then.status(StatusCode::OK.as_u16()).body("{}");
+    });
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let error = client
+        .execute(UpdateSender::new("some-sender", ""))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, SdkError::Validation(_)));
+    sender_mock.assert_hits_async(0).await;
+}
+
+#[tokio::test]
+async fn test_execute_response_without_body() {
+    let server = MockServer::start_async().await;
+    server.mock(|when, then| {
+        when.method(DELETE).path("/custom/1/senders/some-sender");
+        then.status(StatusCode::NO_CONTENT.as_u16());
+    });
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let response = client.execute(DeleteSender).await.unwrap();
+
+    assert_eq!(response.status, StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn test_execute_maps_api_errors() {
+    let server = MockServer::start_async().await;
+    server.mock(|when, then| {
+        when.method(GET).path("/2fa/2/applications/some-app");
+        then.status(StatusCode::NOT_FOUND.as_u16())
+            .header("content-type", "application/json")
+            .body(
+                r#"{"requestError": {"serviceException": {"messageId": "NOT_FOUND", "text": "Not found"}}}"#,
+            );
+    });
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let error = client
+        .execute(GetTfaApplication::new("some-app"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, SdkError::NotFound(_)));
+}
+
+#[tokio::test]
+async fn test_client_method_and_endpoint_send_same_request() {
+    let server = MockServer::start_async().await;
+    let preview_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path(PATH_PREVIEW)
+            .json_body(serde_json::json!({"text": DUMMY_TEXT}));
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(r#"{"originalText": "Some text for tests.", "previews": []}"#);
+    });
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let from_method = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap();
+    let from_endpoint = client
+        .execute(Preview::new(PreviewRequestBody::new(DUMMY_TEXT)))
+        .await
+        .unwrap();
+
+    preview_mock.assert_hits_async(2).await;
+    assert_eq!(from_method.body, from_endpoint.body);
+}
+
+#[tokio::test]
+async fn test_tfa_verification_status_sends_query() {
+    let server = MockServer::start_async().await;
+    let status_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path(PATH_GET_TFA_VERIFICATION_STATUS.replace("{appId}", "some-app"))
+            .query_param("msisdn", "41793026727")
+            .query_param("verified", "true");
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(r#"{"verifications": []}"#);
+    });
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+    let mut query_parameters = TfaVerificationStatusQueryParameters::new("41793026727");
+    query_parameters.verified = Some(true);
+
+    client
+        .tfa_verification_status("some-app", query_parameters)
+        .await
+        .unwrap();
+
+    status_mock.assert_async().await;
+}
+
+#[cfg(tokio)]
+#[test]
+fn test_blocking_execute_custom_endpoint() {
+    let server = MockServer::start();
+    let sender_mock = server.mock(|when, then| {
+        when.method(PUT).path("/custom/1/senders/some-sender");
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(r#"{"id": "some-sender", "name": "Some name"}"#);
+    });
+    let client = BlockingSmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let response = client
+        .execute(UpdateSender::new("some-sender", "Some name"))
+        .unwrap();
+
+    sender_mock.assert();
+    assert_eq!(response.body.name, "Some name");
+}
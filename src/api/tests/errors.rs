@@ -0,0 +1,241 @@
+use std::time::Duration;
+
+use httpmock::prelude::*;
+use reqwest::StatusCode;
+
+use crate::api::sms::{SmsClient, PATH_PREVIEW};
+use crate::api::tests::{test_configuration, DUMMY_TEXT};
+use crate::api::SdkError;
+use crate::model::sms::PreviewRequestBody;
+
+#[cfg(tokio)]
+use crate::api::sms::BlockingSmsClient;
+
+const ERROR_RESPONSE: &str = r#"
+    {
+      "requestError": {
+        "serviceException": {
+          "messageId": "SOME_ERROR",
+          "text": "Some error"
+        }
+      }
+    }
+"#;
+
+async fn preview_error(status: StatusCode, headers: &[(&str, &str)]) -> SdkError {
+    let server = MockServer::start_async().await;
+    server.mock(|when, then| {
+        when.method(POST).path(PATH_PREVIEW);
+        let mut then = then
+            .status(status.as_u16())
+            .header("content-type", "application/json");
+        for (name, value) in headers {
+            then = then.header(*name, *value);
+        }
+        then.body(ERROR_RESPONSE);
+    });
+
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap_err()
+}
+
+async fn empty_preview_error(status: StatusCode, headers: &[(&str, &str)]) -> SdkError {
+    let server = MockServer::start_async().await;
+    server.mock(|when, then| {
+        when.method(POST).path(PATH_PREVIEW);
+        let mut then = then.status(status.as_u16());
+        for (name, value) in headers {
+            then = then.header(*name, *value);
+        }
+        then.body("");
+    });
+
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap_err()
+}
+
+#[tokio::test]
+async fn test_unauthorized() {
+    let error = preview_error(StatusCode::UNAUTHORIZED, &[]).await;
+
+    assert!(matches!(error, SdkError::Unauthorized(_)));
+    assert!(error.is_auth_error());
+    assert!(!error.is_retryable());
+    assert_eq!(error.status(), Some(StatusCode::UNAUTHORIZED));
+}
+
+#[tokio::test]
+async fn test_forbidden() {
+    let error = preview_error(StatusCode::FORBIDDEN, &[]).await;
+
+    assert!(matches!(error, SdkError::Forbidden(_)));
+    assert!(error.is_auth_error());
+    assert!(!error.is_retryable());
+}
+
+#[tokio::test]
+async fn test_not_found() {
+    let error = preview_error(StatusCode::NOT_FOUND, &[]).await;
+
+    assert!(matches!(error, SdkError::NotFound(_)));
+    assert!(error.is_not_found());
+    assert!(!error.is_retryable());
+}
+
+#[tokio::test]
+async fn test_rate_limited() {
+    let error = preview_error(StatusCode::TOO_MANY_REQUESTS, &[("retry-after", "7")]).await;
+
+    assert!(matches!(error, SdkError::RateLimited(_)));
+    assert!(error.is_rate_limited());
+    assert!(error.is_retryable());
+    assert_eq!(error.retry_after(), Some(Duration::from_secs(7)));
+}
+
+#[tokio::test]
+async fn test_rate_limited_without_retry_after() {
+    let error = preview_error(StatusCode::TOO_MANY_REQUESTS, &[]).await;
+
+    assert!(error.is_rate_limited());
+    assert_eq!(error.retry_after(), None);
+}
+
+#[tokio::test]
+async fn test_server_error() {
+    let error = preview_error(StatusCode::INTERNAL_SERVER_ERROR, &[]).await;
+
+    assert!(matches!(error, SdkError::ServerError(_)));
+    assert!(error.is_server_error());
+    assert!(error.is_retryable());
+    assert_eq!(
+        error
+            .api_error()
+            .unwrap()
+            .details
+            .request_error
+            .service_exception
+            .message_id
+            .as_deref(),
+        Some("SOME_ERROR")
+    );
+}
+
+#[tokio::test]
+async fn test_other_client_error() {
+    let error = preview_error(StatusCode::BAD_REQUEST, &[]).await;
+
+    assert!(matches!(error, SdkError::ApiRequestError(_)));
+    assert!(!error.is_retryable());
+    assert!(!error.is_auth_error());
+    assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+}
+
+#[tokio::test]
+async fn test_connection_error_is_retryable() {
+    // Nothing listens on port 9 of localhost.
+    let client = SmsClient::with_configuration(test_configuration("http://127.0.0.1:9"));
+
+    let error = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        SdkError::Reqwest { .. } | SdkError::Connect { .. }
+    ));
+    assert!(error.is_retryable());
+    assert_eq!(error.status(), None);
+}
+
+#[tokio::test]
+async fn test_html_error_response() {
+    let server = MockServer::start_async().await;
+    server.mock(|when, then| {
+        when.method(POST).path(PATH_PREVIEW);
+        then.status(StatusCode::BAD_GATEWAY.as_u16())
+            .header("content-type", "text/html")
+            .body("<html><body>Bad Gateway</body></html>");
+    });
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let error = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap_err();
+
+    match &error {
+        SdkError::UnexpectedResponse {
+            status,
+            body,
+            metadata,
+        } => {
+            assert_eq!(*status, StatusCode::BAD_GATEWAY);
+            assert_eq!(metadata.headers.get("content-type").unwrap(), "text/html");
+            assert_eq!(body, "<html><body>Bad Gateway</body></html>");
+        }
+        error => panic!("unexpected error: {error:?}"),
+    }
+    assert_eq!(error.status(), Some(StatusCode::BAD_GATEWAY));
+    assert!(error.is_server_error());
+    assert!(error.is_retryable());
+    assert!(error.api_error().is_none());
+}
+
+#[tokio::test]
+async fn test_empty_error_response() {
+    let error = empty_preview_error(StatusCode::SERVICE_UNAVAILABLE, &[]).await;
+
+    assert!(matches!(
+        &error,
+        SdkError::UnexpectedResponse { body, .. } if body.is_empty()
+    ));
+    assert!(error.is_server_error());
+}
+
+#[tokio::test]
+async fn test_empty_rate_limited_response() {
+    let error = empty_preview_error(StatusCode::TOO_MANY_REQUESTS, &[("retry-after", "3")]).await;
+
+    assert!(error.is_rate_limited());
+    assert!(error.is_retryable());
+    assert_eq!(error.retry_after(), Some(Duration::from_secs(3)));
+}
+
+#[tokio::test]
+async fn test_empty_unauthorized_response() {
+    let error = empty_preview_error(StatusCode::UNAUTHORIZED, &[]).await;
+
+    assert!(error.is_auth_error());
+    assert!(!error.is_retryable());
+}
+
+#[cfg(tokio)]
+#[test]
+fn test_blocking_html_error_response() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path(PATH_PREVIEW);
+        then.status(StatusCode::SERVICE_UNAVAILABLE.as_u16())
+            .header("content-type", "text/html")
+            .body("<html>Service Unavailable</html>");
+    });
+    let client = BlockingSmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let error = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        SdkError::UnexpectedResponse { status, .. } if status == StatusCode::SERVICE_UNAVAILABLE
+    ));
+}
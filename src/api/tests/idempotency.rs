@@ -0,0 +1,378 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+use crate::api::client::InfobipClient;
+use crate::api::email::{EmailClient, PATH_GET_LOGS as PATH_GET_EMAIL_LOGS};
+use crate::api::idempotency::generate_id;
+use crate::api::sms::{SmsClient, PATH_GET_LOGS, PATH_SEND};
+use crate::api::testing::{FakeServer, Fault};
+use crate::api::tests::{test_configuration, DUMMY_TEXT};
+use crate::api::transport::{Transport, TransportFuture};
+use crate::api::whatsapp::{WhatsAppClient, PATH_SEND_TEXT};
+use crate::api::SdkError;
+use crate::configuration::{Configuration, RetryPolicy};
+use crate::model::sms::{Destination, Message, SendRequestBody};
+use crate::model::whatsapp::{SendTextRequestBody, TextContent};
+
+const DUMMY_BASE_URL: &str = "https://some.url";
+
+const ERROR_RESPONSE: &str = r#"
+    {
+      "requestError": {
+        "serviceException": {
+          "messageId": "GATEWAY_TIMEOUT",
+          "text": "Gateway timeout"
+        }
+      }
+    }
+"#;
+
+const LOGS_RESPONSE: &str = r#"
+    {
+      "results": [
+        {
+          "bulkId": "some-bulk-id",
+          "messageId": "some-message-id",
+          "to": "41793026727",
+          "status": {
+            "groupId": 3,
+            "groupName": "DELIVERED",
+            "id": 5,
+            "name": "DELIVERED_TO_HANDSET"
+          }
+        }
+      ]
+    }
+"#;
+
+// Fails the first `failures` sends with a gateway timeout, and answers log requests with one
+// message if `logged` is set, or with no messages otherwise.
+#[derive(Debug, Default)]
+struct FlakyTransport {
+    failures: usize,
+    logged: bool,
+    sends: Mutex<Vec<serde_json::Value>>,
+    lookups: Mutex<Vec<reqwest::Url>>,
+}
+
+impl Transport for FlakyTransport {
+    fn execute(&self, request: reqwest::Request) -> TransportFuture<'_> {
+        let (status, body) = if request.url().path().ends_with("/logs") {
+            self.lookups.lock().unwrap().push(request.url().clone());
+            match self.logged {
+                true => (StatusCode::OK, LOGS_RESPONSE),
+                false => (StatusCode::OK, r#"{"results": []}"#),
+            }
+        } else {
+            let body = request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(|bytes| serde_json::from_slice(bytes).unwrap())
+                .unwrap_or_default();
+            let mut sends = self.sends.lock().unwrap();
+            sends.push(body);
+            match sends.len() <= self.failures {
+                true => (StatusCode::GATEWAY_TIMEOUT, ERROR_RESPONSE),
+                false => (StatusCode::OK, "{}"),
+            }
+        };
+
+        Box::pin(async move {
+            let response = http::Response::builder()
+                .status(status.as_u16())
+                .header("content-type", "application/json")
+                .body(body)
+                .unwrap();
+
+            Ok(reqwest::Response::from(response))
+        })
+    }
+}
+
+fn idempotent_configuration(max_attempts: u32) -> Configuration {
+    let mut configuration = test_configuration(DUMMY_BASE_URL);
+    configuration.set_idempotent_sends(true);
+    configuration.set_retry_policy(RetryPolicy {
+        max_attempts,
+        base_delay: Duration::from_millis(1),
+        jitter: false,
+        ..Default::default()
+    });
+
+    configuration
+}
+
+fn send_request_body() -> SendRequestBody {
+    let mut message = Message::new(vec![
+        Destination::new("41793026727"),
+        Destination::new("41793026728"),
+    ]);
+    message.text = Some(DUMMY_TEXT.to_string());
+
+    SendRequestBody::new(vec![message])
+}
+
+#[test]
+fn test_generate_id() {
+    let id = generate_id();
+
+    assert_eq!(id.len(), 32);
+    assert!(id.chars().all(|c| c.is_ascii_hexdigit()));
+    assert_ne!(id, generate_id());
+}
+
+#[tokio::test]
+async fn test_idempotent_send_fills_in_ids() {
+    let transport = Arc::new(FlakyTransport::default());
+    let client = SmsClient::with_transport(idempotent_configuration(1), transport.clone());
+
+    client.send(send_request_body()).await.unwrap();
+    client.send(send_request_body()).await.unwrap();
+
+    // Identical requests sent on purpose are told apart.
+    let sends = transport.sends.lock().unwrap();
+    assert_ne!(sends[0]["bulkId"], sends[1]["bulkId"]);
+    let bulk_id = sends[0]["bulkId"].as_str().unwrap();
+    assert_eq!(bulk_id.len(), 32);
+    let destinations = &sends[0]["messages"][0]["destinations"];
+    assert_eq!(destinations[0]["messageId"], format!("{}-0", bulk_id));
+    assert_eq!(destinations[1]["messageId"], format!("{}-1", bulk_id));
+}
+
+#[tokio::test]
+async fn test_idempotent_send_keeps_given_ids() {
+    let transport = Arc::new(FlakyTransport::default());
+    let client = SmsClient::with_transport(test_configuration(DUMMY_BASE_URL), transport.clone())
+        .with_idempotent_sends();
+    let mut request_body = send_request_body();
+    request_body.bulk_id = Some("some-bulk-id".to_string());
+    request_body.messages[0].destinations.as_mut().unwrap()[0].message_id =
+        Some("some-message-id".to_string());
+
+    client.send(request_body).await.unwrap();
+
+    let sends = transport.sends.lock().unwrap();
+    assert_eq!(sends[0]["bulkId"], "some-bulk-id");
+    let destinations = &sends[0]["messages"][0]["destinations"];
+    assert_eq!(destinations[0]["messageId"], "some-message-id");
+    assert_eq!(destinations[1]["messageId"], "some-bulk-id-1");
+}
+
+#[tokio::test]
+async fn test_send_without_idempotency_keeps_ids_empty() {
+    let transport = Arc::new(FlakyTransport::default());
+    let client = SmsClient::with_transport(test_configuration(DUMMY_BASE_URL), transport.clone());
+
+    client.send(send_request_body()).await.unwrap();
+
+    let sends = transport.sends.lock().unwrap();
+    assert!(sends[0].get("bulkId").is_none());
+}
+
+#[tokio::test]
+async fn test_idempotent_send_found_in_logs() {
+    let transport = Arc::new(FlakyTransport {
+        failures: 1,
+        logged: true,
+        ..Default::default()
+    });
+    let client = SmsClient::with_transport(idempotent_configuration(3), transport.clone());
+
+    let response = client.send(send_request_body()).await.unwrap();
+
+    assert_eq!(response.status, StatusCode::OK);
+    let bulk_id = response.body.bulk_id.unwrap();
+    assert_eq!(
+        transport.sends.lock().unwrap()[0]["bulkId"],
+        bulk_id.as_str()
+    );
+    let messages = response.body.messages.unwrap();
+    assert_eq!(messages[0].message_id.as_deref(), Some("some-message-id"));
+    assert_eq!(messages[0].to.as_deref(), Some("41793026727"));
+    assert_eq!(transport.sends.lock().unwrap().len(), 1);
+    let lookups = transport.lookups.lock().unwrap();
+    assert_eq!(lookups.len(), 1);
+    assert_eq!(lookups[0].path(), PATH_GET_LOGS);
+    assert!(lookups[0]
+        .query_pairs()
+        .any(|(key, value)| key == "bulkId" && value == bulk_id));
+}
+
+#[tokio::test]
+async fn test_idempotent_send_resent_if_not_logged() {
+    let transport = Arc::new(FlakyTransport {
+        failures: 1,
+        ..Default::default()
+    });
+    let client = SmsClient::with_transport(idempotent_configuration(3), transport.clone());
+
+    let response = client.send(send_request_body()).await.unwrap();
+
+    assert_eq!(response.status, StatusCode::OK);
+    let sends = transport.sends.lock().unwrap();
+    assert_eq!(sends.len(), 2);
+    assert_eq!(sends[0], sends[1]);
+    assert_eq!(transport.lookups.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_idempotent_send_gives_up_after_max_attempts() {
+    let transport = Arc::new(FlakyTransport {
+        failures: 5,
+        ..Default::default()
+    });
+    let client = SmsClient::with_transport(idempotent_configuration(2), transport.clone());
+
+    let error = client.send(send_request_body()).await.unwrap_err();
+
+    assert!(matches!(error, SdkError::ServerError(_)));
+    assert_eq!(transport.sends.lock().unwrap().len(), 2);
+    assert_eq!(transport.lookups.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_idempotent_send_binary_fills_in_ids() {
+    use crate::model::sms::{BinaryData, BinaryMessage, SendBinaryRequestBody};
+
+    let transport = Arc::new(FlakyTransport::default());
+    let client = SmsClient::with_transport(idempotent_configuration(1), transport.clone());
+    let mut message = BinaryMessage::new(vec![Destination::new("41793026727")]);
+    message.binary = Some(BinaryData::new("0f c2 4a bf 34 13 ba"));
+    let request_body = SendBinaryRequestBody::new(vec![message]);
+
+    client.send_binary(request_body).await.unwrap();
+
+    let sends = transport.sends.lock().unwrap();
+    let bulk_id = sends[0]["bulkId"].as_str().unwrap();
+    assert_eq!(bulk_id.len(), 32);
+    assert_eq!(
+        sends[0]["messages"][0]["destinations"][0]["messageId"],
+        format!("{}-0", bulk_id)
+    );
+}
+
+#[tokio::test]
+async fn test_idempotent_email_send_found_in_logs() {
+    use crate::model::email::SendRequestBody;
+
+    let transport = Arc::new(FlakyTransport {
+        failures: 1,
+        logged: true,
+        ..Default::default()
+    });
+    let client = EmailClient::with_transport(idempotent_configuration(3), transport.clone());
+    let mut request_body = SendRequestBody::new("someone@domain.com");
+    request_body.text = Some(DUMMY_TEXT.to_string());
+
+    let response = client.send(request_body).await.unwrap();
+
+    let bulk_id = response.body.bulk_id.unwrap();
+    assert_eq!(transport.sends.lock().unwrap().len(), 1);
+    let lookups = transport.lookups.lock().unwrap();
+    assert_eq!(lookups[0].path(), PATH_GET_EMAIL_LOGS);
+    assert!(lookups[0]
+        .query_pairs()
+        .any(|(key, value)| key == "bulkId" && value == bulk_id));
+}
+
+#[tokio::test]
+async fn test_idempotent_email_message_id_only_for_one_recipient() {
+    use crate::model::email::SendRequestBody;
+
+    let server = FakeServer::new();
+    let client = server.client().email().with_idempotent_sends();
+    let mut request_body = SendRequestBody::new("someone@domain.com");
+    request_body.text = Some(DUMMY_TEXT.to_string());
+
+    let body = client.send(request_body.clone()).await.unwrap().body;
+    let bulk_id = body.bulk_id.unwrap();
+    let messages = body.messages.unwrap();
+    assert_eq!(messages[0].message_id, Some(format!("{}-0", bulk_id)));
+
+    request_body.to = "someone@domain.com, someone.else@domain.com".to_string();
+    let body = client.send(request_body).await.unwrap().body;
+    let bulk_id = body.bulk_id.unwrap();
+    let messages = body.messages.unwrap();
+    assert_eq!(messages.len(), 2);
+    assert_ne!(messages[0].message_id, messages[1].message_id);
+    assert!(messages
+        .iter()
+        .all(|message| message.message_id != Some(format!("{}-0", bulk_id))));
+}
+
+#[tokio::test]
+async fn test_idempotent_whatsapp_send_is_not_resent() {
+    let transport = Arc::new(FlakyTransport {
+        failures: 1,
+        ..Default::default()
+    });
+    let client = WhatsAppClient::with_transport(idempotent_configuration(3), transport.clone());
+    let request_body =
+        SendTextRequestBody::new("44444444444", "55555555555", TextContent::new(DUMMY_TEXT));
+
+    let error = client.send_text(request_body).await.unwrap_err();
+
+    assert_eq!(error.status(), Some(StatusCode::GATEWAY_TIMEOUT));
+    let sends = transport.sends.lock().unwrap();
+    assert_eq!(sends.len(), 1);
+    assert_eq!(sends[0]["messageId"].as_str().unwrap().len(), 32);
+    assert!(transport.lookups.lock().unwrap().is_empty());
+}
+
+fn idempotent_fake_client(server: &FakeServer) -> InfobipClient {
+    let mut configuration = server.configuration();
+    configuration.set_idempotent_sends(true);
+    configuration.set_retry_policy(RetryPolicy {
+        base_delay: Duration::from_millis(1),
+        jitter: false,
+        ..Default::default()
+    });
+
+    InfobipClient::with_transport(configuration, Arc::new(server.clone()))
+}
+
+#[tokio::test]
+async fn test_idempotent_send_looked_up_after_disconnect() {
+    let server = FakeServer::new();
+    server.inject_fault(PATH_SEND, Fault::Disconnect, 1);
+    let client = idempotent_fake_client(&server).sms();
+
+    client.send(send_request_body()).await.unwrap();
+
+    // The dropped request is looked up before it is sent again.
+    let requests = server.requests();
+    let paths: Vec<&str> = requests.iter().map(|(_, path)| path.as_str()).collect();
+    assert_eq!(paths, [PATH_SEND, PATH_GET_LOGS, PATH_SEND]);
+}
+
+#[tokio::test]
+async fn test_idempotent_whatsapp_send_not_resent_after_disconnect() {
+    let server = FakeServer::new();
+    server.inject_fault(PATH_SEND_TEXT, Fault::Disconnect, 1);
+    let client = idempotent_fake_client(&server).whatsapp();
+    let request_body =
+        SendTextRequestBody::new("44444444444", "55555555555", TextContent::new(DUMMY_TEXT));
+
+    let error = client.send_text(request_body).await.unwrap_err();
+
+    assert!(matches!(error, SdkError::Transport { .. }));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_idempotent_send_retries_rate_limited_attempts_once_each() {
+    let server = FakeServer::new();
+    server.inject_fault(PATH_SEND, Fault::RateLimited(Duration::from_millis(1)), 5);
+    let client = idempotent_fake_client(&server).sms();
+
+    let error = client.send(send_request_body()).await.unwrap_err();
+
+    // Each attempt of the retry policy sends the request once, without looking it up.
+    assert!(error.is_rate_limited());
+    assert_eq!(
+        server.requests().len(),
+        RetryPolicy::default().max_attempts as usize
+    );
+}
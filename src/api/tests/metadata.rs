@@ -0,0 +1,140 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use httpmock::prelude::*;
+use reqwest::{Request, StatusCode};
+
+use crate::api::sms::{SmsClient, PATH_PREVIEW};
+use crate::api::tests::{test_configuration, DUMMY_TEXT};
+use crate::api::transport::{Transport, TransportFuture};
+use crate::configuration::RetryPolicy;
+use crate::model::sms::PreviewRequestBody;
+
+const ERROR_RESPONSE: &str = r#"
+    {
+      "requestError": {
+        "serviceException": {
+          "messageId": "SERVICE_UNAVAILABLE",
+          "text": "Service unavailable"
+        }
+      }
+    }
+"#;
+
+fn test_retry_policy() -> RetryPolicy {
+    RetryPolicy {
+        base_delay: Duration::from_millis(1),
+        jitter: false,
+        ..Default::default()
+    }
+}
+
+// Answers the first request with 503 and every following one with 200.
+#[derive(Debug, Default)]
+struct RecoveringTransport {
+    requests: AtomicU32,
+}
+
+impl Transport for RecoveringTransport {
+    fn execute(&self, _request: Request) -> TransportFuture<'_> {
+        let status = match self.requests.fetch_add(1, Ordering::SeqCst) {
+            0 => StatusCode::SERVICE_UNAVAILABLE,
+            _ => StatusCode::OK,
+        };
+
+        Box::pin(async move {
+            let response = http::Response::builder()
+                .status(status.as_u16())
+                .header("x-request-id", "some-request-id")
+                .body("{}")
+                .unwrap();
+
+            Ok(reqwest::Response::from(response))
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_response_metadata() {
+    let server = MockServer::start_async().await;
+    server.mock(|when, then| {
+        when.method(POST).path(PATH_PREVIEW);
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .header("x-request-id", "some-request-id")
+            .body("{}");
+    });
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let response = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap();
+
+    let metadata = &response.metadata;
+    assert_eq!(
+        metadata.headers.get("x-request-id").unwrap(),
+        "some-request-id"
+    );
+    assert_eq!(metadata.url.path(), PATH_PREVIEW);
+    assert_eq!(metadata.attempts, 1);
+    assert!(metadata.elapsed > Duration::ZERO);
+}
+
+#[tokio::test]
+async fn test_response_metadata_counts_retries() {
+    let mut configuration = test_configuration("https://some.url");
+    configuration.set_retry_policy(test_retry_policy());
+    let client = SmsClient::with_transport(configuration, Arc::new(RecoveringTransport::default()));
+
+    let response = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.metadata.attempts, 2);
+    assert!(response.metadata.elapsed >= Duration::from_millis(1));
+}
+
+#[tokio::test]
+async fn test_error_metadata() {
+    let server = MockServer::start_async().await;
+    server.mock(|when, then| {
+        when.method(POST).path(PATH_PREVIEW);
+        then.status(StatusCode::SERVICE_UNAVAILABLE.as_u16())
+            .header("content-type", "application/json")
+            .header("x-request-id", "some-request-id")
+            .body(ERROR_RESPONSE);
+    });
+    let mut configuration = test_configuration(&server.base_url());
+    configuration.set_retry_policy(test_retry_policy());
+    let client = SmsClient::with_configuration(configuration);
+
+    let error = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap_err();
+
+    let metadata = error.metadata().unwrap();
+    assert_eq!(
+        metadata.headers.get("x-request-id").unwrap(),
+        "some-request-id"
+    );
+    assert_eq!(metadata.url.path(), PATH_PREVIEW);
+    assert_eq!(metadata.attempts, 3);
+    assert_eq!(error.api_error().unwrap().metadata.attempts, 3);
+}
+
+#[tokio::test]
+async fn test_no_metadata_without_response() {
+    let client = SmsClient::with_configuration(test_configuration("http://127.0.0.1:9"));
+
+    let error = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap_err();
+
+    assert!(error.metadata().is_none());
+}
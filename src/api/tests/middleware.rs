@@ -0,0 +1,139 @@
+use std::sync::{Arc, Mutex};
+
+use httpmock::prelude::*;
+use reqwest::header::HeaderValue;
+use reqwest::{Request, StatusCode};
+
+use crate::api::{
+    middleware::{Middleware, Next},
+    sms::{SmsClient, PATH_PREVIEW},
+    tests::{test_configuration, DUMMY_TEXT},
+    transport::TransportFuture,
+};
+use crate::model::sms::PreviewRequestBody;
+
+#[cfg(tokio)]
+use crate::api::sms::BlockingSmsClient;
+
+const DUMMY_BASE_URL: &str = "https://some.url";
+
+// Adds a header to the request and records when it runs.
+#[derive(Debug)]
+struct HeaderMiddleware {
+    name: &'static str,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+impl Middleware for HeaderMiddleware {
+    fn handle<'a>(&'a self, mut request: Request, next: Next<'a>) -> TransportFuture<'a> {
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("before {}", self.name));
+        request
+            .headers_mut()
+            .insert(self.name, HeaderValue::from_static("yes"));
+
+        Box::pin(async move {
+            let response = next.run(request).await;
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("after {}", self.name));
+
+            response
+        })
+    }
+}
+
+// Answers every request without sending it.
+#[derive(Debug)]
+struct ShortCircuitMiddleware;
+
+impl Middleware for ShortCircuitMiddleware {
+    fn handle<'a>(&'a self, _request: Request, _next: Next<'a>) -> TransportFuture<'a> {
+        Box::pin(async {
+            let response = http::Response::builder()
+                .status(StatusCode::OK.as_u16())
+                .body(r#"{"originalText": "cached"}"#)
+                .unwrap();
+
+            Ok(reqwest::Response::from(response))
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_middleware_runs_in_order() {
+    let server = MockServer::start_async().await;
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path(PATH_PREVIEW)
+            .header("x-first", "yes")
+            .header("x-second", "yes");
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body("{}");
+    });
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let mut configuration = test_configuration(&server.base_url());
+    configuration.add_middleware(Arc::new(HeaderMiddleware {
+        name: "x-first",
+        log: log.clone(),
+    }));
+    configuration.add_middleware(Arc::new(HeaderMiddleware {
+        name: "x-second",
+        log: log.clone(),
+    }));
+    let client = SmsClient::with_configuration(configuration);
+
+    let response = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(mock.hits(), 1);
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            "before x-first",
+            "before x-second",
+            "after x-second",
+            "after x-first"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_middleware_short_circuit() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let mut configuration = test_configuration(DUMMY_BASE_URL);
+    configuration.add_middleware(Arc::new(ShortCircuitMiddleware));
+    configuration.add_middleware(Arc::new(HeaderMiddleware {
+        name: "x-never",
+        log: log.clone(),
+    }));
+    let client = SmsClient::with_configuration(configuration);
+
+    let response = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap();
+
+    assert_eq!(response.body.original_text.unwrap(), "cached");
+    assert!(log.lock().unwrap().is_empty());
+}
+
+#[cfg(tokio)]
+#[test]
+fn test_blocking_middleware_short_circuit() {
+    let mut configuration = test_configuration(DUMMY_BASE_URL);
+    configuration.add_middleware(Arc::new(ShortCircuitMiddleware));
+    let client = BlockingSmsClient::with_configuration(configuration);
+
+    let response = client.preview(PreviewRequestBody::new(DUMMY_TEXT)).unwrap();
+
+    assert_eq!(response.body.original_text.unwrap(), "cached");
+}
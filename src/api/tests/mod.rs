@@ -13,6 +13,60 @@ mod whatsapp;
 #[cfg(test)]
 mod email;
 
+#[cfg(all(test, feature = "sms"))]
+mod auth;
+
+#[cfg(all(test, feature = "sms", feature = "email", feature = "whatsapp"))]
+mod client;
+
+#[cfg(all(test, feature = "sms"))]
+mod configuration;
+
+#[cfg(all(test, feature = "sms"))]
+mod correlation;
+
+#[cfg(all(test, feature = "sms"))]
+mod endpoint;
+
+#[cfg(all(test, feature = "sms"))]
+mod errors;
+
+#[cfg(all(test, feature = "sms", feature = "email", feature = "whatsapp"))]
+mod idempotency;
+
+#[cfg(all(test, feature = "sms"))]
+mod metadata;
+
+#[cfg(all(test, feature = "sms"))]
+mod middleware;
+
+#[cfg(all(test, feature = "sms", feature = "email", feature = "whatsapp"))]
+mod pages;
+
+#[cfg(all(test, feature = "sms"))]
+mod path;
+
+#[cfg(all(test, feature = "sms"))]
+mod query;
+
+#[cfg(all(test, feature = "sms"))]
+mod rate_limit;
+
+#[cfg(all(test, feature = "sms"))]
+mod raw;
+
+#[cfg(all(test, feature = "sms"))]
+mod retry;
+
+#[cfg(all(test, feature = "sms", feature = "email", feature = "whatsapp"))]
+mod testing;
+
+#[cfg(all(test, feature = "sms", feature = "tracing"))]
+mod tracing;
+
+#[cfg(all(test, feature = "sms"))]
+mod transport;
+
 const DUMMY_TEXT: &str = "Some text for tests.";
 
 async fn mock_json_endpoint(
@@ -34,6 +88,7 @@ async fn mock_json_endpoint(
     server
 }
 
+#[cfg(tokio)]
 fn mock_blocking_json_endpoint(
     endpoint_method: httpmock::Method,
     endpoint_path: &str,
@@ -57,7 +112,7 @@ fn test_configuration(server_url: &str) -> Configuration {
     Configuration::with_api_key(
         server_url.to_string(),
         ApiKey {
-            key: "some-api-key".to_string(),
+            key: "some-api-key".into(),
             prefix: None,
         },
     )
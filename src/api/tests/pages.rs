@@ -0,0 +1,284 @@
+#[cfg(tokio)]
+use std::sync::Arc;
+
+use futures_util::{pin_mut, StreamExt};
+use httpmock::prelude::*;
+use reqwest::StatusCode;
+
+use crate::api::pages::just_before;
+#[cfg(tokio)]
+use crate::api::sms::BlockingSmsClient;
+use crate::api::sms::{SmsClient, PATH_GET_LOGS};
+use crate::api::testing::FakeServer;
+use crate::api::tests::{test_configuration, DUMMY_TEXT};
+use crate::model::email::DomainsQueryParameters;
+use crate::model::sms::{
+    DeliveryReportsQueryParameters, Destination, LogsQueryParameters, Message, SendRequestBody,
+};
+
+const NEWEST: &str = "2024-03-07T10:00:02.000+0000";
+const MIDDLE: &str = "2024-03-07T10:00:01.000+0000";
+const OLDEST: &str = "2024-03-07T10:00:00.000+0000";
+
+fn logs_body(logs: &[(&str, &str)]) -> String {
+    let results: Vec<_> = logs
+        .iter()
+        .map(
+            |(message_id, sent_at)| serde_json::json!({"messageId": message_id, "sentAt": sent_at}),
+        )
+        .collect();
+
+    serde_json::json!({ "results": results }).to_string()
+}
+
+fn without_sent_until(request: &HttpMockRequest) -> bool {
+    request.query_params.as_ref().map_or(true, |params| {
+        params.iter().all(|(name, _)| name != "sentUntil")
+    })
+}
+
+fn without_sent_since(request: &HttpMockRequest) -> bool {
+    request.query_params.as_ref().map_or(true, |params| {
+        params.iter().all(|(name, _)| name != "sentSince")
+    })
+}
+
+fn send_request_body(count: usize) -> SendRequestBody {
+    let messages = (0..count)
+        .map(|index| {
+            let mut message = Message::new(vec![Destination::new(&format!("4179302672{}", index))]);
+            message.text = Some(DUMMY_TEXT.to_string());
+            message
+        })
+        .collect();
+
+    SendRequestBody::new(messages)
+}
+
+fn logs_query_parameters() -> LogsQueryParameters {
+    let mut query_parameters = LogsQueryParameters::new();
+    query_parameters.limit = Some(2);
+    query_parameters
+}
+
+#[tokio::test]
+async fn test_logs_stream_walks_back_in_time() {
+    let server = MockServer::start_async().await;
+    let first_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path(PATH_GET_LOGS)
+            .query_param("limit", "2")
+            .matches(without_sent_until);
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(logs_body(&[("third", NEWEST), ("second", MIDDLE)]));
+    });
+    let second_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path(PATH_GET_LOGS)
+            .query_param("sentUntil", MIDDLE);
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(logs_body(&[("second", MIDDLE), ("first", OLDEST)]));
+    });
+    let third_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path(PATH_GET_LOGS)
+            .query_param("sentUntil", OLDEST);
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(logs_body(&[("first", OLDEST)]));
+    });
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let logs: Vec<_> = client
+        .logs_stream(logs_query_parameters(), 100)
+        .map(|log| log.unwrap().message_id.unwrap())
+        .collect()
+        .await;
+
+    assert_eq!(logs, vec!["third", "second", "first"]);
+    first_mock.assert_async().await;
+    second_mock.assert_async().await;
+    third_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_logs_stream_lists_crowded_millisecond() {
+    let server = MockServer::start_async().await;
+    // More logs than the limit share the newest timestamp, so the first page can not hold them.
+    let first_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path(PATH_GET_LOGS)
+            .query_param("limit", "2")
+            .matches(without_sent_until);
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(logs_body(&[("sixth", NEWEST), ("fifth", NEWEST)]));
+    });
+    let second_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path(PATH_GET_LOGS)
+            .query_param("sentSince", "2024-03-07T10:00:01.999+0000")
+            .query_param("sentUntil", NEWEST)
+            .query_param("limit", "1000");
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(logs_body(&[
+                ("sixth", NEWEST),
+                ("fifth", NEWEST),
+                ("fourth", NEWEST),
+            ]));
+    });
+    let third_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path(PATH_GET_LOGS)
+            .query_param("sentUntil", "2024-03-07T10:00:01.999+0000")
+            .query_param("limit", "2")
+            .matches(without_sent_since);
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(logs_body(&[("second", MIDDLE), ("first", OLDEST)]));
+    });
+    let fourth_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path(PATH_GET_LOGS)
+            .query_param("sentUntil", OLDEST)
+            .query_param("limit", "2")
+            .matches(without_sent_since);
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(logs_body(&[("first", OLDEST)]));
+    });
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let logs: Vec<_> = client
+        .logs_stream(logs_query_parameters(), 100)
+        .map(|log| log.unwrap().message_id.unwrap())
+        .collect()
+        .await;
+
+    assert_eq!(logs, vec!["sixth", "fifth", "fourth", "second", "first"]);
+    first_mock.assert_async().await;
+    second_mock.assert_async().await;
+    third_mock.assert_async().await;
+    fourth_mock.assert_async().await;
+}
+
+#[test]
+fn test_just_before() {
+    assert_eq!(
+        just_before("2024-03-07T10:00:02.500+0000").unwrap(),
+        "2024-03-07T10:00:02.499+0000"
+    );
+    assert_eq!(
+        just_before("2024-03-01T00:00:00.000+0000").unwrap(),
+        "2024-02-29T23:59:59.999+0000"
+    );
+    assert_eq!(
+        just_before("2025-01-01T00:00:00.000+0000").unwrap(),
+        "2024-12-31T23:59:59.999+0000"
+    );
+    assert!(just_before("yesterday").is_none());
+}
+
+#[tokio::test]
+async fn test_logs_stream_stops_at_max_results() {
+    let server = MockServer::start_async().await;
+    let logs_mock = server.mock(|when, then| {
+        when.method(GET).path(PATH_GET_LOGS);
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(logs_body(&[("third", NEWEST), ("second", MIDDLE)]));
+    });
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let logs: Vec<_> = client
+        .logs_stream(logs_query_parameters(), 1)
+        .collect()
+        .await;
+
+    assert_eq!(logs.len(), 1);
+    logs_mock.assert_hits_async(1).await;
+}
+
+#[tokio::test]
+async fn test_stream_ends_after_error() {
+    let server = MockServer::start_async().await;
+    let logs_mock = server.mock(|when, then| {
+        when.method(GET).path(PATH_GET_LOGS);
+        then.status(StatusCode::BAD_REQUEST.as_u16())
+            .header("content-type", "application/json")
+            .body(r#"{"requestError": {"serviceException": {"messageId": "BAD_REQUEST"}}}"#);
+    });
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let logs = client.logs_stream(logs_query_parameters(), 100);
+    pin_mut!(logs);
+
+    assert!(logs.next().await.unwrap().is_err());
+    assert!(logs.next().await.is_none());
+    logs_mock.assert_hits_async(1).await;
+}
+
+#[tokio::test]
+async fn test_delivery_reports_stream_drains_reports() {
+    let client = FakeServer::new().client().sms();
+    client.send(send_request_body(5)).await.unwrap();
+
+    let mut query_parameters = DeliveryReportsQueryParameters::new();
+    query_parameters.limit = Some(2);
+    let reports: Vec<_> = client
+        .delivery_reports_stream(query_parameters, 100)
+        .map(|report| report.unwrap().to.unwrap())
+        .collect()
+        .await;
+
+    assert_eq!(
+        reports,
+        vec![
+            "41793026720",
+            "41793026721",
+            "41793026722",
+            "41793026723",
+            "41793026724"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_domains_stream_requests_every_page() {
+    let server = FakeServer::new();
+    for domain_name in ["example.com", "example.org", "example.net"] {
+        server.add_verified_domain(domain_name);
+    }
+    let client = server.client().email();
+
+    let mut query_parameters = DomainsQueryParameters::new();
+    query_parameters.size = Some(2);
+    let domains: Vec<_> = client
+        .domains_stream(query_parameters, 100)
+        .map(|domain| domain.unwrap().domain_name.unwrap())
+        .collect()
+        .await;
+
+    assert_eq!(domains, vec!["example.com", "example.org", "example.net"]);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[cfg(tokio)]
+#[test]
+fn test_blocking_delivery_reports_iter() {
+    let server = FakeServer::new();
+    let client = BlockingSmsClient::with_transport(server.configuration(), Arc::new(server));
+    client.send(send_request_body(3)).unwrap();
+
+    let mut query_parameters = DeliveryReportsQueryParameters::new();
+    query_parameters.limit = Some(1);
+    let reports: Vec<_> = client
+        .delivery_reports_iter(query_parameters, 2)
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert_eq!(reports.len(), 2);
+}
@@ -0,0 +1,202 @@
+use httpmock::prelude::*;
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use crate::api::path::{decode_segment, encode_segment, PathError, PathTemplate};
+use crate::api::raw::RawRequest;
+use crate::api::sms::{SmsClient, PATH_GET_TFA_APPLICATION, PATH_VERIFY_PHONE_NUMBER};
+use crate::api::tests::test_configuration;
+use crate::api::SdkError;
+use crate::model::sms::VerifyPhoneNumberRequestBody;
+
+#[cfg(feature = "whatsapp")]
+use crate::api::whatsapp::WhatsAppClient;
+
+#[test]
+fn test_render_encodes_values() {
+    let template = PathTemplate::parse(PATH_GET_TFA_APPLICATION).unwrap();
+
+    let path = template.render(&[("appId", "a/b?c#d e%")]).unwrap();
+
+    assert_eq!(path, "/2fa/2/applications/a%2Fb%3Fc%23d%20e%25");
+}
+
+#[test]
+fn test_render_keeps_unreserved_characters() {
+    let template = PathTemplate::parse("/custom/1/{first}/{second}").unwrap();
+
+    let path = template
+        .render(&[("second", "some.value~2"), ("first", "Some-ID_1")])
+        .unwrap();
+
+    assert_eq!(path, "/custom/1/Some-ID_1/some.value~2");
+}
+
+#[test]
+fn test_render_encodes_non_ascii_values() {
+    let template = PathTemplate::parse("/custom/1/{name}").unwrap();
+
+    let path = template.render(&[("name", "čaj")]).unwrap();
+
+    assert_eq!(path, "/custom/1/%C4%8Daj");
+    assert_eq!(decode_segment("%C4%8Daj"), "čaj");
+}
+
+#[test]
+fn test_render_missing_parameter() {
+    let template = PathTemplate::parse(PATH_VERIFY_PHONE_NUMBER).unwrap();
+
+    let error = template.render(&[]).unwrap_err();
+
+    assert_eq!(error, PathError::MissingParameter("pinId".to_string()));
+}
+
+#[test]
+fn test_render_unknown_parameter() {
+    let template = PathTemplate::parse(PATH_VERIFY_PHONE_NUMBER).unwrap();
+
+    let error = template
+        .render(&[("pinId", "some-pin"), ("pin_id", "some-pin")])
+        .unwrap_err();
+
+    assert_eq!(error, PathError::UnknownParameter("pin_id".to_string()));
+}
+
+#[test]
+fn test_render_invalid_values() {
+    let template = PathTemplate::parse(PATH_VERIFY_PHONE_NUMBER).unwrap();
+
+    for value in ["", ".", ".."] {
+        let error = template.render(&[("pinId", value)]).unwrap_err();
+
+        assert_eq!(error, PathError::InvalidValue("pinId".to_string()));
+    }
+}
+
+#[test]
+fn test_parse_malformed_templates() {
+    for template in ["/custom/{id", "/custom/id}", "/custom/{}", "/custom/{a{b}"] {
+        let error = PathTemplate::parse(template).unwrap_err();
+
+        assert_eq!(error, PathError::MalformedTemplate(template.to_string()));
+    }
+}
+
+#[test]
+fn test_parse_placeholders() {
+    let template =
+        PathTemplate::parse("/whatsapp/2/senders/{sender}/templates/{templateName}").unwrap();
+
+    assert_eq!(template.placeholders(), vec!["sender", "templateName"]);
+    assert_eq!(
+        template.as_str(),
+        "/whatsapp/2/senders/{sender}/templates/{templateName}"
+    );
+    assert_eq!(
+        PathTemplate::parse("/account/1/balance")
+            .unwrap()
+            .placeholders(),
+        Vec::<&str>::new()
+    );
+}
+
+#[test]
+fn test_decode_segment_reverses_encode() {
+    let value = "sale/50% off?#&=+ 🙂";
+
+    assert_eq!(decode_segment(&encode_segment(value)), value);
+    assert_eq!(decode_segment("100%"), "100%");
+    assert_eq!(decode_segment("%zz%2"), "%zz%2");
+}
+
+#[tokio::test]
+async fn test_client_encodes_path_parameters() {
+    let server = MockServer::start_async().await;
+    let application_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/2fa/2/applications/some%2Fapp%3Fid%23");
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(r#"{"applicationId": "some/app?id#", "name": "Some app"}"#);
+    });
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let response = client.tfa_application("some/app?id#").await.unwrap();
+
+    application_mock.assert_async().await;
+    assert_eq!(response.body.application_id.unwrap(), "some/app?id#");
+}
+
+#[tokio::test]
+async fn test_client_rejects_invalid_path_parameters_before_sending() {
+    let server = MockServer::start_async().await;
+    let verify_mock = server.mock(|when, then| {
+        when.any_request();
+        then.status(StatusCode::OK.as_u16()).body("{}");
+    });
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let error = client
+        .verify_phone_number("..", VerifyPhoneNumberRequestBody::new("1234"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        SdkError::InvalidPath(PathError::InvalidValue(ref name)) if name == "pinId"
+    ));
+    verify_mock.assert_hits_async(0).await;
+}
+
+#[cfg(feature = "whatsapp")]
+#[tokio::test]
+async fn test_whatsapp_template_name_is_encoded() {
+    let server = MockServer::start_async().await;
+    let delete_mock = server.mock(|when, then| {
+        when.method(DELETE)
+            .path("/whatsapp/2/senders/447860099299/templates/sale%2F50%25%20off");
+        then.status(StatusCode::NO_CONTENT.as_u16());
+    });
+    let client = WhatsAppClient::with_configuration(test_configuration(&server.base_url()));
+
+    let status = client
+        .delete_template("447860099299", "sale/50% off")
+        .await
+        .unwrap();
+
+    delete_mock.assert_async().await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn test_raw_path_parameters() {
+    let server = MockServer::start_async().await;
+    let sender_mock = server.mock(|when, then| {
+        when.method(GET).path("/custom/1/senders/some%20sender%2F1");
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body("{}");
+    });
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let request =
+        RawRequest::get("/custom/1/senders/{sender}").path_param("sender", "some sender/1");
+    client.raw::<Value>(request).await.unwrap();
+
+    sender_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_raw_missing_path_parameter() {
+    let client = SmsClient::with_configuration(test_configuration("http://localhost"));
+
+    let error = client
+        .raw::<Value>(RawRequest::get("/custom/1/senders/{sender}"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        SdkError::InvalidPath(PathError::MissingParameter(ref name)) if name == "sender"
+    ));
+}
@@ -0,0 +1,241 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use httpmock::prelude::*;
+use reqwest::{Method, StatusCode};
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+
+use crate::api::endpoint::{Endpoint, NoBody};
+use crate::api::query::to_pairs;
+use crate::api::sms::{SmsClient, PATH_GET_LOGS};
+use crate::api::tests::test_configuration;
+use crate::api::SdkError;
+use crate::model::sms::{
+    LogsQueryParameters, ScheduledQueryParameters, SendOverQueryParametersQueryParameters,
+    TfaVerificationStatusQueryParameters,
+};
+
+// Date serialized as a string, like the date types of most crates.
+struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl Serialize for Date {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum Status {
+    Delivered,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TypedQuery {
+    sent_since: Date,
+    limit: u32,
+    price: f64,
+    verified: bool,
+    general_status: Status,
+    skipped: Option<String>,
+    message_id: Vec<&'static str>,
+}
+
+#[derive(Serialize)]
+struct NestedQuery {
+    inner: TfaVerificationStatusQueryParameters,
+}
+
+struct GetNested {
+    query: NestedQuery,
+}
+
+impl Endpoint for GetNested {
+    type Query = NestedQuery;
+    type Body = NoBody;
+    type Response = Value;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path_template(&self) -> &str {
+        "/custom/1/nested"
+    }
+
+    fn query(&self) -> Option<&Self::Query> {
+        Some(&self.query)
+    }
+}
+
+fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+    expected
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_typed_values_in_declaration_order() {
+    let query = TypedQuery {
+        sent_since: Date {
+            year: 2024,
+            month: 3,
+            day: 7,
+        },
+        limit: 10,
+        price: 0.5,
+        verified: true,
+        general_status: Status::Delivered,
+        skipped: None,
+        message_id: vec!["first", "second"],
+    };
+
+    assert_eq!(
+        to_pairs(&query).unwrap(),
+        pairs(&[
+            ("sentSince", "2024-03-07"),
+            ("limit", "10"),
+            ("price", "0.5"),
+            ("verified", "true"),
+            ("generalStatus", "DELIVERED"),
+            ("messageId", "first"),
+            ("messageId", "second"),
+        ])
+    );
+}
+
+#[test]
+fn test_logs_query_parameters() {
+    let mut query_parameters = LogsQueryParameters::new();
+    query_parameters.message_id = Some(vec!["first".to_string(), "second".to_string()]);
+    query_parameters.mcc = Some(vec!["219".to_string(), "220".to_string()]);
+    query_parameters.sent_since = Some("2024-03-07T10:00:00.000+0000".to_string());
+    query_parameters.limit = Some(100);
+
+    assert_eq!(
+        to_pairs(&query_parameters).unwrap(),
+        pairs(&[
+            ("messageId", "first"),
+            ("messageId", "second"),
+            ("sentSince", "2024-03-07T10:00:00.000+0000"),
+            ("limit", "100"),
+            ("mcc", "219"),
+            ("mcc", "220"),
+        ])
+    );
+}
+
+#[test]
+fn test_renamed_query_parameters() {
+    assert_eq!(
+        to_pairs(&ScheduledQueryParameters::new("some-bulk")).unwrap(),
+        pairs(&[("bulkId", "some-bulk")])
+    );
+
+    let mut query_parameters = SendOverQueryParametersQueryParameters::new(
+        "some-user",
+        "some-password",
+        vec!["41793026727".to_string(), "41793026728".to_string()],
+    );
+    query_parameters.intermediate_report = Some(false);
+    query_parameters.validity_period = Some(60);
+
+    assert_eq!(
+        to_pairs(&query_parameters).unwrap(),
+        pairs(&[
+            ("username", "some-user"),
+            ("password", "some-password"),
+            ("to", "41793026727,41793026728"),
+            ("intermediateReport", "false"),
+            ("validityPeriod", "60"),
+        ])
+    );
+}
+
+#[test]
+fn test_maps_pairs_and_unit() {
+    let map = BTreeMap::from([("b", vec![2, 3]), ("a", vec![1])]);
+    assert_eq!(
+        to_pairs(&map).unwrap(),
+        pairs(&[("a", "1"), ("b", "2"), ("b", "3")])
+    );
+
+    let raw = vec![
+        ("messageId".to_string(), "first".to_string()),
+        ("messageId".to_string(), "second".to_string()),
+    ];
+    assert_eq!(to_pairs(&raw).unwrap(), raw);
+
+    assert!(to_pairs(&()).unwrap().is_empty());
+    assert!(to_pairs(&None::<LogsQueryParameters>).unwrap().is_empty());
+}
+
+#[test]
+fn test_unsupported_values() {
+    let nested = NestedQuery {
+        inner: TfaVerificationStatusQueryParameters::new("41793026727"),
+    };
+
+    assert!(to_pairs(&nested).is_err());
+    assert!(to_pairs("messageId").is_err());
+    assert!(to_pairs(&vec!["first", "second"]).is_err());
+}
+
+#[tokio::test]
+async fn test_client_sends_repeated_query_parameters() {
+    let server = MockServer::start_async().await;
+    let logs_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path(PATH_GET_LOGS)
+            .query_param("messageId", "first")
+            .query_param("messageId", "second")
+            .query_param("mcc", "219")
+            .query_param("mcc", "220")
+            .query_param("limit", "10");
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(r#"{"results": []}"#);
+    });
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+    let mut query_parameters = LogsQueryParameters::new();
+    query_parameters.message_id = Some(vec!["first".to_string(), "second".to_string()]);
+    query_parameters.mcc = Some(vec!["219".to_string(), "220".to_string()]);
+    query_parameters.limit = Some(10);
+
+    client.logs(query_parameters).await.unwrap();
+
+    logs_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_unsupported_query_is_not_sent() {
+    let server = MockServer::start_async().await;
+    let any_mock = server.mock(|when, then| {
+        when.any_request();
+        then.status(StatusCode::OK.as_u16()).body("{}");
+    });
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+    let endpoint = GetNested {
+        query: NestedQuery {
+            inner: TfaVerificationStatusQueryParameters::new("41793026727"),
+        },
+    };
+
+    let error = client.execute(endpoint).await.unwrap_err();
+
+    assert!(matches!(error, SdkError::Serde(_)));
+    any_mock.assert_hits_async(0).await;
+}
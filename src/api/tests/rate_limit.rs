@@ -0,0 +1,47 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use httpmock::prelude::*;
+use reqwest::StatusCode;
+
+use crate::api::{
+    sms::{SmsClient, PATH_PREVIEW},
+    tests::{test_configuration, DUMMY_TEXT},
+};
+use crate::configuration::{RateLimit, RateLimiter};
+use crate::model::sms::PreviewRequestBody;
+
+#[tokio::test]
+async fn test_rate_limiter_shared_between_clients() {
+    let server = MockServer::start_async().await;
+    let mock = server.mock(|when, then| {
+        when.method(POST).path(PATH_PREVIEW);
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body("{}");
+    });
+
+    let mut configuration = test_configuration(&server.base_url());
+    configuration.set_rate_limiter(Arc::new(
+        RateLimiter::new().with_endpoint_limit(PATH_PREVIEW, RateLimit::per_second(20)),
+    ));
+    let first_client = SmsClient::with_configuration(configuration.clone());
+    let second_client = SmsClient::with_configuration(configuration);
+
+    let start = Instant::now();
+    for _ in 0..20 {
+        first_client
+            .preview(PreviewRequestBody::new(DUMMY_TEXT))
+            .await
+            .unwrap();
+    }
+    for _ in 0..2 {
+        second_client
+            .preview(PreviewRequestBody::new(DUMMY_TEXT))
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(mock.hits(), 22);
+    assert!(start.elapsed() >= Duration::from_millis(100));
+}
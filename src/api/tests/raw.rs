@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use httpmock::prelude::*;
+use reqwest::StatusCode;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::api::client::InfobipClient;
+use crate::api::raw::RawRequest;
+use crate::api::sms::SmsClient;
+use crate::api::tests::test_configuration;
+use crate::api::{user_agent, SdkError};
+use crate::configuration::RetryPolicy;
+
+#[cfg(tokio)]
+use crate::api::sms::BlockingSmsClient;
+
+#[derive(Debug, Serialize)]
+struct SenderBody {
+    name: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Sender {
+    id: String,
+    name: String,
+}
+
+#[tokio::test]
+async fn test_raw_get_with_repeated_query_parameters() {
+    let server = MockServer::start_async().await;
+    let logs_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/custom/1/logs")
+            .query_param("messageId", "first-message-id")
+            .query_param("messageId", "second-message-id")
+            .query_param("limit", "10")
+            .header("authorization", "App some-api-key")
+            .header("user-agent", user_agent());
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(r#"{"results": [{"messageId": "first-message-id"}]}"#);
+    });
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let request = RawRequest::get("/custom/1/logs")
+        .query("messageId", "first-message-id")
+        .query("messageId", "second-message-id")
+        .query("limit", 10);
+    let response = client.raw::<Value>(request).await.unwrap();
+
+    logs_mock.assert_async().await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["results"][0]["messageId"], "first-message-id");
+}
+
+#[tokio::test]
+async fn test_raw_json_body_with_typed_response() {
+    let server = MockServer::start_async().await;
+    let sender_mock = server.mock(|when, then| {
+        when.method(PUT)
+            .path("/custom/1/senders/some-sender")
+            .header("content-type", "application/json")
+            .json_body(json!({"name": "Some name"}));
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(r#"{"id": "some-sender", "name": "Some name"}"#);
+    });
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let request = RawRequest::put("/custom/1/senders/some-sender")
+        .json(&SenderBody {
+            name: "Some name".to_string(),
+        })
+        .unwrap();
+    let response = client.raw::<Sender>(request).await.unwrap();
+
+    sender_mock.assert_async().await;
+    assert_eq!(
+        response.body,
+        Sender {
+            id: "some-sender".to_string(),
+            name: "Some name".to_string(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_raw_multipart_body_is_rebuilt_for_retries() {
+    let server = MockServer::start_async().await;
+    let upload_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/custom/1/uploads")
+            .header_exists("content-type")
+            .body_contains(r#"name="description""#)
+            .body_contains("Some description")
+            .body_contains(r#"name="file"; filename="notes.txt""#)
+            .body_contains("Some notes.");
+        then.status(StatusCode::SERVICE_UNAVAILABLE.as_u16())
+            .header("content-type", "application/json")
+            .body("{}");
+    });
+    let mut configuration = test_configuration(&server.base_url());
+    configuration.set_retry_policy(RetryPolicy {
+        base_delay: Duration::ZERO,
+        ..RetryPolicy::new(3)
+    });
+    let client = SmsClient::with_configuration(configuration);
+
+    let request = RawRequest::post("/custom/1/uploads")
+        .multipart_text("description", "Some description")
+        .multipart_file("file", "notes.txt", b"Some notes.".to_vec());
+    let error = client.raw::<Value>(request).await.unwrap_err();
+
+    upload_mock.assert_hits_async(3).await;
+    assert_eq!(error.status(), Some(StatusCode::SERVICE_UNAVAILABLE));
+}
+
+#[tokio::test]
+async fn test_raw_maps_api_errors() {
+    let server = MockServer::start_async().await;
+    server.mock(|when, then| {
+        when.method(DELETE).path("/custom/1/senders/missing");
+        then.status(StatusCode::NOT_FOUND.as_u16())
+            .header("content-type", "application/json")
+            .body(
+                r#"{"requestError": {"serviceException": {"messageId": "NOT_FOUND", "text": "Not found"}}}"#,
+            );
+    });
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let error = client
+        .raw::<Value>(RawRequest::delete("/custom/1/senders/missing"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, SdkError::NotFound(_)));
+}
+
+#[tokio::test]
+async fn test_raw_response_without_body() {
+    let server = MockServer::start_async().await;
+    server.mock(|when, then| {
+        when.method(DELETE).path("/custom/1/senders/some-sender");
+        then.status(StatusCode::NO_CONTENT.as_u16());
+    });
+    let client = InfobipClient::with_configuration(test_configuration(&server.base_url()));
+
+    let response = client
+        .raw::<Value>(RawRequest::delete("/custom/1/senders/some-sender"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, StatusCode::NO_CONTENT);
+    assert_eq!(response.body, Value::Null);
+}
+
+#[cfg(tokio)]
+#[test]
+fn test_blocking_raw() {
+    let server = MockServer::start();
+    let balance_mock = server.mock(|when, then| {
+        when.method(GET).path("/account/1/balance");
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(r#"{"balance": 10.5, "currency": "EUR"}"#);
+    });
+    let client = BlockingSmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let response = client
+        .raw::<Value>(RawRequest::get("/account/1/balance"))
+        .unwrap();
+
+    balance_mock.assert();
+    assert_eq!(response.body["currency"], "EUR");
+}
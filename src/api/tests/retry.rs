@@ -0,0 +1,229 @@
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use httpmock::prelude::*;
+use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+use reqwest::StatusCode;
+
+use crate::api::{
+    retry_after,
+    sms::{SmsClient, PATH_PREVIEW},
+    testing::{FakeServer, Fault},
+    tests::{test_configuration, DUMMY_TEXT},
+    SdkError,
+};
+use crate::configuration::RetryPolicy;
+use crate::model::sms::PreviewRequestBody;
+
+const ERROR_RESPONSE: &str = r#"
+    {
+      "requestError": {
+        "serviceException": {
+          "messageId": "TOO_MANY_REQUESTS",
+          "text": "Too many requests"
+        }
+      }
+    }
+"#;
+
+fn test_retry_policy() -> RetryPolicy {
+    RetryPolicy {
+        base_delay: Duration::from_millis(1),
+        jitter: false,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_retries_until_max_attempts() {
+    let server = MockServer::start_async().await;
+    let mock = server.mock(|when, then| {
+        when.method(POST).path(PATH_PREVIEW);
+        then.status(StatusCode::SERVICE_UNAVAILABLE.as_u16())
+            .header("content-type", "application/json")
+            .body(ERROR_RESPONSE);
+    });
+
+    let mut configuration = test_configuration(&server.base_url());
+    configuration.set_retry_policy(test_retry_policy());
+    let client = SmsClient::with_configuration(configuration);
+
+    let error = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap_err();
+
+    assert_eq!(mock.hits(), 3);
+    if let SdkError::ServerError(api_error) = error {
+        assert_eq!(api_error.status, StatusCode::SERVICE_UNAVAILABLE);
+    } else {
+        panic!("not API request error")
+    }
+}
+
+#[tokio::test]
+async fn test_retries_honor_retry_after() {
+    let server = MockServer::start_async().await;
+    let mock = server.mock(|when, then| {
+        when.method(POST).path(PATH_PREVIEW);
+        then.status(StatusCode::TOO_MANY_REQUESTS.as_u16())
+            .header("content-type", "application/json")
+            .header("retry-after", "0")
+            .body(ERROR_RESPONSE);
+    });
+
+    let mut configuration = test_configuration(&server.base_url());
+    configuration.set_retry_policy(RetryPolicy {
+        base_delay: Duration::from_secs(60),
+        ..test_retry_policy()
+    });
+    let client = SmsClient::with_configuration(configuration);
+
+    client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap_err();
+
+    assert_eq!(mock.hits(), 3);
+}
+
+#[tokio::test]
+async fn test_no_retries_when_retry_after_exceeds_max_delay() {
+    let server = MockServer::start_async().await;
+    let mock = server.mock(|when, then| {
+        when.method(POST).path(PATH_PREVIEW);
+        then.status(StatusCode::TOO_MANY_REQUESTS.as_u16())
+            .header("content-type", "application/json")
+            .header("retry-after", "60")
+            .body(ERROR_RESPONSE);
+    });
+
+    let mut configuration = test_configuration(&server.base_url());
+    configuration.set_retry_policy(test_retry_policy());
+    let client = SmsClient::with_configuration(configuration);
+
+    let error = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap_err();
+
+    assert_eq!(mock.hits(), 1);
+    assert_eq!(error.retry_after(), Some(Duration::from_secs(60)));
+}
+
+#[tokio::test]
+async fn test_no_retries_on_unlisted_status() {
+    let server = MockServer::start_async().await;
+    let mock = server.mock(|when, then| {
+        when.method(POST).path(PATH_PREVIEW);
+        then.status(StatusCode::BAD_REQUEST.as_u16())
+            .header("content-type", "application/json")
+            .body(ERROR_RESPONSE);
+    });
+
+    let mut configuration = test_configuration(&server.base_url());
+    configuration.set_retry_policy(test_retry_policy());
+    let client = SmsClient::with_configuration(configuration);
+
+    client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap_err();
+
+    assert_eq!(mock.hits(), 1);
+}
+
+#[tokio::test]
+async fn test_without_retries() {
+    let server = MockServer::start_async().await;
+    let mock = server.mock(|when, then| {
+        when.method(POST).path(PATH_PREVIEW);
+        then.status(StatusCode::SERVICE_UNAVAILABLE.as_u16())
+            .header("content-type", "application/json")
+            .body(ERROR_RESPONSE);
+    });
+
+    let mut configuration = test_configuration(&server.base_url());
+    configuration.set_retry_policy(test_retry_policy());
+    let client = SmsClient::with_configuration(configuration);
+
+    client
+        .without_retries()
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap_err();
+
+    assert_eq!(mock.hits(), 1);
+}
+
+#[test]
+fn test_retry_policy_delay() {
+    let policy = RetryPolicy {
+        base_delay: Duration::from_millis(100),
+        max_delay: Duration::from_millis(300),
+        jitter: false,
+        ..Default::default()
+    };
+
+    assert_eq!(policy.delay(1, None), Some(Duration::from_millis(100)));
+    assert_eq!(policy.delay(2, None), Some(Duration::from_millis(200)));
+    assert_eq!(policy.delay(3, None), Some(Duration::from_millis(300)));
+    assert_eq!(
+        policy.delay(1, Some(Duration::from_millis(250))),
+        Some(Duration::from_millis(250))
+    );
+    assert_eq!(policy.delay(1, Some(Duration::from_secs(10))), None);
+}
+
+#[test]
+fn test_retry_policy_delay_with_jitter() {
+    let policy = RetryPolicy {
+        base_delay: Duration::from_millis(100),
+        ..Default::default()
+    };
+
+    let delay = policy.delay(1, None).unwrap();
+
+    assert!(delay >= Duration::from_millis(50));
+    assert!(delay <= Duration::from_millis(100));
+}
+
+#[test]
+fn test_retry_after() {
+    let mut headers = HeaderMap::new();
+    assert_eq!(retry_after(&headers), None);
+
+    headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+    assert_eq!(retry_after(&headers), Some(Duration::from_secs(7)));
+
+    let date = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(120));
+    headers.insert(RETRY_AFTER, HeaderValue::from_str(&date).unwrap());
+    let delay = retry_after(&headers).unwrap();
+    assert!(delay > Duration::from_secs(100) && delay <= Duration::from_secs(120));
+
+    headers.insert(RETRY_AFTER, HeaderValue::from_static("soon"));
+    assert_eq!(retry_after(&headers), None);
+}
+
+#[test]
+fn test_retries_in_runtime_without_timers() {
+    let server = FakeServer::new();
+    server.inject_fault(
+        PATH_PREVIEW,
+        Fault::Status(StatusCode::SERVICE_UNAVAILABLE),
+        1,
+    );
+    let mut configuration = server.configuration();
+    configuration.set_retry_policy(test_retry_policy());
+    let client = SmsClient::with_transport(configuration, Arc::new(server.clone()));
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    let response = runtime
+        .block_on(client.preview(PreviewRequestBody::new(DUMMY_TEXT)))
+        .unwrap();
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(server.requests().len(), 2);
+}
@@ -1,10 +1,13 @@
 use crate::api::{
     sms::*,
-    tests::{mock_blocking_json_endpoint, mock_json_endpoint, test_configuration, DUMMY_TEXT},
+    tests::{mock_json_endpoint, test_configuration, DUMMY_TEXT},
     SdkError,
 };
 use crate::model::sms::{ScheduledStatus::Paused, *};
 
+#[cfg(tokio)]
+use crate::api::tests::mock_blocking_json_endpoint;
+
 const DUMMY_BASE_URL: &str = "https://some.url";
 
 #[tokio::test]
@@ -58,6 +61,7 @@ async fn test_preview_bad_request() {
     }
 }
 
+#[cfg(tokio)]
 #[test]
 fn test_blocking_preview_valid() {
     let expected_response = r#"
@@ -119,7 +123,7 @@ async fn test_preview_server_error() {
     let request_body = PreviewRequestBody::new(DUMMY_TEXT);
 
     let error = client.preview(request_body).await.unwrap_err();
-    if let SdkError::ApiRequestError(api_error) = error {
+    if let SdkError::Unauthorized(api_error) = error {
         assert_eq!(api_error.status, expected_status);
         assert!(!api_error
             .details
@@ -285,6 +289,45 @@ async fn test_send_valid() {
     assert!(!response.body.messages.unwrap().is_empty());
 }
 
+#[cfg(tokio)]
+#[test]
+fn test_blocking_send_valid() {
+    let expected_response = r#"
+    {
+      "bulkId": "2034072219640523073",
+      "messages": [
+        {
+          "messageId": "41793026727",
+          "status": {
+            "groupId": 1,
+            "groupName": "PENDING",
+            "id": 26,
+            "name": "MESSAGE_ACCEPTED"
+          },
+          "to": "123456789101"
+        }
+      ]
+    }
+    "#;
+
+    let server = mock_blocking_json_endpoint(
+        httpmock::Method::POST,
+        PATH_SEND,
+        expected_response,
+        reqwest::StatusCode::OK,
+    );
+
+    let client = BlockingSmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let message = Message::new(vec![Destination::new("123456789101")]);
+    let request_body = SendRequestBody::new(vec![message]);
+
+    let response = client.send(request_body).unwrap();
+
+    assert_eq!(response.status, reqwest::StatusCode::OK);
+    assert_eq!(response.body.bulk_id.unwrap(), "2034072219640523073");
+}
+
 #[tokio::test]
 async fn test_send_binary_valid() {
     let expected_response = r#"
@@ -1070,6 +1113,44 @@ async fn test_send_pin_over_sms_valid() {
     );
 }
 
+#[cfg(tokio)]
+#[test]
+fn test_blocking_send_pin_over_sms_valid() {
+    let expected_response = r#"
+    {
+      "pinId": "9C817C6F8AF3D48F9FE553282AFA2B67",
+      "to": "41793026727",
+      "ncStatus": "NC_DESTINATION_REACHABLE",
+      "smsStatus": "MESSAGE_SENT"
+    }
+    "#;
+
+    let server = mock_blocking_json_endpoint(
+        httpmock::Method::POST,
+        PATH_SEND_PIN_OVER_SMS,
+        expected_response,
+        reqwest::StatusCode::OK,
+    );
+
+    let client = BlockingSmsClient::with_configuration(test_configuration(&server.base_url()));
+
+    let request_body = SendPinOverSmsRequestBody::new(
+        "HJ675435E3A6EA43432G5F37A635KJ8B",
+        "16A8B5FE2BCD6CA716A2D780CB3F3390",
+        "5555555555",
+    );
+
+    let response = client
+        .send_pin_over_sms(SendPinOverSmsQueryParameters::default(), request_body)
+        .unwrap();
+
+    assert_eq!(response.status, reqwest::StatusCode::OK);
+    assert_eq!(
+        response.body.pin_id.unwrap(),
+        "9C817C6F8AF3D48F9FE553282AFA2B67"
+    );
+}
+
 #[tokio::test]
 async fn test_send_pin_over_sms_empty_app_id() {
     let client = SmsClient::with_configuration(test_configuration("https://some.url"));
@@ -0,0 +1,484 @@
+use std::time::{Duration, Instant};
+
+use reqwest::StatusCode;
+
+use crate::api::testing::{FakeServer, Fault};
+use crate::api::transport::Transport;
+use crate::api::{sms::PATH_PREVIEW, tests::DUMMY_TEXT, SdkError};
+use crate::configuration::RetryPolicy;
+use crate::model::email::{
+    AddDomainRequestBody, DomainsQueryParameters, SendRequestBody as EmailSendRequestBody,
+};
+use crate::model::sms::*;
+use crate::model::whatsapp::{CreateTemplateRequestBody, SendTextRequestBody, TextContent};
+
+fn text_message(to: &str, text: &str) -> Message {
+    let mut message = Message::new(vec![Destination::new(to)]);
+    message.text = Some(text.to_string());
+    message
+}
+
+#[tokio::test]
+async fn test_sent_sms_produce_reports_once_and_logs() {
+    let server = FakeServer::new();
+    server.set_undeliverable("41793026728");
+    let client = server.client().sms();
+
+    let response = client
+        .send(SendRequestBody::new(vec![
+            text_message("41793026727", DUMMY_TEXT),
+            text_message("41793026728", DUMMY_TEXT),
+        ]))
+        .await
+        .unwrap();
+    let bulk_id = response.body.bulk_id.unwrap();
+    assert_eq!(response.body.messages.unwrap().len(), 2);
+
+    let reports = client
+        .delivery_reports(DeliveryReportsQueryParameters::new())
+        .await
+        .unwrap()
+        .body
+        .results
+        .unwrap();
+    let group_names: Vec<_> = reports
+        .iter()
+        .map(|report| report.status.clone().unwrap().group_name.unwrap())
+        .collect();
+    assert_eq!(group_names, vec!["DELIVERED", "UNDELIVERABLE"]);
+    assert!(reports
+        .iter()
+        .all(|report| report.bulk_id.as_deref() == Some(bulk_id.as_str())));
+
+    let reports = client
+        .delivery_reports(DeliveryReportsQueryParameters::new())
+        .await
+        .unwrap();
+    assert!(reports.body.results.unwrap().is_empty());
+
+    let mut query_parameters = LogsQueryParameters::new();
+    query_parameters.general_status = Some("DELIVERED".to_string());
+    let logs = client
+        .logs(query_parameters)
+        .await
+        .unwrap()
+        .body
+        .results
+        .unwrap();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].to.as_deref(), Some("41793026727"));
+    assert_eq!(logs[0].text.as_deref(), Some(DUMMY_TEXT));
+}
+
+#[tokio::test]
+async fn test_logs_filtered_by_repeated_message_ids() {
+    let client = FakeServer::new().client().sms();
+    let messages = client
+        .send(SendRequestBody::new(vec![
+            text_message("41793026727", DUMMY_TEXT),
+            text_message("41793026728", DUMMY_TEXT),
+            text_message("41793026729", DUMMY_TEXT),
+        ]))
+        .await
+        .unwrap()
+        .body
+        .messages
+        .unwrap();
+
+    let mut query_parameters = LogsQueryParameters::new();
+    query_parameters.message_id = Some(vec![
+        messages[0].message_id.clone().unwrap(),
+        messages[2].message_id.clone().unwrap(),
+    ]);
+    let logs = client
+        .logs(query_parameters)
+        .await
+        .unwrap()
+        .body
+        .results
+        .unwrap();
+
+    let recipients: Vec<_> = logs.iter().map(|log| log.to.as_deref()).collect();
+    assert_eq!(recipients, vec![Some("41793026727"), Some("41793026729")]);
+}
+
+#[tokio::test]
+async fn test_scheduled_bulk_lifecycle() {
+    let server = FakeServer::new();
+    let client = server.client().sms();
+
+    let mut message = text_message("41793026727", DUMMY_TEXT);
+    message.send_at = Some("2100-01-01T10:00:00.000+0000".to_string());
+    let mut request_body = SendRequestBody::new(vec![message]);
+    request_body.bulk_id = Some("some-bulk".to_string());
+    client.send(request_body).await.unwrap();
+
+    let scheduled = client
+        .scheduled(ScheduledQueryParameters::new("some-bulk"))
+        .await
+        .unwrap();
+    assert_eq!(scheduled.body.send_at, "2100-01-01T10:00:00.000+0000");
+
+    let rescheduled = client
+        .reschedule(
+            RescheduleQueryParameters::new("some-bulk"),
+            RescheduleRequestBody::new("2100-01-02T10:00:00.000+02:00"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(rescheduled.body.send_at, "2100-01-02T08:00:00.000+0000");
+
+    client
+        .update_scheduled_status(
+            UpdateScheduledStatusQueryParameters::new("some-bulk"),
+            UpdateScheduledStatusRequestBody::new(ScheduledStatus::Paused),
+        )
+        .await
+        .unwrap();
+    server.deliver_scheduled();
+    let reports = client
+        .delivery_reports(DeliveryReportsQueryParameters::new())
+        .await
+        .unwrap();
+    assert!(reports.body.results.unwrap().is_empty());
+
+    client
+        .update_scheduled_status(
+            UpdateScheduledStatusQueryParameters::new("some-bulk"),
+            UpdateScheduledStatusRequestBody::new(ScheduledStatus::Pending),
+        )
+        .await
+        .unwrap();
+    server.deliver_scheduled();
+    let status = client
+        .scheduled_status(ScheduledStatusQueryParameters::new("some-bulk"))
+        .await
+        .unwrap();
+    assert_eq!(status.body.status, Some(ScheduledStatus::Finished));
+    let reports = client
+        .delivery_reports(DeliveryReportsQueryParameters::new())
+        .await
+        .unwrap();
+    assert_eq!(reports.body.results.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_unknown_bulk_not_found() {
+    let client = FakeServer::new().client().sms();
+
+    let error = client
+        .scheduled(ScheduledQueryParameters::new("missing-bulk"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, SdkError::NotFound(_)));
+}
+
+#[tokio::test]
+async fn test_inbound_sms_returned_once() {
+    let server = FakeServer::new();
+    server.receive_sms("41793026727", "447860099299", "Hello there");
+    let client = server.client().sms();
+
+    let reports = client
+        .inbound_reports(InboundReportsQueryParameters::new())
+        .await
+        .unwrap();
+    let results = reports.body.results.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].keyword.as_deref(), Some("HELLO"));
+
+    let reports = client
+        .inbound_reports(InboundReportsQueryParameters::new())
+        .await
+        .unwrap();
+    assert_eq!(reports.body.message_count, Some(0));
+}
+
+#[tokio::test]
+async fn test_preview_counts_messages() {
+    let client = FakeServer::new().client().sms();
+
+    let response = client
+        .preview(PreviewRequestBody::new(&"a".repeat(161)))
+        .await
+        .unwrap();
+
+    let preview = &response.body.previews.unwrap()[0];
+    assert_eq!(preview.message_count, Some(2));
+    assert_eq!(preview.characters_remaining, Some(145));
+}
+
+#[tokio::test]
+async fn test_tfa_pin_verification() {
+    let server = FakeServer::new();
+    let client = server.client().sms();
+
+    let application = client
+        .create_tfa_application(TfaApplication::new("some-app"))
+        .await
+        .unwrap()
+        .body;
+    let app_id = application.application_id.unwrap();
+    let template = client
+        .create_tfa_message_template(
+            &app_id,
+            TfaMessageTemplate::new("Your PIN is {{pin}}", PinType::Numeric, 4),
+        )
+        .await
+        .unwrap()
+        .body;
+    let msg_id = template.message_id.unwrap();
+
+    let response = client
+        .send_pin_over_sms(
+            SendPinOverSmsQueryParameters::new(),
+            SendPinOverSmsRequestBody::new(&app_id, &msg_id, "41793026727"),
+        )
+        .await
+        .unwrap();
+    let pin_id = response.body.pin_id.unwrap();
+    let code = server.pin_code(&pin_id).unwrap();
+    assert_eq!(code.len(), 4);
+
+    let logs = client.logs(LogsQueryParameters::new()).await.unwrap();
+    assert_eq!(
+        logs.body.results.unwrap()[0].text,
+        Some(format!("Your PIN is {}", code))
+    );
+
+    let wrong = client
+        .verify_phone_number(&pin_id, VerifyPhoneNumberRequestBody::new("wrong"))
+        .await
+        .unwrap();
+    assert_eq!(wrong.body.pin_error.as_deref(), Some("WRONG_PIN"));
+    assert_eq!(wrong.body.verified, Some(false));
+
+    let verified = client
+        .verify_phone_number(&pin_id, VerifyPhoneNumberRequestBody::new(&code))
+        .await
+        .unwrap();
+    assert_eq!(verified.body.verified, Some(true));
+    assert_eq!(verified.body.attempts_remaining, Some(8));
+
+    let status = client
+        .tfa_verification_status(
+            &app_id,
+            TfaVerificationStatusQueryParameters::new("41793026727"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(status.body.verifications.unwrap()[0].verified, Some(true));
+}
+
+#[tokio::test]
+async fn test_email_send_and_domains() {
+    let server = FakeServer::new();
+    let client = server.client().email();
+
+    let mut request_body = EmailSendRequestBody::new("someone@example.com");
+    request_body.from = Some("sender@example.com".to_string());
+    request_body.text = Some(DUMMY_TEXT.to_string());
+    let response = client.send(request_body).await.unwrap();
+    let message = &response.body.messages.unwrap()[0];
+    assert_eq!(message.to.as_deref(), Some("someone@example.com"));
+
+    let logs = client
+        .logs(crate::model::email::LogsQueryParameters::new())
+        .await
+        .unwrap();
+    let log = &logs.body.results.unwrap()[0];
+    assert_eq!(log.from.as_deref(), Some("sender@example.com"));
+    assert_eq!(log.text.as_deref(), Some(DUMMY_TEXT));
+
+    client
+        .add_domain(AddDomainRequestBody::new("example.com"))
+        .await
+        .unwrap();
+    server.add_verified_domain("example.org");
+    let status = client.verify_domain("example.com").await.unwrap();
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(
+        client.domain("example.com").await.unwrap().body.active,
+        Some(true)
+    );
+
+    let mut query_parameters = DomainsQueryParameters::new();
+    query_parameters.size = Some(1);
+    let domains = client.domains(query_parameters).await.unwrap().body;
+    assert_eq!(domains.paging.unwrap().total_pages, Some(2));
+    assert_eq!(domains.results.unwrap().len(), 1);
+
+    let status = client.delete_domain("example.com").await.unwrap();
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(matches!(
+        client.domain("example.com").await.unwrap_err(),
+        SdkError::NotFound(_)
+    ));
+}
+
+#[tokio::test]
+async fn test_whatsapp_messages_and_templates() {
+    let server = FakeServer::new();
+    let client = server.client().whatsapp();
+
+    client
+        .send_text(SendTextRequestBody::new(
+            "44444444444",
+            "55555555555",
+            TextContent::new("some text"),
+        ))
+        .await
+        .unwrap();
+    let messages = server.whatsapp_messages();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].kind, "text");
+    assert_eq!(messages[0].content["text"], "some text");
+
+    let request_body: CreateTemplateRequestBody = serde_json::from_str(
+        r#"
+            {
+              "name": "some_template",
+              "language": "en",
+              "category": "OTP",
+              "structure": {
+                "body": {
+                  "text": "body {{1}} content"
+                }
+              }
+            }
+        "#,
+    )
+    .unwrap();
+    client
+        .create_template("44444444444", request_body)
+        .await
+        .unwrap();
+    let templates = client.templates("44444444444").await.unwrap();
+    assert_eq!(templates.body.templates.unwrap().len(), 1);
+
+    let status = client
+        .delete_template("44444444444", "some_template")
+        .await
+        .unwrap();
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let templates = client.templates("44444444444").await.unwrap();
+    assert!(templates.body.templates.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_encoded_path_parameters_are_decoded() {
+    let server = FakeServer::new();
+    let client = server.client().whatsapp();
+    let request_body: CreateTemplateRequestBody = serde_json::from_str(
+        r#"
+            {
+              "name": "sale/50% off",
+              "language": "en",
+              "category": "OTP",
+              "structure": {
+                "body": {
+                  "text": "body {{1}} content"
+                }
+              }
+            }
+        "#,
+    )
+    .unwrap();
+    client
+        .create_template("44444444444", request_body)
+        .await
+        .unwrap();
+
+    let status = client
+        .delete_template("44444444444", "sale/50% off")
+        .await
+        .unwrap();
+
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(server
+        .requests()
+        .iter()
+        .any(|(_, path)| path.ends_with("/templates/sale%2F50%25%20off")));
+}
+
+#[tokio::test]
+async fn test_injected_faults() {
+    let server = FakeServer::new();
+    server.inject_fault(
+        PATH_PREVIEW,
+        Fault::Status(StatusCode::SERVICE_UNAVAILABLE),
+        1,
+    );
+    server.inject_fault(PATH_PREVIEW, Fault::Disconnect, 1);
+    let client = server.client().sms();
+
+    let error = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, SdkError::ServerError(_)));
+
+    let error = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, SdkError::Transport { .. }));
+
+    client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap();
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_injected_rate_limit_retried() {
+    let server = FakeServer::new();
+    server.inject_fault(PATH_PREVIEW, Fault::RateLimited(Duration::ZERO), 2);
+    let mut configuration = server.configuration();
+    configuration.set_retry_policy(RetryPolicy {
+        base_delay: Duration::from_millis(1),
+        jitter: false,
+        ..Default::default()
+    });
+    let client = crate::api::sms::SmsClient::with_transport(
+        configuration,
+        std::sync::Arc::new(server.clone()),
+    );
+
+    client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap();
+
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_injected_latency() {
+    let server = FakeServer::new();
+    server.set_path_latency(PATH_PREVIEW, Duration::from_millis(50));
+    let client = server.client().sms();
+
+    let start = Instant::now();
+    client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap();
+
+    assert!(start.elapsed() >= Duration::from_millis(50));
+}
+
+#[tokio::test]
+async fn test_requests_without_authentication_rejected() {
+    let server = FakeServer::new();
+    let request = reqwest::Client::new()
+        .get(format!("{}/sms/1/logs", crate::api::testing::FAKE_BASE_URL))
+        .build()
+        .unwrap();
+
+    let response = server.execute(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+use httpmock::prelude::*;
+use reqwest::StatusCode;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::Layer;
+
+use crate::api::{
+    sms::{endpoints::GetTfaApplication, SmsClient, PATH_GET_TFA_APPLICATION, PATH_SEND},
+    tests::{test_configuration, DUMMY_TEXT},
+};
+use crate::model::sms::{Destination, Message, SendRequestBody};
+
+type Spans = Arc<Mutex<HashMap<String, HashMap<String, String>>>>;
+
+// Collects the fields of every span, by span name.
+#[derive(Clone, Default)]
+struct SpanCollector {
+    spans: Spans,
+    names: Arc<Mutex<HashMap<Id, String>>>,
+}
+
+struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+}
+
+impl<S: Subscriber> Layer<S> for SpanCollector {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _ctx: Context<'_, S>) {
+        let name = attrs.metadata().name().to_string();
+        let mut spans = self.spans.lock().unwrap();
+        let fields = spans.entry(name.clone()).or_default();
+        attrs.record(&mut FieldVisitor(fields));
+        self.names.lock().unwrap().insert(id.clone(), name);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+        let name = self.names.lock().unwrap()[id].clone();
+        let mut spans = self.spans.lock().unwrap();
+        values.record(&mut FieldVisitor(spans.get_mut(&name).unwrap()));
+    }
+}
+
+#[tokio::test]
+async fn test_send_span_fields() {
+    let expected_response = r#"
+        {
+          "bulkId": "some-bulk-id",
+          "messages": [
+            {"messageId": "first-message-id", "to": "555555555555"},
+            {"messageId": "second-message-id", "to": "555555555556"}
+          ]
+        }
+    "#;
+
+    let server = MockServer::start_async().await;
+    server.mock(|when, then| {
+        when.method(POST).path(PATH_SEND);
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(expected_response);
+    });
+
+    let collector = SpanCollector::default();
+    let subscriber = tracing_subscriber::registry().with(collector.clone());
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+    let mut message = Message::new(vec![Destination::new("555555555555")]);
+    message.text = Some(DUMMY_TEXT.to_string());
+
+    client
+        .send(SendRequestBody::new(vec![message]))
+        .await
+        .unwrap();
+
+    let spans = collector.spans.lock().unwrap();
+    let fields = &spans["SmsClient::send"];
+    assert_eq!(fields["path"], PATH_SEND);
+    assert_eq!(fields["method"], "POST");
+    assert_eq!(fields["status"], "200");
+    assert_eq!(fields["retries"], "0");
+    assert_eq!(fields["bulk_id"], "some-bulk-id");
+    assert_eq!(fields["message_ids"], "first-message-id,second-message-id");
+    assert!(fields.contains_key("latency_ms"));
+    assert!(fields.contains_key("correlation_id"));
+    assert!(!fields
+        .values()
+        .any(|value| value.contains("some-api-key") || value.contains(DUMMY_TEXT)));
+}
+
+#[tokio::test]
+async fn test_execute_span_fields() {
+    let server = MockServer::start_async().await;
+    server.mock(|when, then| {
+        when.method(GET).path("/2fa/2/applications/some-app");
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(r#"{"applicationId": "some-app", "name": "Some app", "messageId": "some-id"}"#);
+    });
+
+    let collector = SpanCollector::default();
+    let subscriber = tracing_subscriber::registry().with(collector.clone());
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let client = SmsClient::with_configuration(test_configuration(&server.base_url()));
+    client
+        .execute(GetTfaApplication::new("some-app"))
+        .await
+        .unwrap();
+
+    let spans = collector.spans.lock().unwrap();
+    let fields = &spans["SmsClient::execute"];
+    assert_eq!(fields["path"], PATH_GET_TFA_APPLICATION);
+    assert_eq!(fields["method"], "GET");
+    assert_eq!(fields["status"], "200");
+    // Only responses to sent messages are searched for IDs.
+    assert!(!fields.contains_key("message_ids"));
+}
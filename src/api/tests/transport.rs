@@ -0,0 +1,332 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+use std::time::{Duration, Instant};
+
+use reqwest::StatusCode;
+
+use crate::api::{
+    sms::{SmsClient, PATH_PREVIEW},
+    tests::{test_configuration, DUMMY_TEXT},
+    transport::{Transport, TransportFuture},
+    SdkError,
+};
+use crate::configuration::{RateLimit, RateLimiter, RetryPolicy};
+use crate::model::sms::PreviewRequestBody;
+
+#[cfg(tokio)]
+use crate::api::sms::BlockingSmsClient;
+#[cfg(async_io)]
+use crate::api::transport::AsyncIoTransport;
+#[cfg(async_io)]
+use crate::configuration::{ApiKey, Configuration, ConfigurationError};
+#[cfg(async_io)]
+use httpmock::prelude::*;
+
+const DUMMY_BASE_URL: &str = "https://some.url";
+
+const PREVIEW_RESPONSE: &str = r#"
+    {
+      "originalText": "Some text for tests.",
+      "previews": []
+    }
+"#;
+
+// Answers every request from memory, failing the first `failures` requests before sending them,
+// or after sending them if `fail_after_sending` is set.
+#[derive(Debug, Default)]
+struct InMemoryTransport {
+    failures: u32,
+    fail_after_sending: bool,
+    attempts: AtomicU32,
+    requests: Mutex<Vec<reqwest::Request>>,
+}
+
+impl Transport for InMemoryTransport {
+    fn execute(&self, request: reqwest::Request) -> TransportFuture<'_> {
+        let attempt = self.attempts.fetch_add(1, Ordering::SeqCst) + 1;
+        self.requests.lock().unwrap().push(request);
+
+        Box::pin(async move {
+            if attempt <= self.failures && self.fail_after_sending {
+                return Err(SdkError::transport("connection reset"));
+            }
+            if attempt <= self.failures {
+                return Err(SdkError::transport_connect("connection refused"));
+            }
+
+            let response = http::Response::builder()
+                .status(StatusCode::OK.as_u16())
+                .header("content-type", "application/json")
+                .body(PREVIEW_RESPONSE)
+                .unwrap();
+
+            Ok(reqwest::Response::from(response))
+        })
+    }
+}
+
+// Unparks the thread that polls a future when it is woken.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+// Polls a future to completion on the current thread, outside of any async runtime.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = Box::pin(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_custom_transport() {
+    let transport = Arc::new(InMemoryTransport::default());
+    let client = SmsClient::with_transport(test_configuration(DUMMY_BASE_URL), transport.clone());
+
+    let response = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body.original_text.unwrap(), DUMMY_TEXT);
+
+    let requests = transport.requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method(), reqwest::Method::POST);
+    assert_eq!(
+        requests[0].url().as_str(),
+        format!("{}{}", DUMMY_BASE_URL, PATH_PREVIEW)
+    );
+    assert_eq!(requests[0].headers()["Authorization"], "App some-api-key");
+    assert!(requests[0].headers().contains_key("User-Agent"));
+}
+
+#[tokio::test]
+async fn test_custom_transport_errors_are_retried() {
+    let transport = Arc::new(InMemoryTransport {
+        failures: 2,
+        ..Default::default()
+    });
+    let mut configuration = test_configuration(DUMMY_BASE_URL);
+    configuration.set_retry_policy(RetryPolicy {
+        base_delay: Duration::from_millis(1),
+        ..Default::default()
+    });
+    let client = SmsClient::with_transport(configuration, transport.clone());
+
+    let response = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(transport.attempts.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_custom_transport_errors_after_sending_are_not_retried() {
+    let transport = Arc::new(InMemoryTransport {
+        failures: 2,
+        fail_after_sending: true,
+        ..Default::default()
+    });
+    let mut configuration = test_configuration(DUMMY_BASE_URL);
+    configuration.set_retry_policy(RetryPolicy {
+        base_delay: Duration::from_millis(1),
+        ..Default::default()
+    });
+    let client = SmsClient::with_transport(configuration, transport.clone());
+
+    let error = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, SdkError::Transport { .. }));
+    assert!(!error.is_retryable());
+    assert_eq!(transport.attempts.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_custom_transport_error() {
+    let transport = Arc::new(InMemoryTransport {
+        failures: 1,
+        ..Default::default()
+    });
+    let client = SmsClient::with_transport(test_configuration(DUMMY_BASE_URL), transport);
+
+    let error = client
+        .preview(PreviewRequestBody::new(DUMMY_TEXT))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, SdkError::Connect { .. }));
+    assert!(error.is_retryable());
+}
+
+#[cfg(tokio)]
+#[test]
+fn test_blocking_custom_transport() {
+    let transport = Arc::new(InMemoryTransport::default());
+    let client =
+        BlockingSmsClient::with_transport(test_configuration(DUMMY_BASE_URL), transport.clone());
+
+    let response = client.preview(PreviewRequestBody::new(DUMMY_TEXT)).unwrap();
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(transport.attempts.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_custom_transport_without_runtime() {
+    let transport = Arc::new(InMemoryTransport {
+        failures: 1,
+        ..Default::default()
+    });
+    let mut configuration = test_configuration(DUMMY_BASE_URL);
+    configuration.set_retry_policy(RetryPolicy {
+        base_delay: Duration::from_millis(50),
+        jitter: false,
+        ..Default::default()
+    });
+    configuration.set_rate_limiter(Arc::new(
+        RateLimiter::new().with_endpoint_limit(PATH_PREVIEW, RateLimit::per_second(1)),
+    ));
+    let client = SmsClient::with_transport(configuration, transport.clone());
+
+    let start = Instant::now();
+    let response = block_on(client.preview(PreviewRequestBody::new(DUMMY_TEXT))).unwrap();
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(transport.attempts.load(Ordering::SeqCst), 2);
+    // The retry waited for its delay and for the rate limiter.
+    assert!(start.elapsed() >= Duration::from_millis(900));
+}
+
+#[cfg(async_io)]
+#[test]
+fn test_async_io_transport_without_runtime() {
+    let server = MockServer::start();
+    let preview_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path(PATH_PREVIEW)
+            .header("Authorization", "App some-api-key");
+        then.status(StatusCode::OK.as_u16())
+            .header("content-type", "application/json")
+            .body(PREVIEW_RESPONSE);
+    });
+    let configuration = test_configuration(&server.base_url());
+    let transport = AsyncIoTransport::with_configuration(&configuration).unwrap();
+    let client = SmsClient::with_transport(configuration, Arc::new(transport));
+
+    // The second request is sent over the connection of the first one.
+    for _ in 0..2 {
+        let response = block_on(client.preview(PreviewRequestBody::new(DUMMY_TEXT))).unwrap();
+
+        assert_eq!(response.status, StatusCode::OK);
+        assert_eq!(response.body.original_text.unwrap(), DUMMY_TEXT);
+    }
+    preview_mock.assert_hits(2);
+}
+
+#[cfg(async_io)]
+#[test]
+fn test_async_io_transport_timeout() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path(PATH_PREVIEW);
+        then.status(StatusCode::OK.as_u16())
+            .body(PREVIEW_RESPONSE)
+            .delay(Duration::from_secs(2));
+    });
+    let configuration = Configuration::builder(server.base_url())
+        .api_key(ApiKey::new("some-api-key".to_string()))
+        .timeout(Duration::from_millis(100))
+        .build()
+        .unwrap();
+    let transport = AsyncIoTransport::with_configuration(&configuration).unwrap();
+    let client = SmsClient::with_transport(configuration, Arc::new(transport));
+
+    let start = Instant::now();
+    let error = block_on(client.preview(PreviewRequestBody::new(DUMMY_TEXT))).unwrap_err();
+
+    assert!(error.is_timeout());
+    assert!(start.elapsed() < Duration::from_secs(2));
+}
+
+#[cfg(async_io)]
+#[test]
+fn test_async_io_transport_rejects_proxy() {
+    let builder = Configuration::builder(DUMMY_BASE_URL)
+        .proxy(reqwest::Proxy::all("http://some.proxy:8080").unwrap());
+
+    // Without Tokio, the builder already builds an `AsyncIoTransport`.
+    #[cfg(not(tokio))]
+    let error = builder.build().unwrap_err();
+    #[cfg(tokio)]
+    let error = AsyncIoTransport::with_configuration(&builder.build().unwrap()).unwrap_err();
+
+    assert!(matches!(
+        error,
+        ConfigurationError::UnsupportedByTransport("proxy")
+    ));
+}
+
+#[cfg(async_io)]
+#[test]
+fn test_async_io_transport_connect_error() {
+    // Nothing listens on port 9 of localhost.
+    let transport = AsyncIoTransport::new();
+    let client = SmsClient::with_transport(
+        test_configuration("http://127.0.0.1:9"),
+        Arc::new(transport),
+    );
+
+    let error = block_on(client.preview(PreviewRequestBody::new(DUMMY_TEXT))).unwrap_err();
+
+    assert!(matches!(error, SdkError::Connect { .. }));
+}
+
+#[cfg(async_io)]
+#[test]
+fn test_async_io_transport_does_not_retry_after_sending() {
+    use std::io::Read;
+    use std::net::TcpListener;
+
+    // Reads the request and closes the connection without responding, counting the connections.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let connections = Arc::new(AtomicU32::new(0));
+    let accepted = connections.clone();
+    thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            accepted.fetch_add(1, Ordering::SeqCst);
+            let _ = stream.read(&mut [0; 4096]);
+        }
+    });
+    let mut configuration = test_configuration(&format!("http://{}", address));
+    configuration.set_retry_policy(RetryPolicy {
+        base_delay: Duration::from_millis(1),
+        ..Default::default()
+    });
+    let client = SmsClient::with_transport(configuration, Arc::new(AsyncIoTransport::new()));
+
+    let error = block_on(client.preview(PreviewRequestBody::new(DUMMY_TEXT))).unwrap_err();
+
+    assert!(matches!(error, SdkError::Transport { .. }));
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+}
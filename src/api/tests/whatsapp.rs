@@ -1,8 +1,11 @@
 use crate::api::tests::{mock_json_endpoint, test_configuration};
 use crate::api::whatsapp::*;
-use crate::api::SdkError::ApiRequestError;
+use crate::api::SdkError::{ApiRequestError, RateLimited, Unauthorized};
 use crate::model::whatsapp::*;
 
+#[cfg(tokio)]
+use crate::api::tests::mock_blocking_json_endpoint;
+
 fn dummy_send_text_request_body() -> SendTextRequestBody {
     SendTextRequestBody::new("44444444444", "55555555555", TextContent::new("some text"))
 }
@@ -139,7 +142,7 @@ async fn send_text_api_error_401() {
         .unwrap();
 
     match sdk_error {
-        ApiRequestError(api_error) => {
+        Unauthorized(api_error) => {
             assert_eq!(api_error.status, reqwest::StatusCode::UNAUTHORIZED);
             assert_eq!(
                 api_error
@@ -187,7 +190,7 @@ async fn send_text_api_error_429() {
         .unwrap();
 
     match sdk_error {
-        ApiRequestError(api_error) => {
+        RateLimited(api_error) => {
             assert_eq!(api_error.status, reqwest::StatusCode::TOO_MANY_REQUESTS);
             assert_eq!(
                 api_error
@@ -796,7 +799,7 @@ async fn templates_valid() {
     "#;
 
     let sender = "441134960000";
-    let path = PATH_GET_TEMPLATES.replace("{sender}", &sender);
+    let path = PATH_GET_TEMPLATES.replace("{sender}", sender);
 
     let server = mock_json_endpoint(
         httpmock::Method::GET,
@@ -886,7 +889,7 @@ async fn create_template_valid() {
     "#;
 
     let sender = "441134960000";
-    let path = PATH_CREATE_TEMPLATE.replace("{sender}", &sender);
+    let path = PATH_CREATE_TEMPLATE.replace("{sender}", sender);
 
     let server = mock_json_endpoint(
         httpmock::Method::POST,
@@ -932,3 +935,61 @@ async fn delete_template_valid() {
 
     assert_eq!(status, reqwest::StatusCode::NO_CONTENT);
 }
+
+#[cfg(tokio)]
+#[test]
+fn blocking_send_text_valid() {
+    let expected_response = r#"
+        {
+          "to": "55555555555",
+          "messageCount": 1,
+          "messageId": "a28dd97c-1ffb-4fcf-99f1-0b557ed381da",
+          "status": {
+            "groupId": 1,
+            "groupName": "PENDING",
+            "id": 7,
+            "name": "PENDING_ENROUTE",
+            "description": "Message sent to next instance"
+          }
+        }
+    "#;
+
+    let server = mock_blocking_json_endpoint(
+        httpmock::Method::POST,
+        PATH_SEND_TEXT,
+        expected_response,
+        reqwest::StatusCode::OK,
+    );
+
+    let wa_client =
+        BlockingWhatsAppClient::with_configuration(test_configuration(&server.base_url()));
+
+    let response = wa_client.send_text(dummy_send_text_request_body()).unwrap();
+
+    assert_eq!(response.status, reqwest::StatusCode::OK);
+    assert_eq!(response.body.message_count.unwrap(), 1);
+}
+
+#[cfg(tokio)]
+#[test]
+fn blocking_delete_template_valid() {
+    let template_name = "media_template_with_buttons";
+    let sender = "441134960000";
+    let path = PATH_DELETE_TEMPLATE
+        .replace("{sender}", sender)
+        .replace("{templateName}", template_name);
+
+    let server = mock_blocking_json_endpoint(
+        httpmock::Method::DELETE,
+        &path,
+        "",
+        reqwest::StatusCode::NO_CONTENT,
+    );
+
+    let wa_client =
+        BlockingWhatsAppClient::with_configuration(test_configuration(&server.base_url()));
+
+    let status = wa_client.delete_template(sender, template_name).unwrap();
+
+    assert_eq!(status, reqwest::StatusCode::NO_CONTENT);
+}
@@ -0,0 +1,24 @@
+// Waits between retries and for the rate limiter, on whatever executor polls the clients. On
+// `wasm32`, JavaScript timers are used. Otherwise the timers of `futures-timer` are used, which
+// share one background thread. Tokio timers are not used even within a Tokio runtime, as they
+// panic in runtimes built without `enable_time()`, which can not be detected beforehand.
+use std::time::Duration;
+
+// The clocks of the standard library panic on `wasm32`, where the ones of JavaScript are used.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use std::time::{Instant, SystemTime};
+#[cfg(target_arch = "wasm32")]
+pub(crate) use web_time::{Instant, SystemTime};
+
+// Waits for the given duration without blocking the executor.
+pub(crate) async fn sleep(duration: Duration) {
+    if duration.is_zero() {
+        return;
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    futures_timer::Delay::new(duration).await;
+
+    #[cfg(target_arch = "wasm32")]
+    gloo_timers::future::sleep(duration).await;
+}
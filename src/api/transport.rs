@@ -0,0 +1,136 @@
+//! HTTP transports used by the clients to send requests to the Infobip API.
+//!
+//! Clients build fully authenticated `reqwest::Request` values and hand them to a `Transport`,
+//! which sends them and returns the response. The default transport is `ReqwestTransport`, or
+//! `AsyncIoTransport` with the `async-io` feature and without the `tokio` feature.
+//! Implement `Transport` to send requests through a different HTTP stack, to inject faults in
+//! tests, or to answer requests from memory.
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use reqwest::{ClientBuilder, Request, Response};
+
+use crate::api::SdkError;
+use crate::configuration::Configuration;
+
+#[cfg(async_io)]
+mod async_io;
+
+#[cfg(async_io)]
+pub use async_io::AsyncIoTransport;
+
+// Transport of clients and token providers that are not given one. `ReqwestTransport` needs a
+// Tokio reactor, so `AsyncIoTransport` is preferred when Tokio is not enabled.
+#[cfg(not(all(async_io, not(tokio))))]
+pub(crate) type DefaultTransport = ReqwestTransport;
+#[cfg(all(async_io, not(tokio)))]
+pub(crate) type DefaultTransport = AsyncIoTransport;
+
+/// Future returned by `Transport::execute`.
+#[cfg(not(target_arch = "wasm32"))]
+pub type TransportFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Response, SdkError>> + Send + 'a>>;
+
+/// Future returned by `Transport::execute`. It is not `Send` on `wasm32`, where responses are
+/// bound to the JavaScript thread that fetched them.
+#[cfg(target_arch = "wasm32")]
+pub type TransportFuture<'a> = Pin<Box<dyn Future<Output = Result<Response, SdkError>> + 'a>>;
+
+/// Sends HTTP requests on behalf of the clients.
+///
+/// Responses can be built from any `http::Response` with `reqwest::Response::from`. Failures that
+/// happen before any part of the request was sent, e.g. to connect, should be returned with
+/// `SdkError::transport_connect()`, which the retry policy treats like connection errors. Other
+/// failures to obtain a response should be returned with `SdkError::transport()`, and are not
+/// retried, as the server may have received the request.
+///
+/// # Example
+/// ```
+/// # use infobip_sdk::api::transport::{Transport, TransportFuture};
+/// # use infobip_sdk::api::SdkError;
+/// #[derive(Debug)]
+/// struct UnavailableTransport;
+///
+/// impl Transport for UnavailableTransport {
+///     fn execute(&self, _request: reqwest::Request) -> TransportFuture<'_> {
+///         Box::pin(async { Err(SdkError::transport_connect("network is down")) })
+///     }
+/// }
+/// ```
+pub trait Transport: fmt::Debug + Send + Sync {
+    /// Sends the request and returns the response, whatever its status is.
+    fn execute(&self, request: Request) -> TransportFuture<'_>;
+}
+
+/// Default transport, which sends requests with a `reqwest::Client`.
+#[derive(Clone, Debug, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Creates a new `ReqwestTransport` with a default `reqwest::Client`.
+    pub fn new() -> ReqwestTransport {
+        Default::default()
+    }
+
+    /// Creates a new `ReqwestTransport` that sends requests with the given `reqwest::Client`.
+    pub fn with_client(client: reqwest::Client) -> ReqwestTransport {
+        ReqwestTransport { client }
+    }
+
+    /// Creates a new `ReqwestTransport` with a `reqwest::Client` set up with the timeouts, proxy
+    /// and root certificates of the configuration.
+    pub fn with_configuration(
+        configuration: &Configuration,
+    ) -> Result<ReqwestTransport, reqwest::Error> {
+        let builder = connection_options(reqwest::Client::builder(), configuration);
+
+        Ok(ReqwestTransport::with_client(builder.build()?))
+    }
+}
+
+// Applies the timeouts, proxy and root certificates of the configuration.
+#[cfg(not(target_arch = "wasm32"))]
+fn connection_options(mut builder: ClientBuilder, configuration: &Configuration) -> ClientBuilder {
+    if let Some(timeout) = configuration.connect_timeout() {
+        builder = builder.connect_timeout(timeout);
+    }
+    if let Some(timeout) = configuration.timeout() {
+        builder = builder.timeout(timeout);
+    }
+    if let Some(timeout) = configuration.read_timeout() {
+        builder = builder.read_timeout(timeout);
+    }
+    if let Some(proxy) = configuration.proxy() {
+        builder = builder.proxy(proxy.clone());
+    }
+    #[cfg(tls)]
+    for certificate in configuration.root_certificates() {
+        builder = builder.add_root_certificate(certificate.clone());
+    }
+
+    builder
+}
+
+// The Fetch API leaves connections to the host. Only the timeout of the configuration applies,
+// which clients set on every request.
+#[cfg(target_arch = "wasm32")]
+fn connection_options(builder: ClientBuilder, _configuration: &Configuration) -> ClientBuilder {
+    builder
+}
+
+// Transport used by clients built with `with_configuration()`. Configurations from
+// `ConfigurationBuilder::build()` carry the transport built there, with their connection options.
+// The others have no connection options, so they get a default transport.
+pub(crate) fn default_transport(configuration: &Configuration) -> Arc<dyn Transport> {
+    Arc::new(configuration.http_transport().cloned().unwrap_or_default())
+}
+
+impl Transport for ReqwestTransport {
+    fn execute(&self, request: Request) -> TransportFuture<'_> {
+        Box::pin(async move { Ok(self.client.execute(request).await?) })
+    }
+}
@@ -0,0 +1,330 @@
+// Transport that needs no async runtime. Connections are opened with `async-net`, secured with
+// `rustls` and spoken to with `hyper`. Nothing can be spawned, so every request drives its
+// connection itself until the response body has been read, and keeps it for the next request.
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_net::TcpStream;
+use futures_io::{AsyncRead, AsyncWrite};
+use futures_rustls::pki_types::ServerName;
+use futures_rustls::rustls::{crypto::ring, ClientConfig, RootCertStore};
+use futures_rustls::TlsConnector;
+use futures_util::future::{self, Either};
+use http::header::{HeaderValue, HOST};
+use http::uri::{PathAndQuery, Uri};
+use http_body_util::BodyExt;
+use hyper::client::conn::http1::{self, Connection, SendRequest};
+use reqwest::{Body, Request, Response, ResponseBuilderExt, Url};
+use smol_hyper::rt::FuturesIo;
+
+use crate::api::transport::{Transport, TransportFuture};
+use crate::api::{timer, SdkError};
+use crate::configuration::{Configuration, ConfigurationError};
+
+trait Socket: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> Socket for T {}
+
+type Io = FuturesIo<Box<dyn Socket>>;
+
+// Scheme, host and port of the connections that can be reused for a URL.
+type Origin = (String, String, u16);
+
+/// Transport that sends requests over HTTP/1.1 with `async-io` and `rustls`, so that the
+/// asynchronous clients run on any executor, or with a plain `block_on`. Servers are verified with
+/// the root certificates of the `webpki-roots` crate.
+///
+/// Connections are kept open and reused. As no task is spawned to look after them, they are only
+/// read from and written to while a request is sent over them.
+///
+/// # Example
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use infobip_sdk::api::sms::SmsClient;
+/// # use infobip_sdk::api::transport::AsyncIoTransport;
+/// # use infobip_sdk::configuration::{ApiKey, Configuration};
+/// let configuration = Configuration::builder("https://some.api.infobip.com")
+///     .api_key(ApiKey::new("some-api-key".to_string()))
+///     .build()
+///     .unwrap();
+/// let transport = AsyncIoTransport::with_configuration(&configuration).unwrap();
+/// let client = SmsClient::with_transport(configuration, Arc::new(transport));
+/// ```
+#[derive(Clone)]
+pub struct AsyncIoTransport {
+    tls: TlsConnector,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    idle: Arc<Mutex<HashMap<Origin, Vec<Conn>>>>,
+}
+
+impl AsyncIoTransport {
+    /// Creates a new `AsyncIoTransport` without timeouts.
+    pub fn new() -> AsyncIoTransport {
+        Default::default()
+    }
+
+    /// Creates a new `AsyncIoTransport` with the timeouts of the configuration. Fails if the
+    /// configuration has a proxy or root certificates, which are not supported.
+    pub fn with_configuration(
+        configuration: &Configuration,
+    ) -> Result<AsyncIoTransport, ConfigurationError> {
+        if configuration.proxy().is_some() {
+            return Err(ConfigurationError::UnsupportedByTransport("proxy"));
+        }
+        #[cfg(tls)]
+        if !configuration.root_certificates().is_empty() {
+            return Err(ConfigurationError::UnsupportedByTransport(
+                "root certificates",
+            ));
+        }
+
+        Ok(AsyncIoTransport {
+            connect_timeout: configuration.connect_timeout(),
+            timeout: configuration.timeout(),
+            read_timeout: configuration.read_timeout(),
+            ..Default::default()
+        })
+    }
+
+    async fn send(&self, request: Request) -> Result<Response, SdkError> {
+        let url = request.url().clone();
+        let origin = origin(&url)?;
+        let mut request = http::Request::try_from(request)?;
+
+        // Connections take requests in origin form, and the host in a header.
+        let path = request
+            .uri()
+            .path_and_query()
+            .cloned()
+            .unwrap_or_else(|| PathAndQuery::from_static("/"));
+        *request.uri_mut() = Uri::from(path);
+        let host = match url.port() {
+            Some(port) => format!("{}:{}", origin.1, port),
+            None => origin.1.clone(),
+        };
+        request.headers_mut().insert(
+            HOST,
+            HeaderValue::from_str(&host).map_err(SdkError::transport)?,
+        );
+
+        let mut conn = loop {
+            match self.take_idle(&origin) {
+                // The server may have closed the connection while it was idle.
+                Some(mut conn) => {
+                    if conn.ready().await {
+                        break conn;
+                    }
+                }
+                None => break self.connect(&origin).await?,
+            }
+        };
+
+        let response = conn.exchange(request, self.read_timeout).await?;
+        if !conn.sender.is_closed() {
+            self.idle
+                .lock()
+                .unwrap()
+                .entry(origin)
+                .or_default()
+                .push(conn);
+        }
+
+        let (parts, body) = response.into_parts();
+        let mut builder = http::Response::builder()
+            .status(parts.status)
+            .version(parts.version)
+            .url(url);
+        if let Some(headers) = builder.headers_mut() {
+            *headers = parts.headers;
+        }
+
+        Ok(Response::from(
+            builder.body(body).map_err(SdkError::transport)?,
+        ))
+    }
+
+    fn take_idle(&self, origin: &Origin) -> Option<Conn> {
+        self.idle.lock().unwrap().get_mut(origin)?.pop()
+    }
+
+    async fn connect(&self, origin: &Origin) -> Result<Conn, SdkError> {
+        let (scheme, host, port) = origin;
+
+        let connect = async {
+            // IPv6 addresses are written in brackets in URLs only.
+            let address = host.trim_start_matches('[').trim_end_matches(']');
+            let tcp = TcpStream::connect((address, *port))
+                .await
+                .map_err(SdkError::transport_connect)?;
+            let stream: Box<dyn Socket> = match scheme.as_str() {
+                "https" => {
+                    let name =
+                        ServerName::try_from(address.to_string()).map_err(SdkError::transport)?;
+                    Box::new(
+                        self.tls
+                            .connect(name, tcp)
+                            .await
+                            .map_err(SdkError::transport_connect)?,
+                    )
+                }
+                _ => Box::new(tcp),
+            };
+
+            let (sender, connection) = http1::handshake(FuturesIo::new(stream))
+                .await
+                .map_err(SdkError::transport_connect)?;
+
+            Ok(Conn {
+                sender,
+                connection: Box::pin(connection),
+            })
+        };
+
+        with_timeout(self.connect_timeout, connect)
+            .await
+            .unwrap_or_else(|| Err(timed_out("connecting")))
+    }
+}
+
+impl Default for AsyncIoTransport {
+    fn default() -> Self {
+        let mut roots = RootCertStore::empty();
+        roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+        let mut config = ClientConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .expect("the ring provider supports the default protocol versions")
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        config.alpn_protocols = vec![b"http/1.1".to_vec()];
+
+        AsyncIoTransport {
+            tls: TlsConnector::from(Arc::new(config)),
+            connect_timeout: None,
+            timeout: None,
+            read_timeout: None,
+            idle: Default::default(),
+        }
+    }
+}
+
+impl fmt::Debug for AsyncIoTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncIoTransport")
+            .field("connect_timeout", &self.connect_timeout)
+            .field("timeout", &self.timeout)
+            .field("read_timeout", &self.read_timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Transport for AsyncIoTransport {
+    fn execute(&self, request: Request) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let timeout = request.timeout().copied().or(self.timeout);
+
+            with_timeout(timeout, self.send(request))
+                .await
+                .unwrap_or_else(|| Err(timed_out("waiting for the response")))
+        })
+    }
+}
+
+// An open connection: the handle requests are sent with, and the future that reads and writes the
+// connection while it is polled.
+struct Conn {
+    sender: SendRequest<Body>,
+    connection: Pin<Box<Connection<Io, Body>>>,
+}
+
+impl Conn {
+    // Returns `true` once the connection can take a request, or `false` if it was closed.
+    async fn ready(&mut self) -> bool {
+        let ready = self.sender.ready();
+        futures_util::pin_mut!(ready);
+
+        match future::select(ready, self.connection.as_mut()).await {
+            Either::Left((result, _)) => result.is_ok(),
+            Either::Right(_) => false,
+        }
+    }
+
+    // Sends the request and reads the whole response, while driving the connection. Only failures
+    // before any part of the request was written are connection errors, which may be retried.
+    async fn exchange(
+        &mut self,
+        request: http::Request<Body>,
+        read_timeout: Option<Duration>,
+    ) -> Result<http::Response<Vec<u8>>, SdkError> {
+        let sender = &mut self.sender;
+        let exchange = async move {
+            let response = with_timeout(read_timeout, sender.try_send_request(request))
+                .await
+                .ok_or_else(|| timed_out("reading the response"))?
+                .map_err(|error| match error.message() {
+                    Some(_) => SdkError::transport_connect(error.into_error()),
+                    None => SdkError::transport(error.into_error()),
+                })?;
+            let (parts, mut body) = response.into_parts();
+
+            let mut bytes = Vec::new();
+            while let Some(frame) = with_timeout(read_timeout, body.frame())
+                .await
+                .ok_or_else(|| timed_out("reading the response"))?
+            {
+                if let Ok(data) = frame.map_err(SdkError::transport)?.into_data() {
+                    bytes.extend_from_slice(&data);
+                }
+            }
+
+            Ok(http::Response::from_parts(parts, bytes))
+        };
+        futures_util::pin_mut!(exchange);
+
+        match future::select(exchange, self.connection.as_mut()).await {
+            Either::Left((result, _)) => result,
+            // The connection was closed, so the exchange either got all it needs or fails.
+            Either::Right((_, exchange)) => exchange.await,
+        }
+    }
+}
+
+fn origin(url: &Url) -> Result<Origin, SdkError> {
+    let host = url
+        .host_str()
+        .ok_or_else(|| SdkError::transport(format!("URL without host: {}", url)))?;
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| SdkError::transport(format!("URL without port: {}", url)))?;
+
+    Ok((url.scheme().to_string(), host.to_string(), port))
+}
+
+// Waits for the future, or returns `None` once the duration has passed.
+async fn with_timeout<F: Future>(duration: Option<Duration>, future: F) -> Option<F::Output> {
+    match duration {
+        Some(duration) => {
+            let sleep = timer::sleep(duration);
+            futures_util::pin_mut!(future, sleep);
+
+            match future::select(future, sleep).await {
+                Either::Left((output, _)) => Some(output),
+                Either::Right(_) => None,
+            }
+        }
+        None => Some(future.await),
+    }
+}
+
+fn timed_out(stage: &str) -> SdkError {
+    SdkError::timeout(io::Error::new(
+        io::ErrorKind::TimedOut,
+        format!("timed out {}", stage),
+    ))
+}
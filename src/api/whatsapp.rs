@@ -1,18 +1,21 @@
 //! Module with client and endpoint functions for the WhatsApp channel.
 
-use std::collections::HashMap;
+use std::sync::Arc;
 
-use reqwest::{Method, Response};
+use serde::de::DeserializeOwned;
 use serde::Serialize;
 use validator::Validate;
 
-use crate::api::{
-    build_api_error, send_no_body_request, send_valid_json_request, SdkError, SdkResponse,
-};
-use crate::configuration::Configuration;
+use crate::api::endpoint::Endpoint;
+use crate::api::idempotency::{self, generate_id};
+use crate::api::raw::{Raw, RawRequest};
+use crate::api::transport::{default_transport, Transport};
+use crate::api::whatsapp::endpoints::*;
+use crate::api::{execute_endpoint, SdkError, SdkResponse};
+use crate::configuration::{Configuration, RetryPolicy};
 use crate::model::whatsapp::{
     CreateTemplateRequestBody, CreateTemplateResponseBody, SendAudioRequestBody,
-    SendAudioResponseBody, SendContactRequestBody, SendContactResponseBody,
+    SendAudioResponseBody, SendContactRequestBody, SendContactResponseBody, SendContentRequestBody,
     SendDocumentRequestBody, SendDocumentResponseBody, SendImageRequestBody, SendImageResponseBody,
     SendInteractiveButtonsRequestBody, SendInteractiveButtonsResponseBody,
     SendInteractiveListRequestBody, SendInteractiveListResponseBody,
@@ -23,6 +26,14 @@ use crate::model::whatsapp::{
     SendVideoRequestBody, SendVideoResponseBody, TemplatesResponseBody,
 };
 
+#[cfg(tokio)]
+use tokio::runtime::Runtime;
+
+#[cfg(tokio)]
+use crate::api::blocking_runtime;
+
+pub mod endpoints;
+
 pub const PATH_CREATE_TEMPLATE: &str = "/whatsapp/2/senders/{sender}/templates";
 pub const PATH_DELETE_TEMPLATE: &str = "/whatsapp/2/senders/{sender}/templates/{templateName}";
 pub const PATH_GET_TEMPLATES: &str = "/whatsapp/2/senders/{sender}/templates";
@@ -41,36 +52,192 @@ pub const PATH_SEND_TEMPLATE: &str = "/whatsapp/1/message/template";
 pub const PATH_SEND_TEXT: &str = "/whatsapp/1/message/text";
 pub const PATH_SEND_VIDEO: &str = "/whatsapp/1/message/video";
 
+// Request bodies of messages that get their IDs filled in by idempotent sends.
+trait MessageIds {
+    // Fills in the IDs that are missing.
+    fn fill_ids(&mut self);
+}
+
+impl<T: Serialize + Validate> MessageIds for SendContentRequestBody<T> {
+    fn fill_ids(&mut self) {
+        if self.message_id.is_none() {
+            self.message_id = Some(generate_id());
+        }
+    }
+}
+
+impl MessageIds for SendTemplateRequestBody {
+    fn fill_ids(&mut self) {
+        let bulk_id = match &self.bulk_id {
+            Some(bulk_id) => bulk_id.clone(),
+            None => generate_id(),
+        };
+
+        for (index, message) in self.messages.iter_mut().enumerate() {
+            message
+                .message_id
+                .get_or_insert_with(|| idempotency::message_id(&bulk_id, index));
+        }
+        self.bulk_id = Some(bulk_id);
+    }
+}
+
 /// Main asynchronous client for the Infobip WhatsApp channel.
 #[derive(Clone, Debug)]
 pub struct WhatsAppClient {
     pub configuration: Configuration,
-    pub http_client: reqwest::Client,
+    pub transport: Arc<dyn Transport>,
 }
 
 impl WhatsAppClient {
     /// Builds and returns a new asynchronous `WhatsAppClient` with a specified configuration.
     pub fn with_configuration(configuration: Configuration) -> Self {
+        let transport = default_transport(&configuration);
+        WhatsAppClient::with_transport(configuration, transport)
+    }
+
+    /// Builds and returns a new asynchronous `WhatsAppClient` with a specified configuration,
+    /// sending requests through a custom transport.
+    pub fn with_transport(configuration: Configuration, transport: Arc<dyn Transport>) -> Self {
         WhatsAppClient {
             configuration,
-            http_client: reqwest::Client::new(),
+            transport,
         }
     }
 
-    async fn send_request<T: Validate + Serialize>(
-        &self,
-        request_body: T,
-        parameters: HashMap<String, String>,
-        method: Method,
-        path: &str,
-    ) -> Result<Response, SdkError> {
-        send_valid_json_request(
-            &self.http_client,
-            &self.configuration,
-            request_body,
-            parameters,
-            method,
-            path,
+    /// Returns a copy of this client that sends every request exactly once, ignoring the retry
+    /// policy of its configuration. Use it for calls that must not be repeated.
+    pub fn without_retries(&self) -> Self {
+        let mut client = self.clone();
+        client
+            .configuration
+            .set_retry_policy(RetryPolicy::disabled());
+
+        client
+    }
+
+    /// Returns a copy of this client that sends the given correlation ID in the
+    /// `X-Correlation-Id` header of every request, e.g. to match a call with your own logs.
+    pub fn with_correlation_id(&self, correlation_id: impl Into<String>) -> Self {
+        let mut client = self.clone();
+        client.configuration.set_correlation_id(correlation_id);
+
+        client
+    }
+
+    /// Returns a copy of this client with idempotent sends enabled. See
+    /// `Configuration::set_idempotent_sends()`.
+    pub fn with_idempotent_sends(&self) -> Self {
+        let mut client = self.clone();
+        client.configuration.set_idempotent_sends(true);
+
+        client
+    }
+
+    /// Sends a request to an endpoint, which can be one of `api::whatsapp::endpoints` or one
+    /// defined in your own code. See `api::endpoint`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use infobip_sdk::api::whatsapp::endpoints::GetTemplates;
+    /// # use infobip_sdk::api::whatsapp::WhatsAppClient;
+    /// # use infobip_sdk::configuration::Configuration;
+    /// # use reqwest::StatusCode;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let wa_client = WhatsAppClient::with_configuration(Configuration::from_env_api_key()?);
+    ///
+    /// let response = wa_client.execute(GetTemplates::new("44444444444")).await?;
+    ///
+    /// assert_eq!(response.status, StatusCode::OK);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "WhatsAppClient::execute",
+            skip_all,
+            fields(
+                path = endpoint.path_template(),
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
+    pub async fn execute<E: Endpoint>(
+        &self,
+        endpoint: E,
+    ) -> Result<SdkResponse<E::Response>, SdkError> {
+        self.request(endpoint).await
+    }
+
+    /// Sends a request to any path of the API, for endpoints the SDK does not model. The response
+    /// body is deserialized into `T`, e.g. `serde_json::Value`. See `api::raw`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use infobip_sdk::api::raw::RawRequest;
+    /// # use infobip_sdk::api::whatsapp::WhatsAppClient;
+    /// # use infobip_sdk::configuration::Configuration;
+    /// # use serde_json::Value;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let wa_client = WhatsAppClient::with_configuration(Configuration::from_env_api_key()?);
+    ///
+    /// let response = wa_client
+    ///     .raw::<Value>(RawRequest::get("/whatsapp/1/senders").query("limit", 10))
+    ///     .await?;
+    ///
+    /// println!("{}", response.body);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "WhatsAppClient::raw",
+            skip_all,
+            fields(
+                path = request.path(),
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
+    pub async fn raw<T: DeserializeOwned>(
+        &self,
+        request: RawRequest,
+    ) -> Result<SdkResponse<T>, SdkError> {
+        self.request(Raw::new(request)).await
+    }
+
+    // Sends a request to an endpoint, within the span of the calling method.
+    async fn request<E: Endpoint>(
+        &self,
+        endpoint: E,
+    ) -> Result<SdkResponse<E::Response>, SdkError> {
+        execute_endpoint(self.transport.as_ref(), &self.configuration, &endpoint).await
+    }
+
+    // Sends a message. With idempotent sends, its IDs are filled in, and failures without a clear
+    // outcome are returned as they are, since there are no logs to check before sending again.
+    async fn send_message<T: MessageIds, E: Endpoint>(
+        &self,
+        mut request_body: T,
+        endpoint: fn(T) -> E,
+    ) -> Result<SdkResponse<E::Response>, SdkError> {
+        if !self.configuration.idempotent_sends() {
+            return self.request(endpoint(request_body)).await;
+        }
+
+        request_body.fill_ids();
+        execute_endpoint(
+            self.transport.as_ref(),
+            &idempotency::without_ambiguous_retries(&self.configuration),
+            &endpoint(request_body),
         )
         .await
     }
@@ -102,25 +269,22 @@ impl WhatsAppClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "WhatsAppClient::send_text",
+            skip_all,
+            fields(
+                path = PATH_SEND_TEXT,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn send_text(
         &self,
         request_body: SendTextRequestBody,
     ) -> Result<SdkResponse<SendTextResponseBody>, SdkError> {
-        let response = self
-            .send_request(request_body, HashMap::new(), Method::POST, PATH_SEND_TEXT)
-            .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.send_message(request_body, SendText::new).await
     }
 
     /// Send a document to a single recipient. Document messages can only be successfully delivered
@@ -150,30 +314,22 @@ impl WhatsAppClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "WhatsAppClient::send_document",
+            skip_all,
+            fields(
+                path = PATH_SEND_DOCUMENT,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn send_document(
         &self,
         request_body: SendDocumentRequestBody,
     ) -> Result<SdkResponse<SendDocumentResponseBody>, SdkError> {
-        let response = self
-            .send_request(
-                request_body,
-                HashMap::new(),
-                Method::POST,
-                PATH_SEND_DOCUMENT,
-            )
-            .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.send_message(request_body, SendDocument::new).await
     }
 
     /// Send an image to a single recipient. Image messages can only be successfully delivered if
@@ -203,25 +359,22 @@ impl WhatsAppClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "WhatsAppClient::send_image",
+            skip_all,
+            fields(
+                path = PATH_SEND_IMAGE,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn send_image(
         &self,
         request_body: SendImageRequestBody,
     ) -> Result<SdkResponse<SendImageResponseBody>, SdkError> {
-        let response = self
-            .send_request(request_body, HashMap::new(), Method::POST, PATH_SEND_IMAGE)
-            .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.send_message(request_body, SendImage::new).await
     }
 
     /// Send an audio to a single recipient. Audio messages can only be successfully delivered if
@@ -251,25 +404,22 @@ impl WhatsAppClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "WhatsAppClient::send_audio",
+            skip_all,
+            fields(
+                path = PATH_SEND_AUDIO,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn send_audio(
         &self,
         request_body: SendAudioRequestBody,
     ) -> Result<SdkResponse<SendAudioResponseBody>, SdkError> {
-        let response = self
-            .send_request(request_body, HashMap::new(), Method::POST, PATH_SEND_AUDIO)
-            .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.send_message(request_body, SendAudio::new).await
     }
 
     /// Send a video to a single recipient. Video messages can only be successfully delivered if
@@ -299,25 +449,22 @@ impl WhatsAppClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "WhatsAppClient::send_video",
+            skip_all,
+            fields(
+                path = PATH_SEND_VIDEO,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn send_video(
         &self,
         request_body: SendVideoRequestBody,
     ) -> Result<SdkResponse<SendVideoResponseBody>, SdkError> {
-        let response = self
-            .send_request(request_body, HashMap::new(), Method::POST, PATH_SEND_VIDEO)
-            .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.send_message(request_body, SendVideo::new).await
     }
 
     /// Send a sticker to a single recipient. Sticker messages can only be successfully delivered
@@ -347,30 +494,22 @@ impl WhatsAppClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "WhatsAppClient::send_sticker",
+            skip_all,
+            fields(
+                path = PATH_SEND_STICKER,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn send_sticker(
         &self,
         request_body: SendStickerRequestBody,
     ) -> Result<SdkResponse<SendStickerResponseBody>, SdkError> {
-        let response = self
-            .send_request(
-                request_body,
-                HashMap::new(),
-                Method::POST,
-                PATH_SEND_STICKER,
-            )
-            .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.send_message(request_body, SendSticker::new).await
     }
 
     /// Send a location to a single recipient. Location messages can only be successfully
@@ -400,30 +539,22 @@ impl WhatsAppClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "WhatsAppClient::send_location",
+            skip_all,
+            fields(
+                path = PATH_SEND_LOCATION,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn send_location(
         &self,
         request_body: SendLocationRequestBody,
     ) -> Result<SdkResponse<SendLocationResponseBody>, SdkError> {
-        let response = self
-            .send_request(
-                request_body,
-                HashMap::new(),
-                Method::POST,
-                PATH_SEND_LOCATION,
-            )
-            .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.send_message(request_body, SendLocation::new).await
     }
 
     /// Send a contact to a single recipient. Contact messages can only be successfully delivered
@@ -454,30 +585,22 @@ impl WhatsAppClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "WhatsAppClient::send_contact",
+            skip_all,
+            fields(
+                path = PATH_SEND_CONTACT,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn send_contact(
         &self,
         request_body: SendContactRequestBody,
     ) -> Result<SdkResponse<SendContactResponseBody>, SdkError> {
-        let response = self
-            .send_request(
-                request_body,
-                HashMap::new(),
-                Method::POST,
-                PATH_SEND_CONTACT,
-            )
-            .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.send_message(request_body, SendContact::new).await
     }
 
     /// Send an interactive buttons message to a single recipient. Interactive buttons messages
@@ -516,29 +639,23 @@ impl WhatsAppClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "WhatsAppClient::send_interactive_buttons",
+            skip_all,
+            fields(
+                path = PATH_SEND_INTERACTIVE_BUTTONS,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn send_interactive_buttons(
         &self,
         request_body: SendInteractiveButtonsRequestBody,
     ) -> Result<SdkResponse<SendInteractiveButtonsResponseBody>, SdkError> {
-        let response = self
-            .send_request(
-                request_body,
-                HashMap::new(),
-                Method::POST,
-                PATH_SEND_INTERACTIVE_BUTTONS,
-            )
-            .await?;
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.send_message(request_body, SendInteractiveButtons::new)
+            .await
     }
 
     /// Send an interactive list message to a single recipient. Interactive list messages can only
@@ -579,29 +696,23 @@ impl WhatsAppClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "WhatsAppClient::send_interactive_list",
+            skip_all,
+            fields(
+                path = PATH_SEND_INTERACTIVE_LIST,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn send_interactive_list(
         &self,
         request_body: SendInteractiveListRequestBody,
     ) -> Result<SdkResponse<SendInteractiveListResponseBody>, SdkError> {
-        let response = self
-            .send_request(
-                request_body,
-                HashMap::new(),
-                Method::POST,
-                PATH_SEND_INTERACTIVE_LIST,
-            )
-            .await?;
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.send_message(request_body, SendInteractiveList::new)
+            .await
     }
 
     /// Send an interactive product message to a single recipient. Interactive product messages
@@ -634,29 +745,23 @@ impl WhatsAppClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "WhatsAppClient::send_interactive_product",
+            skip_all,
+            fields(
+                path = PATH_SEND_INTERACTIVE_PRODUCT,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn send_interactive_product(
         &self,
         request_body: SendInteractiveProductRequestBody,
     ) -> Result<SdkResponse<SendInteractiveProductResponseBody>, SdkError> {
-        let response = self
-            .send_request(
-                request_body,
-                HashMap::new(),
-                Method::POST,
-                PATH_SEND_INTERACTIVE_PRODUCT,
-            )
-            .await?;
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.send_message(request_body, SendInteractiveProduct::new)
+            .await
     }
 
     /// Send an interactive multi-product message to a single recipient. Interactive multi-product
@@ -697,29 +802,23 @@ impl WhatsAppClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "WhatsAppClient::send_interactive_multiproduct",
+            skip_all,
+            fields(
+                path = PATH_SEND_INTERACTIVE_MULTIPRODUCT,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn send_interactive_multiproduct(
         &self,
         request_body: SendInteractiveMultiproductRequestBody,
     ) -> Result<SdkResponse<SendInteractiveMultiproductResponseBody>, SdkError> {
-        let response = self
-            .send_request(
-                request_body,
-                HashMap::new(),
-                Method::POST,
-                PATH_SEND_INTERACTIVE_MULTIPRODUCT,
-            )
-            .await?;
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.send_message(request_body, SendInteractiveMultiproduct::new)
+            .await
     }
 
     /// Create a WhatsApp template. Created template will be submitted for WhatsApp's review and
@@ -760,27 +859,24 @@ impl WhatsAppClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "WhatsAppClient::create_template",
+            skip_all,
+            fields(
+                path = PATH_CREATE_TEMPLATE,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn create_template(
         &self,
         sender: &str,
         request_body: CreateTemplateRequestBody,
     ) -> Result<SdkResponse<CreateTemplateResponseBody>, SdkError> {
-        let path = PATH_CREATE_TEMPLATE.replace("{sender}", sender);
-
-        let response = self
-            .send_request(request_body, HashMap::new(), Method::POST, path.as_str())
-            .await?;
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(CreateTemplate::new(sender, request_body))
+            .await
     }
 
     ///  all the templates and their statuses for a given sender.
@@ -801,32 +897,22 @@ impl WhatsAppClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "WhatsAppClient::templates",
+            skip_all,
+            fields(
+                path = PATH_GET_TEMPLATES,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn templates(
         &self,
         sender: &str,
     ) -> Result<SdkResponse<TemplatesResponseBody>, SdkError> {
-        let path = PATH_GET_TEMPLATES.replace("{sender}", sender);
-
-        let response = send_no_body_request(
-            &self.http_client,
-            &self.configuration,
-            HashMap::new(),
-            Method::GET,
-            path.as_str(),
-        )
-        .await?;
-
-        let status = response.status();
-        let text = response.text().await?;
-
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
-        }
+        self.request(GetTemplates::new(sender)).await
     }
 
     /// Delete a WhatsApp template.
@@ -859,32 +945,27 @@ impl WhatsAppClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "WhatsAppClient::delete_template",
+            skip_all,
+            fields(
+                path = PATH_DELETE_TEMPLATE,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn delete_template(
         &self,
         sender: &str,
         template_name: &str,
     ) -> Result<reqwest::StatusCode, SdkError> {
-        let path = PATH_DELETE_TEMPLATE
-            .replace("{sender}", sender)
-            .replace("{templateName}", template_name);
-
-        let response = send_no_body_request(
-            &self.http_client,
-            &self.configuration,
-            HashMap::new(),
-            Method::DELETE,
-            path.as_str(),
-        )
-        .await?;
-
-        let status = response.status();
+        let response = self
+            .request(DeleteTemplate::new(sender, template_name))
+            .await?;
 
-        if status.is_success() {
-            Ok(status)
-        } else {
-            let text = response.text().await?;
-            Err(build_api_error(status, &text))
-        }
+        Ok(response.status)
     }
 
     /// Send a single or multiple template messages to one or more recipients. Template messages
@@ -926,29 +1007,234 @@ impl WhatsAppClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "WhatsAppClient::send_template",
+            skip_all,
+            fields(
+                path = PATH_SEND_TEMPLATE,
+                method, correlation_id, status, latency_ms, retries, bulk_id, message_ids
+            )
+        )
+    )]
     pub async fn send_template(
         &self,
         request_body: SendTemplateRequestBody,
     ) -> Result<SdkResponse<SendTemplateResponseBody>, SdkError> {
-        let response = self
-            .send_request(
-                request_body,
-                HashMap::new(),
-                Method::POST,
-                PATH_SEND_TEMPLATE,
-            )
-            .await?;
+        self.send_message(request_body, SendTemplate::new).await
+    }
+}
+
+/// Blocking client for the Infobip WhatsApp channel.
+///
+/// Calls the asynchronous `WhatsAppClient` on a runtime owned by the client, so both share request
+/// building and response handling.
+#[cfg(tokio)]
+#[derive(Clone, Debug)]
+pub struct BlockingWhatsAppClient {
+    client: WhatsAppClient,
+    runtime: Arc<Runtime>,
+}
+
+#[cfg(tokio)]
+impl BlockingWhatsAppClient {
+    /// Builds and returns a new `BlockingWhatsAppClient` with a specified configuration.
+    pub fn with_configuration(configuration: Configuration) -> BlockingWhatsAppClient {
+        let transport = default_transport(&configuration);
+        BlockingWhatsAppClient::with_transport(configuration, transport)
+    }
 
-        let status = response.status();
-        let text = response.text().await?;
+    /// Builds and returns a new `BlockingWhatsAppClient` with a specified configuration and a custom
+    /// transport to send requests.
+    ///
+    /// # Panics
+    /// Panics if the runtime used to drive requests can not be created.
+    pub fn with_transport(
+        configuration: Configuration,
+        transport: Arc<dyn Transport>,
+    ) -> BlockingWhatsAppClient {
+        BlockingWhatsAppClient {
+            client: WhatsAppClient::with_transport(configuration, transport),
+            runtime: blocking_runtime(),
+        }
+    }
 
-        if status.is_success() {
-            Ok(SdkResponse {
-                body: serde_json::from_str(&text)?,
-                status,
-            })
-        } else {
-            Err(build_api_error(status, &text))
+    /// Returns a copy of this client that sends every request exactly once, ignoring the retry
+    /// policy of its configuration. Use it for calls that must not be repeated.
+    pub fn without_retries(&self) -> Self {
+        BlockingWhatsAppClient {
+            client: self.client.without_retries(),
+            runtime: self.runtime.clone(),
         }
     }
+
+    /// Returns a copy of this client that sends the given correlation ID in the
+    /// `X-Correlation-Id` header of every request, e.g. to match a call with your own logs.
+    pub fn with_correlation_id(&self, correlation_id: impl Into<String>) -> Self {
+        BlockingWhatsAppClient {
+            client: self.client.with_correlation_id(correlation_id),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// Returns a copy of this client with idempotent sends enabled. See
+    /// `Configuration::set_idempotent_sends()`.
+    pub fn with_idempotent_sends(&self) -> Self {
+        BlockingWhatsAppClient {
+            client: self.client.with_idempotent_sends(),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// Blocking version of `WhatsAppClient::execute()`.
+    pub fn execute<E: Endpoint>(&self, endpoint: E) -> Result<SdkResponse<E::Response>, SdkError> {
+        self.runtime.block_on(self.client.execute(endpoint))
+    }
+
+    /// Blocking version of `WhatsAppClient::raw()`.
+    pub fn raw<T: DeserializeOwned>(
+        &self,
+        request: RawRequest,
+    ) -> Result<SdkResponse<T>, SdkError> {
+        self.runtime.block_on(self.client.raw(request))
+    }
+
+    /// Blocking version of `WhatsAppClient::send_text()`.
+    pub fn send_text(
+        &self,
+        request_body: SendTextRequestBody,
+    ) -> Result<SdkResponse<SendTextResponseBody>, SdkError> {
+        self.runtime.block_on(self.client.send_text(request_body))
+    }
+
+    /// Blocking version of `WhatsAppClient::send_document()`.
+    pub fn send_document(
+        &self,
+        request_body: SendDocumentRequestBody,
+    ) -> Result<SdkResponse<SendDocumentResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.send_document(request_body))
+    }
+
+    /// Blocking version of `WhatsAppClient::send_image()`.
+    pub fn send_image(
+        &self,
+        request_body: SendImageRequestBody,
+    ) -> Result<SdkResponse<SendImageResponseBody>, SdkError> {
+        self.runtime.block_on(self.client.send_image(request_body))
+    }
+
+    /// Blocking version of `WhatsAppClient::send_audio()`.
+    pub fn send_audio(
+        &self,
+        request_body: SendAudioRequestBody,
+    ) -> Result<SdkResponse<SendAudioResponseBody>, SdkError> {
+        self.runtime.block_on(self.client.send_audio(request_body))
+    }
+
+    /// Blocking version of `WhatsAppClient::send_video()`.
+    pub fn send_video(
+        &self,
+        request_body: SendVideoRequestBody,
+    ) -> Result<SdkResponse<SendVideoResponseBody>, SdkError> {
+        self.runtime.block_on(self.client.send_video(request_body))
+    }
+
+    /// Blocking version of `WhatsAppClient::send_sticker()`.
+    pub fn send_sticker(
+        &self,
+        request_body: SendStickerRequestBody,
+    ) -> Result<SdkResponse<SendStickerResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.send_sticker(request_body))
+    }
+
+    /// Blocking version of `WhatsAppClient::send_location()`.
+    pub fn send_location(
+        &self,
+        request_body: SendLocationRequestBody,
+    ) -> Result<SdkResponse<SendLocationResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.send_location(request_body))
+    }
+
+    /// Blocking version of `WhatsAppClient::send_contact()`.
+    pub fn send_contact(
+        &self,
+        request_body: SendContactRequestBody,
+    ) -> Result<SdkResponse<SendContactResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.send_contact(request_body))
+    }
+
+    /// Blocking version of `WhatsAppClient::send_interactive_buttons()`.
+    pub fn send_interactive_buttons(
+        &self,
+        request_body: SendInteractiveButtonsRequestBody,
+    ) -> Result<SdkResponse<SendInteractiveButtonsResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.send_interactive_buttons(request_body))
+    }
+
+    /// Blocking version of `WhatsAppClient::send_interactive_list()`.
+    pub fn send_interactive_list(
+        &self,
+        request_body: SendInteractiveListRequestBody,
+    ) -> Result<SdkResponse<SendInteractiveListResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.send_interactive_list(request_body))
+    }
+
+    /// Blocking version of `WhatsAppClient::send_interactive_product()`.
+    pub fn send_interactive_product(
+        &self,
+        request_body: SendInteractiveProductRequestBody,
+    ) -> Result<SdkResponse<SendInteractiveProductResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.send_interactive_product(request_body))
+    }
+
+    /// Blocking version of `WhatsAppClient::send_interactive_multiproduct()`.
+    pub fn send_interactive_multiproduct(
+        &self,
+        request_body: SendInteractiveMultiproductRequestBody,
+    ) -> Result<SdkResponse<SendInteractiveMultiproductResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.send_interactive_multiproduct(request_body))
+    }
+
+    /// Blocking version of `WhatsAppClient::create_template()`.
+    pub fn create_template(
+        &self,
+        sender: &str,
+        request_body: CreateTemplateRequestBody,
+    ) -> Result<SdkResponse<CreateTemplateResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.create_template(sender, request_body))
+    }
+
+    /// Blocking version of `WhatsAppClient::templates()`.
+    pub fn templates(&self, sender: &str) -> Result<SdkResponse<TemplatesResponseBody>, SdkError> {
+        self.runtime.block_on(self.client.templates(sender))
+    }
+
+    /// Blocking version of `WhatsAppClient::delete_template()`.
+    pub fn delete_template(
+        &self,
+        sender: &str,
+        template_name: &str,
+    ) -> Result<reqwest::StatusCode, SdkError> {
+        self.runtime
+            .block_on(self.client.delete_template(sender, template_name))
+    }
+
+    /// Blocking version of `WhatsAppClient::send_template()`.
+    pub fn send_template(
+        &self,
+        request_body: SendTemplateRequestBody,
+    ) -> Result<SdkResponse<SendTemplateResponseBody>, SdkError> {
+        self.runtime
+            .block_on(self.client.send_template(request_body))
+    }
 }
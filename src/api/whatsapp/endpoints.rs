@@ -0,0 +1,602 @@
+//! Endpoints of the WhatsApp channel, which `WhatsAppClient` calls. Each one can also be passed
+//! to `WhatsAppClient::execute()`.
+
+use reqwest::Method;
+use serde::de::IgnoredAny;
+use validator::Validate;
+
+use crate::api::endpoint::{Endpoint, Json, NoBody};
+use crate::api::whatsapp::*;
+use crate::api::SdkError;
+use crate::model::whatsapp::{
+    CreateTemplateRequestBody, CreateTemplateResponseBody, SendAudioRequestBody,
+    SendAudioResponseBody, SendContactRequestBody, SendContactResponseBody,
+    SendDocumentRequestBody, SendDocumentResponseBody, SendImageRequestBody, SendImageResponseBody,
+    SendInteractiveButtonsRequestBody, SendInteractiveButtonsResponseBody,
+    SendInteractiveListRequestBody, SendInteractiveListResponseBody,
+    SendInteractiveMultiproductRequestBody, SendInteractiveMultiproductResponseBody,
+    SendInteractiveProductRequestBody, SendInteractiveProductResponseBody, SendLocationRequestBody,
+    SendLocationResponseBody, SendStickerRequestBody, SendStickerResponseBody,
+    SendTemplateRequestBody, SendTemplateResponseBody, SendTextRequestBody, SendTextResponseBody,
+    SendVideoRequestBody, SendVideoResponseBody, TemplatesResponseBody,
+};
+
+/// Sends a text message. See `WhatsAppClient::send_text()`.
+#[derive(Clone, Debug)]
+pub struct SendText {
+    body: Json<SendTextRequestBody>,
+}
+
+impl SendText {
+    pub fn new(request_body: SendTextRequestBody) -> Self {
+        SendText {
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for SendText {
+    type Query = ();
+    type Body = Json<SendTextRequestBody>;
+    type Response = SendTextResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_SEND_TEXT
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Sends a document. See `WhatsAppClient::send_document()`.
+#[derive(Clone, Debug)]
+pub struct SendDocument {
+    body: Json<SendDocumentRequestBody>,
+}
+
+impl SendDocument {
+    pub fn new(request_body: SendDocumentRequestBody) -> Self {
+        SendDocument {
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for SendDocument {
+    type Query = ();
+    type Body = Json<SendDocumentRequestBody>;
+    type Response = SendDocumentResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_SEND_DOCUMENT
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Sends an image. See `WhatsAppClient::send_image()`.
+#[derive(Clone, Debug)]
+pub struct SendImage {
+    body: Json<SendImageRequestBody>,
+}
+
+impl SendImage {
+    pub fn new(request_body: SendImageRequestBody) -> Self {
+        SendImage {
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for SendImage {
+    type Query = ();
+    type Body = Json<SendImageRequestBody>;
+    type Response = SendImageResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_SEND_IMAGE
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Sends an audio message. See `WhatsAppClient::send_audio()`.
+#[derive(Clone, Debug)]
+pub struct SendAudio {
+    body: Json<SendAudioRequestBody>,
+}
+
+impl SendAudio {
+    pub fn new(request_body: SendAudioRequestBody) -> Self {
+        SendAudio {
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for SendAudio {
+    type Query = ();
+    type Body = Json<SendAudioRequestBody>;
+    type Response = SendAudioResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_SEND_AUDIO
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Sends a video. See `WhatsAppClient::send_video()`.
+#[derive(Clone, Debug)]
+pub struct SendVideo {
+    body: Json<SendVideoRequestBody>,
+}
+
+impl SendVideo {
+    pub fn new(request_body: SendVideoRequestBody) -> Self {
+        SendVideo {
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for SendVideo {
+    type Query = ();
+    type Body = Json<SendVideoRequestBody>;
+    type Response = SendVideoResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_SEND_VIDEO
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Sends a sticker. See `WhatsAppClient::send_sticker()`.
+#[derive(Clone, Debug)]
+pub struct SendSticker {
+    body: Json<SendStickerRequestBody>,
+}
+
+impl SendSticker {
+    pub fn new(request_body: SendStickerRequestBody) -> Self {
+        SendSticker {
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for SendSticker {
+    type Query = ();
+    type Body = Json<SendStickerRequestBody>;
+    type Response = SendStickerResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_SEND_STICKER
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Sends a location. See `WhatsAppClient::send_location()`.
+#[derive(Clone, Debug)]
+pub struct SendLocation {
+    body: Json<SendLocationRequestBody>,
+}
+
+impl SendLocation {
+    pub fn new(request_body: SendLocationRequestBody) -> Self {
+        SendLocation {
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for SendLocation {
+    type Query = ();
+    type Body = Json<SendLocationRequestBody>;
+    type Response = SendLocationResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_SEND_LOCATION
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Sends a contact. See `WhatsAppClient::send_contact()`.
+#[derive(Clone, Debug)]
+pub struct SendContact {
+    body: Json<SendContactRequestBody>,
+}
+
+impl SendContact {
+    pub fn new(request_body: SendContactRequestBody) -> Self {
+        SendContact {
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for SendContact {
+    type Query = ();
+    type Body = Json<SendContactRequestBody>;
+    type Response = SendContactResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_SEND_CONTACT
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Sends a message with buttons. See `WhatsAppClient::send_interactive_buttons()`.
+#[derive(Clone, Debug)]
+pub struct SendInteractiveButtons {
+    body: Json<SendInteractiveButtonsRequestBody>,
+}
+
+impl SendInteractiveButtons {
+    pub fn new(request_body: SendInteractiveButtonsRequestBody) -> Self {
+        SendInteractiveButtons {
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for SendInteractiveButtons {
+    type Query = ();
+    type Body = Json<SendInteractiveButtonsRequestBody>;
+    type Response = SendInteractiveButtonsResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_SEND_INTERACTIVE_BUTTONS
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Sends a message with a list. See `WhatsAppClient::send_interactive_list()`.
+#[derive(Clone, Debug)]
+pub struct SendInteractiveList {
+    body: Json<SendInteractiveListRequestBody>,
+}
+
+impl SendInteractiveList {
+    pub fn new(request_body: SendInteractiveListRequestBody) -> Self {
+        SendInteractiveList {
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for SendInteractiveList {
+    type Query = ();
+    type Body = Json<SendInteractiveListRequestBody>;
+    type Response = SendInteractiveListResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_SEND_INTERACTIVE_LIST
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Sends a product message. See `WhatsAppClient::send_interactive_product()`.
+#[derive(Clone, Debug)]
+pub struct SendInteractiveProduct {
+    body: Json<SendInteractiveProductRequestBody>,
+}
+
+impl SendInteractiveProduct {
+    pub fn new(request_body: SendInteractiveProductRequestBody) -> Self {
+        SendInteractiveProduct {
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for SendInteractiveProduct {
+    type Query = ();
+    type Body = Json<SendInteractiveProductRequestBody>;
+    type Response = SendInteractiveProductResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_SEND_INTERACTIVE_PRODUCT
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Sends a multi-product message. See `WhatsAppClient::send_interactive_multiproduct()`.
+#[derive(Clone, Debug)]
+pub struct SendInteractiveMultiproduct {
+    body: Json<SendInteractiveMultiproductRequestBody>,
+}
+
+impl SendInteractiveMultiproduct {
+    pub fn new(request_body: SendInteractiveMultiproductRequestBody) -> Self {
+        SendInteractiveMultiproduct {
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for SendInteractiveMultiproduct {
+    type Query = ();
+    type Body = Json<SendInteractiveMultiproductRequestBody>;
+    type Response = SendInteractiveMultiproductResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_SEND_INTERACTIVE_MULTIPRODUCT
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Sends a template message to one or more recipients. See `WhatsAppClient::send_template()`.
+#[derive(Clone, Debug)]
+pub struct SendTemplate {
+    body: Json<SendTemplateRequestBody>,
+}
+
+impl SendTemplate {
+    pub fn new(request_body: SendTemplateRequestBody) -> Self {
+        SendTemplate {
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for SendTemplate {
+    type Query = ();
+    type Body = Json<SendTemplateRequestBody>;
+    type Response = SendTemplateResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_SEND_TEMPLATE
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Creates a message template for a sender. See `WhatsAppClient::create_template()`.
+#[derive(Clone, Debug)]
+pub struct CreateTemplate {
+    sender: String,
+    body: Json<CreateTemplateRequestBody>,
+}
+
+impl CreateTemplate {
+    pub fn new(sender: &str, request_body: CreateTemplateRequestBody) -> Self {
+        CreateTemplate {
+            sender: sender.to_string(),
+            body: Json(request_body),
+        }
+    }
+}
+
+impl Endpoint for CreateTemplate {
+    type Query = ();
+    type Body = Json<CreateTemplateRequestBody>;
+    type Response = CreateTemplateResponseBody;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_CREATE_TEMPLATE
+    }
+
+    fn path_params(&self) -> Vec<(&str, &str)> {
+        vec![("sender", &self.sender)]
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        Ok(self.body.validate()?)
+    }
+}
+
+/// Gets the message templates of a sender. See `WhatsAppClient::templates()`.
+#[derive(Clone, Debug)]
+pub struct GetTemplates {
+    sender: String,
+}
+
+impl GetTemplates {
+    pub fn new(sender: &str) -> Self {
+        GetTemplates {
+            sender: sender.to_string(),
+        }
+    }
+}
+
+impl Endpoint for GetTemplates {
+    type Query = ();
+    type Body = NoBody;
+    type Response = TemplatesResponseBody;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_GET_TEMPLATES
+    }
+
+    fn path_params(&self) -> Vec<(&str, &str)> {
+        vec![("sender", &self.sender)]
+    }
+}
+
+/// Deletes a message template of a sender, in all its languages. See
+/// `WhatsAppClient::delete_template()`.
+#[derive(Clone, Debug)]
+pub struct DeleteTemplate {
+    sender: String,
+    template_name: String,
+}
+
+impl DeleteTemplate {
+    pub fn new(sender: &str, template_name: &str) -> Self {
+        DeleteTemplate {
+            sender: sender.to_string(),
+            template_name: template_name.to_string(),
+        }
+    }
+}
+
+impl Endpoint for DeleteTemplate {
+    type Query = ();
+    type Body = NoBody;
+    type Response = IgnoredAny;
+
+    fn method(&self) -> Method {
+        Method::DELETE
+    }
+
+    fn path_template(&self) -> &str {
+        PATH_DELETE_TEMPLATE
+    }
+
+    fn path_params(&self) -> Vec<(&str, &str)> {
+        vec![
+            ("sender", &self.sender),
+            ("templateName", &self.template_name),
+        ]
+    }
+}
@@ -0,0 +1,207 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::Url;
+
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::Proxy;
+
+use crate::api::auth::TokenProvider;
+use crate::api::middleware::Middleware;
+use crate::api::transport::DefaultTransport;
+use crate::configuration::{
+    ApiKey, BasicAuth, Configuration, ConfigurationError, RateLimiter, RetryPolicy, SecretString,
+};
+
+/// Builds a `Configuration` with any authentication mode, timeouts, proxy, root certificates and
+/// default headers.
+///
+/// Created with `Configuration::builder()`. If the base URL has no scheme, `https://` is assumed.
+/// When several authentication modes are set, a token provider takes precedence over the API key,
+/// which takes precedence over basic authentication and then over the bearer access token.
+#[derive(Debug)]
+pub struct ConfigurationBuilder {
+    configuration: Configuration,
+}
+
+impl ConfigurationBuilder {
+    pub(super) fn new(base_url: String) -> ConfigurationBuilder {
+        ConfigurationBuilder {
+            configuration: Configuration::new(base_url),
+        }
+    }
+
+    /// Authenticates requests with an API key.
+    pub fn api_key(mut self, api_key: ApiKey) -> Self {
+        self.configuration.settings_mut().api_key = Some(api_key);
+        self
+    }
+
+    /// Authenticates requests with a username and password.
+    pub fn basic_auth(mut self, basic_auth: BasicAuth) -> Self {
+        self.configuration.settings_mut().basic_auth = Some(basic_auth);
+        self
+    }
+
+    /// Authenticates requests with an OAuth2 bearer access token.
+    pub fn bearer_access_token(mut self, token: impl Into<SecretString>) -> Self {
+        self.configuration.settings_mut().bearer_access_token = Some(token.into());
+        self
+    }
+
+    /// Authenticates requests with bearer access tokens obtained from a token provider, e.g. an
+    /// `OAuth2TokenProvider`.
+    pub fn token_provider(mut self, token_provider: Arc<dyn TokenProvider>) -> Self {
+        self.configuration.settings_mut().token_provider = Some(token_provider);
+        self
+    }
+
+    /// Sets the retry policy. Retries are disabled by default.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.configuration.settings_mut().retry_policy = retry_policy;
+        self
+    }
+
+    /// Sets the rate limiter shared by every client built from the configuration.
+    pub fn rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.configuration.settings_mut().rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Adds a middleware at the end of the chain.
+    pub fn middleware(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.configuration
+            .settings_mut()
+            .middleware
+            .push(middleware);
+        self
+    }
+
+    /// Sets the timeout for establishing a connection. There is no timeout by default.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.configuration.settings_mut().connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for a whole request, from sending it to reading the response body. There
+    /// is no timeout by default.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.configuration.settings_mut().timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for each read from the connection. There is no timeout by default.
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.configuration.settings_mut().read_timeout = Some(timeout);
+        self
+    }
+
+    /// Sends requests through a proxy. Setting a proxy disables the system proxy.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.configuration.settings_mut().proxy = Some(proxy);
+        self
+    }
+
+    /// Adds a trusted root certificate, e.g. for a TLS-intercepting corporate proxy.
+    #[cfg(tls)]
+    pub fn add_root_certificate(mut self, certificate: reqwest::Certificate) -> Self {
+        self.configuration
+            .settings_mut()
+            .root_certificates
+            .push(certificate);
+        self
+    }
+
+    /// Adds a header sent with every request. Authentication and `User-Agent` headers set by the
+    /// client take precedence over default headers with the same name. Values of headers that
+    /// carry credentials, like `Authorization` or `X-Api-Key`, are marked as sensitive, so that
+    /// they are redacted from `Debug` output.
+    pub fn default_header(mut self, name: HeaderName, mut value: HeaderValue) -> Self {
+        if is_credential(&name) {
+            value.set_sensitive(true);
+        }
+        self.configuration
+            .settings_mut()
+            .default_headers
+            .insert(name, value);
+        self
+    }
+
+    /// Adds headers sent with every request, replacing default headers with the same names. Values
+    /// of headers that carry credentials are marked as sensitive.
+    pub fn default_headers(mut self, mut headers: HeaderMap) -> Self {
+        for (name, value) in headers.iter_mut() {
+            if is_credential(name) {
+                value.set_sensitive(true);
+            }
+        }
+        self.configuration
+            .settings_mut()
+            .default_headers
+            .extend(headers);
+        self
+    }
+
+    /// Appends text, usually the name and version of your application, to the `User-Agent` header
+    /// of every request.
+    pub fn user_agent_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.configuration.settings_mut().user_agent_suffix = Some(suffix.into());
+        self
+    }
+
+    /// Sets the correlation ID sent in the `X-Correlation-Id` header of every request, instead of
+    /// a random ID generated for each call.
+    pub fn correlation_id(mut self, correlation_id: impl Into<String>) -> Self {
+        self.configuration.settings_mut().correlation_id = Some(correlation_id.into());
+        self
+    }
+
+    /// Enables or disables idempotent sends. See `Configuration::set_idempotent_sends()`.
+    pub fn idempotent_sends(mut self, idempotent_sends: bool) -> Self {
+        self.configuration.settings_mut().idempotent_sends = idempotent_sends;
+        self
+    }
+
+    /// Validates the options and returns the `Configuration`.
+    pub fn build(self) -> Result<Configuration, ConfigurationError> {
+        let mut configuration = self.configuration;
+
+        let base_url = configuration.base_url().trim();
+        let base_url = if base_url.contains("://") {
+            base_url.trim_end_matches('/').to_string()
+        } else {
+            format!("https://{}", base_url.trim_end_matches('/'))
+        };
+
+        match Url::parse(&base_url) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") && url.has_host() => {}
+            _ => return Err(ConfigurationError::InvalidBaseUrl(base_url)),
+        }
+        configuration.settings_mut().base_url = base_url;
+
+        if let Some(correlation_id) = configuration.correlation_id() {
+            if HeaderValue::from_str(correlation_id).is_err() {
+                return Err(ConfigurationError::InvalidCorrelationId(
+                    correlation_id.to_string(),
+                ));
+            }
+        }
+
+        // Builds the HTTP transport once, so invalid proxies or certificates fail here, and every
+        // client built from the configuration shares it.
+        let http_transport = DefaultTransport::with_configuration(&configuration)?;
+        configuration.settings_mut().http_transport = Some(http_transport);
+
+        Ok(configuration)
+    }
+}
+
+// Returns `true` if the header likely carries credentials, e.g. `Authorization`,
+// `Proxy-Authorization`, `Cookie` or `X-Api-Key`.
+fn is_credential(name: &HeaderName) -> bool {
+    const PARTS: [&str; 6] = ["auth", "cookie", "key", "password", "secret", "token"];
+
+    PARTS.iter().any(|part| name.as_str().contains(part))
+}
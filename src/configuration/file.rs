@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+use std::env;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::header::{HeaderName, HeaderValue};
+use serde_derive::Deserialize;
+
+use crate::api::auth::OAuth2TokenProvider;
+use crate::configuration::{ApiKey, BasicAuth, Configuration, ConfigurationError, SecretString};
+
+// Profile used when none is given and `IB_PROFILE` is not set.
+const DEFAULT_PROFILE: &str = "default";
+
+// One named profile of a configuration file. Every key is optional here, so that missing ones
+// can be reported by name, and so that environment variables can fill them in.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Profile {
+    base_url: Option<String>,
+    api_key: Option<SecretString>,
+    api_key_prefix: Option<String>,
+    username: Option<String>,
+    password: Option<SecretString>,
+    bearer_access_token: Option<SecretString>,
+    client_id: Option<String>,
+    client_secret: Option<SecretString>,
+    connect_timeout_secs: Option<f64>,
+    timeout_secs: Option<f64>,
+    read_timeout_secs: Option<f64>,
+    proxy: Option<String>,
+    user_agent_suffix: Option<String>,
+    #[serde(default)]
+    default_headers: HashMap<String, String>,
+}
+
+impl Configuration {
+    /// Loads the profile named by the `IB_PROFILE` environment variable, or the `default` profile,
+    /// from a TOML or JSON file. See `Configuration::from_file_profile()`.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Configuration, ConfigurationError> {
+        let profile = env::var("IB_PROFILE").unwrap_or_else(|_| DEFAULT_PROFILE.to_string());
+
+        Configuration::from_file_profile(path, &profile)
+    }
+
+    /// Loads a named profile from a TOML or JSON file, chosen by the file extension.
+    ///
+    /// Each top-level table of the file is a profile, with the keys `base_url`, `api_key`,
+    /// `api_key_prefix`, `username`, `password`, `bearer_access_token`, `client_id`,
+    /// `client_secret`, `connect_timeout_secs`, `timeout_secs`, `read_timeout_secs`, `proxy`,
+    /// `user_agent_suffix` and `default_headers`. Only `base_url` and one authentication mode are
+    /// required. With `client_id` and `client_secret`, access tokens are obtained with an
+    /// `OAuth2TokenProvider`.
+    ///
+    /// The environment variables `IB_<PROFILE>_<KEY>` override the authentication keys and the
+    /// base URL of the file, where `<PROFILE>` is the profile name in upper case, with other
+    /// characters than letters and digits replaced by `_`, and `<KEY>` is the key in upper case,
+    /// e.g. `IB_MARKETING_API_KEY`. This keeps secrets out of the file.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use infobip_sdk::configuration::Configuration;
+    /// // infobip.toml:
+    /// //
+    /// // [marketing]
+    /// // base_url = "https://some.api.infobip.com"
+    /// // api_key = "some-api-key"
+    /// //
+    /// // [transactional]
+    /// // base_url = "https://other.api.infobip.com"
+    /// // timeout_secs = 10
+    /// // # API key read from IB_TRANSACTIONAL_API_KEY.
+    /// let configuration = Configuration::from_file_profile("infobip.toml", "transactional")?;
+    /// # Ok::<(), infobip_sdk::configuration::ConfigurationError>(())
+    /// ```
+    pub fn from_file_profile(
+        path: impl AsRef<Path>,
+        profile: &str,
+    ) -> Result<Configuration, ConfigurationError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|source| ConfigurationError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        from_str(path, &content, profile, |name| env::var(name).ok())
+    }
+}
+
+// Builds the configuration of a profile from the contents of the file at `path`, reading
+// overrides with `env_var`.
+pub(crate) fn from_str<F>(
+    path: &Path,
+    content: &str,
+    profile_name: &str,
+    env_var: F,
+) -> Result<Configuration, ConfigurationError>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut profiles = parse(path, content)?;
+    let mut profile = profiles
+        .remove(profile_name)
+        .ok_or_else(|| ConfigurationError::ProfileNotFound(profile_name.to_string()))?;
+
+    let env_prefix = env_prefix(profile_name);
+    let env_value = |key: &str| env_var(&format!("{}{}", env_prefix, key.to_uppercase()));
+    let override_with = |value: &mut Option<String>, key: &str| {
+        if let Some(env_value) = env_value(key) {
+            *value = Some(env_value);
+        }
+    };
+    let override_secret_with = |value: &mut Option<SecretString>, key: &str| {
+        if let Some(env_value) = env_value(key) {
+            *value = Some(SecretString::new(env_value));
+        }
+    };
+    override_with(&mut profile.base_url, "base_url");
+    override_secret_with(&mut profile.api_key, "api_key");
+    override_with(&mut profile.api_key_prefix, "api_key_prefix");
+    override_with(&mut profile.username, "username");
+    override_secret_with(&mut profile.password, "password");
+    override_secret_with(&mut profile.bearer_access_token, "bearer_access_token");
+    override_with(&mut profile.client_id, "client_id");
+    override_secret_with(&mut profile.client_secret, "client_secret");
+
+    build(profile, profile_name)
+}
+
+// Parses the profiles of a file, in the format given by its extension.
+fn parse(path: &Path, content: &str) -> Result<HashMap<String, Profile>, ConfigurationError> {
+    let result = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("toml") => toml::from_str(content).map_err(|error| error.to_string()),
+        Some("json") => serde_json::from_str(content).map_err(|error| error.to_string()),
+        _ => return Err(ConfigurationError::UnsupportedFormat(path.to_path_buf())),
+    };
+
+    result.map_err(|message| ConfigurationError::InvalidFile {
+        path: path.to_path_buf(),
+        message,
+    })
+}
+
+// Returns the prefix of the environment variables that override values of a profile.
+fn env_prefix(profile_name: &str) -> String {
+    let name: String = profile_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    format!("IB_{}_", name)
+}
+
+fn build(profile: Profile, profile_name: &str) -> Result<Configuration, ConfigurationError> {
+    let missing = |key: &str| ConfigurationError::MissingKey {
+        profile: profile_name.to_string(),
+        key: key.to_string(),
+    };
+    let invalid = |key: &str| ConfigurationError::InvalidValue {
+        profile: profile_name.to_string(),
+        key: key.to_string(),
+    };
+
+    let base_url = profile.base_url.ok_or_else(|| missing("base_url"))?;
+    let mut builder = Configuration::builder(base_url);
+
+    let mut client_credentials = None;
+    if let Some(key) = profile.api_key {
+        builder = builder.api_key(ApiKey {
+            prefix: Some(profile.api_key_prefix.unwrap_or_else(|| "App".to_string())),
+            key,
+        });
+    } else if let Some(username) = profile.username {
+        builder = builder.basic_auth(BasicAuth {
+            username,
+            password: profile.password,
+        });
+    } else if let Some(token) = profile.bearer_access_token {
+        builder = builder.bearer_access_token(token);
+    } else if let Some(client_id) = profile.client_id {
+        let client_secret = profile
+            .client_secret
+            .ok_or_else(|| missing("client_secret"))?;
+        client_credentials = Some((client_id, client_secret));
+    } else {
+        return Err(missing("api_key"));
+    }
+
+    let duration = |seconds: Option<f64>, key: &str| match seconds {
+        Some(seconds) if (0.0..u64::MAX as f64).contains(&seconds) => {
+            Ok(Some(Duration::from_secs_f64(seconds)))
+        }
+        Some(_) => Err(invalid(key)),
+        None => Ok(None),
+    };
+    if let Some(timeout) = duration(profile.connect_timeout_secs, "connect_timeout_secs")? {
+        builder = builder.connect_timeout(timeout);
+    }
+    if let Some(timeout) = duration(profile.timeout_secs, "timeout_secs")? {
+        builder = builder.timeout(timeout);
+    }
+    if let Some(timeout) = duration(profile.read_timeout_secs, "read_timeout_secs")? {
+        builder = builder.read_timeout(timeout);
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(proxy) = profile.proxy {
+        builder = builder.proxy(reqwest::Proxy::all(proxy).map_err(|_| invalid("proxy"))?);
+    }
+    // Requests made with the Fetch API go through the proxy of the host.
+    #[cfg(target_arch = "wasm32")]
+    if profile.proxy.is_some() {
+        return Err(invalid("proxy"));
+    }
+    if let Some(suffix) = profile.user_agent_suffix {
+        builder = builder.user_agent_suffix(suffix);
+    }
+    for (name, value) in profile.default_headers {
+        let key = format!("default_headers.{}", name);
+        let name = HeaderName::try_from(name).map_err(|_| invalid(&key))?;
+        let value = HeaderValue::try_from(value).map_err(|_| invalid(&key))?;
+        builder = builder.default_header(name, value);
+    }
+
+    let mut configuration = builder.build()?;
+    if let Some((client_id, client_secret)) = client_credentials {
+        let token_provider = OAuth2TokenProvider::new(&configuration, client_id, client_secret);
+        configuration.set_token_provider(Arc::new(token_provider));
+    }
+
+    Ok(configuration)
+}
@@ -1,77 +1,464 @@
 //! Configuration of the Infobip client
 use std::env::{self, VarError};
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use thiserror::Error;
+
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::Proxy;
+
+use crate::api::auth::TokenProvider;
+use crate::api::middleware::Middleware;
+use crate::api::transport::DefaultTransport;
+
+mod builder;
+mod file;
+mod rate_limit;
+mod secret;
+
+pub use builder::ConfigurationBuilder;
+pub use rate_limit::{RateLimit, RateLimiter};
+pub use secret::SecretString;
+
+/// Error returned when a `Configuration` can not be built.
+#[derive(Error, Debug)]
+pub enum ConfigurationError {
+    #[error("invalid base URL: {0}")]
+    InvalidBaseUrl(String),
+
+    #[error("HTTP client error")]
+    HttpClient(#[from] reqwest::Error),
+
+    #[error("could not read configuration file {}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("invalid configuration file {}: {message}", path.display())]
+    InvalidFile { path: PathBuf, message: String },
+
+    #[error("unsupported configuration file format: {}", .0.display())]
+    UnsupportedFormat(PathBuf),
+
+    #[error("profile `{0}` not found")]
+    ProfileNotFound(String),
+
+    #[error("missing `{key}` in profile `{profile}`")]
+    MissingKey { profile: String, key: String },
+
+    #[error("invalid value for `{key}` in profile `{profile}`")]
+    InvalidValue { profile: String, key: String },
+
+    #[error("invalid correlation ID: {0}")]
+    InvalidCorrelationId(String),
+
+    #[error("{0} not supported by the HTTP transport")]
+    UnsupportedByTransport(&'static str),
+}
 
 /// Holds the necessary configuration URL and authentication details of an Infobip client.
-#[derive(Debug, Clone)]
+///
+/// Clones share the settings until one of them is modified, so they are cheap to create, e.g. for
+/// the channel clients of an `InfobipClient`.
+#[derive(Clone)]
 pub struct Configuration {
+    settings: Arc<Settings>,
+}
+
+// The settings of a `Configuration`, copied on the first modification of a shared clone.
+#[derive(Clone)]
+struct Settings {
     base_url: String,
     basic_auth: Option<BasicAuth>,
-    bearer_access_token: Option<String>,
+    bearer_access_token: Option<SecretString>,
     api_key: Option<ApiKey>,
+    token_provider: Option<Arc<dyn TokenProvider>>,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    middleware: Vec<Arc<dyn Middleware>>,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    #[cfg(not(target_arch = "wasm32"))]
+    proxy: Option<Proxy>,
+    #[cfg(tls)]
+    root_certificates: Vec<reqwest::Certificate>,
+    default_headers: HeaderMap,
+    user_agent_suffix: Option<String>,
+    correlation_id: Option<String>,
+    idempotent_sends: bool,
+    http_transport: Option<DefaultTransport>,
 }
 
 impl Configuration {
+    // Creates a `Configuration` without authentication and with every option unset.
+    fn new(base_url: String) -> Configuration {
+        let settings = Settings {
+            base_url,
+            basic_auth: None,
+            bearer_access_token: None,
+            api_key: None,
+            token_provider: None,
+            retry_policy: RetryPolicy::disabled(),
+            rate_limiter: None,
+            middleware: Vec::new(),
+            connect_timeout: None,
+            timeout: None,
+            read_timeout: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            proxy: None,
+            #[cfg(tls)]
+            root_certificates: Vec::new(),
+            default_headers: HeaderMap::new(),
+            user_agent_suffix: None,
+            correlation_id: None,
+            idempotent_sends: false,
+            http_transport: None,
+        };
+
+        Configuration {
+            settings: Arc::new(settings),
+        }
+    }
+
+    // Returns the settings for modification, copying them first if a clone shares them.
+    fn settings_mut(&mut self) -> &mut Settings {
+        Arc::make_mut(&mut self.settings)
+    }
+
     /// Reads API key details and IB_BASE_URL environment variable to build and return a
     /// `Configuration` instance.
     pub fn from_env_api_key() -> Result<Configuration, VarError> {
-        Ok(Configuration {
-            base_url: env::var("IB_BASE_URL")?,
-            api_key: Some(ApiKey::from_env()?),
-            basic_auth: None,
-            bearer_access_token: None,
-        })
+        Ok(Configuration::with_api_key(
+            env::var("IB_BASE_URL")?,
+            ApiKey::from_env()?,
+        ))
     }
 
     // Builds and returns a `Configuration` instance set with an API key.
     pub fn with_api_key(base_url: String, api_key: ApiKey) -> Configuration {
-        Configuration {
-            base_url,
-            api_key: Some(api_key),
-            basic_auth: None,
-            bearer_access_token: None,
-        }
+        let mut configuration = Configuration::new(base_url);
+        configuration.settings_mut().api_key = Some(api_key);
+        configuration
+    }
+
+    /// Returns a `ConfigurationBuilder` to set up authentication, timeouts, proxy and headers.
+    ///
+    /// # Example
+    /// ```
+    /// # use std::time::Duration;
+    /// # use infobip_sdk::configuration::{BasicAuth, Configuration};
+    /// let configuration = Configuration::builder("https://some.api.infobip.com")
+    ///     .basic_auth(BasicAuth::new("username".into(), Some("password".into())))
+    ///     .connect_timeout(Duration::from_secs(5))
+    ///     .timeout(Duration::from_secs(30))
+    ///     .user_agent_suffix("my-app/1.0")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert!(configuration.basic_auth().is_some());
+    /// ```
+    pub fn builder(base_url: impl Into<String>) -> ConfigurationBuilder {
+        ConfigurationBuilder::new(base_url.into())
     }
 
     /// Returns the base URL of the Configuration.
     pub fn base_url(&self) -> &String {
-        &self.base_url
+        &self.settings.base_url
     }
 
     /// Returns the API key of the Configuration.
     pub fn api_key(&self) -> Option<&ApiKey> {
-        self.api_key.as_ref()
+        self.settings.api_key.as_ref()
     }
 
     /// Returns the basic authentication of the Configuration.
     pub fn basic_auth(&self) -> Option<&BasicAuth> {
-        self.basic_auth.as_ref()
+        self.settings.basic_auth.as_ref()
     }
 
     /// Returns the bearer access token of the Configuration.
-    pub fn bearer_access_token(&self) -> Option<&String> {
-        self.bearer_access_token.as_ref()
+    pub fn bearer_access_token(&self) -> Option<&SecretString> {
+        self.settings.bearer_access_token.as_ref()
+    }
+
+    /// Returns the token provider of the Configuration.
+    pub fn token_provider(&self) -> Option<&dyn TokenProvider> {
+        self.settings.token_provider.as_deref()
+    }
+
+    /// Sets the provider of bearer access tokens for every client built from this Configuration.
+    /// It takes precedence over the other authentication modes.
+    pub fn set_token_provider(&mut self, token_provider: Arc<dyn TokenProvider>) {
+        self.settings_mut().token_provider = Some(token_provider);
+    }
+
+    /// Returns the retry policy of the Configuration.
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.settings.retry_policy
+    }
+
+    /// Sets the retry policy used by every client built from this Configuration. Retries are
+    /// disabled by default.
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.settings_mut().retry_policy = retry_policy;
+    }
+
+    /// Returns the rate limiter of the Configuration.
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.settings.rate_limiter.as_deref()
+    }
+
+    /// Sets the rate limiter used by every client built from this Configuration. Clones of the
+    /// Configuration share the same limiter, so its limits apply to all of them together.
+    pub fn set_rate_limiter(&mut self, rate_limiter: Arc<RateLimiter>) {
+        self.settings_mut().rate_limiter = Some(rate_limiter);
+    }
+
+    /// Returns the middleware chain of the Configuration, in the order it runs.
+    pub fn middleware(&self) -> &[Arc<dyn Middleware>] {
+        &self.settings.middleware
+    }
+
+    /// Adds a middleware at the end of the chain run around every request sent by clients built
+    /// from this Configuration.
+    pub fn add_middleware(&mut self, middleware: Arc<dyn Middleware>) {
+        self.settings_mut().middleware.push(middleware);
+    }
+
+    /// Returns the timeout for establishing a connection.
+    pub fn connect_timeout(&self) -> Option<Duration> {
+        self.settings.connect_timeout
+    }
+
+    /// Returns the timeout for a whole request, from sending it to reading the response body.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.settings.timeout
+    }
+
+    /// Returns the timeout for each read from the connection.
+    pub fn read_timeout(&self) -> Option<Duration> {
+        self.settings.read_timeout
+    }
+
+    /// Returns the proxy requests are sent through.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn proxy(&self) -> Option<&Proxy> {
+        self.settings.proxy.as_ref()
+    }
+
+    /// Returns the additional trusted root certificates.
+    #[cfg(tls)]
+    pub fn root_certificates(&self) -> &[reqwest::Certificate] {
+        &self.settings.root_certificates
+    }
+
+    /// Returns the headers sent with every request.
+    pub fn default_headers(&self) -> &HeaderMap {
+        &self.settings.default_headers
+    }
+
+    /// Returns the text appended to the `User-Agent` header of every request.
+    pub fn user_agent_suffix(&self) -> Option<&str> {
+        self.settings.user_agent_suffix.as_deref()
+    }
+
+    /// Returns the correlation ID sent with every request, if one is set.
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.settings.correlation_id.as_deref()
+    }
+
+    /// Sets the correlation ID sent in the `X-Correlation-Id` header of every request. Without
+    /// one, a random ID is generated for each call.
+    pub fn set_correlation_id(&mut self, correlation_id: impl Into<String>) {
+        self.settings_mut().correlation_id = Some(correlation_id.into());
+    }
+
+    /// Returns `true` if message sends fill in missing bulk and message IDs, and look them up
+    /// before sending a message again.
+    pub fn idempotent_sends(&self) -> bool {
+        self.settings.idempotent_sends
+    }
+
+    /// Enables idempotent sends for the SMS, Email and WhatsApp send endpoints. Missing bulk and
+    /// message IDs are generated once per call, and kept for every attempt of that call. When a
+    /// send fails without a clear outcome, e.g. with a timeout or a gateway error, the message logs
+    /// are checked for its bulk ID, and the request is only sent again, within the retry policy,
+    /// if nothing was logged. WhatsApp has no logs, so such failures are returned without sending
+    /// the request again. Disabled by default.
+    ///
+    /// An email gets a message ID only if it has a single recipient, without CC or BCC.
+    pub fn set_idempotent_sends(&mut self, idempotent_sends: bool) {
+        self.settings_mut().idempotent_sends = idempotent_sends;
+    }
+
+    // Returns the transport built by `ConfigurationBuilder::build()`, if the configuration comes
+    // from the builder.
+    pub(crate) fn http_transport(&self) -> Option<&DefaultTransport> {
+        self.settings.http_transport.as_ref()
+    }
+}
+
+impl fmt::Debug for Configuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let settings = &self.settings;
+        let mut debug = f.debug_struct("Configuration");
+        debug
+            .field("base_url", &settings.base_url)
+            .field("basic_auth", &settings.basic_auth)
+            .field("bearer_access_token", &settings.bearer_access_token)
+            .field("api_key", &settings.api_key)
+            .field("token_provider", &settings.token_provider)
+            .field("retry_policy", &settings.retry_policy)
+            .field("rate_limiter", &settings.rate_limiter)
+            .field("middleware", &settings.middleware)
+            .field("connect_timeout", &settings.connect_timeout)
+            .field("timeout", &settings.timeout)
+            .field("read_timeout", &settings.read_timeout);
+        #[cfg(not(target_arch = "wasm32"))]
+        debug.field("proxy", &settings.proxy);
+        #[cfg(tls)]
+        debug.field("root_certificates", &settings.root_certificates);
+        debug
+            .field("default_headers", &settings.default_headers)
+            .field("user_agent_suffix", &settings.user_agent_suffix)
+            .field("correlation_id", &settings.correlation_id)
+            .field("idempotent_sends", &settings.idempotent_sends)
+            .field("http_transport", &settings.http_transport)
+            .finish()
+    }
+}
+
+/// Holds the rules for automatically retrying failed requests.
+///
+/// A request is retried when the response status is one of `retry_on_status`, or when the
+/// connection fails or times out and the corresponding flag is set. If the response has a
+/// `Retry-After` header, its value is used as the delay, otherwise the delay grows exponentially
+/// from `base_delay`. Delays never exceed `max_delay`: if the server asks to wait longer, the
+/// request is not retried and its error is returned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one. A value of 1 disables retries.
+    pub max_attempts: u32,
+
+    /// Delay before the first retry. Doubles with every following attempt.
+    pub base_delay: Duration,
+
+    /// Upper bound for the delay between two attempts. A `Retry-After` above it stops the retries.
+    pub max_delay: Duration,
+
+    /// Randomizes each delay between half and all of its value, to avoid retry storms.
+    pub jitter: bool,
+
+    /// Response status codes that trigger a retry.
+    pub retry_on_status: Vec<StatusCode>,
+
+    /// Retry when the connection to the server can not be established.
+    pub retry_on_connect_errors: bool,
+
+    /// Retry when the request times out.
+    pub retry_on_timeouts: bool,
+}
+
+impl RetryPolicy {
+    /// Creates a new `RetryPolicy` with the given maximum number of attempts and default values
+    /// for all other fields.
+    pub fn new(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a `RetryPolicy` that sends every request exactly once.
+    pub fn disabled() -> RetryPolicy {
+        RetryPolicy::new(1)
+    }
+
+    /// Returns `true` if a request will be attempted more than once.
+    pub fn is_enabled(&self) -> bool {
+        self.max_attempts > 1
+    }
+
+    /// Returns the delay to wait before the given retry, starting at 1 for the first retry. A
+    /// `Retry-After` value sent by the server takes precedence over the exponential delay, and
+    /// `None` is returned if it exceeds `max_delay`, as retrying earlier would be rejected again.
+    pub fn delay(&self, retry: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        if let Some(retry_after) = retry_after {
+            return (retry_after <= self.max_delay).then_some(retry_after);
+        }
+
+        let exponent = retry.saturating_sub(1).min(31);
+        let delay = self.base_delay.saturating_mul(1 << exponent);
+        let delay = if self.jitter {
+            delay.mul_f64(0.5 + fastrand::f64() / 2.0)
+        } else {
+            delay
+        };
+
+        Some(delay.min(self.max_delay))
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, starting with a 500ms delay, on rate limiting, gateway errors, connection
+    /// errors and timeouts.
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            retry_on_status: vec![
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            retry_on_connect_errors: true,
+            retry_on_timeouts: true,
+        }
     }
 }
 
-/// Holds the details for authentication based on username and password.
+/// Holds the details for authentication based on username and password. The password is redacted
+/// when printed.
 #[derive(Debug, Clone)]
 pub struct BasicAuth {
     pub username: String,
-    pub password: Option<String>,
+    pub password: Option<SecretString>,
 }
 
-/// Holds the details for API key authentication.
+impl BasicAuth {
+    /// Creates a new `BasicAuth`.
+    pub fn new(username: String, password: Option<String>) -> BasicAuth {
+        BasicAuth {
+            username,
+            password: password.map(SecretString::new),
+        }
+    }
+}
+
+/// Holds the details for API key authentication. The key is redacted when printed.
 #[derive(Debug, Clone)]
 pub struct ApiKey {
     pub prefix: Option<String>,
-    pub key: String,
+    pub key: SecretString,
 }
 
 impl ApiKey {
     /// Creates a new `ApiKey`.
     pub fn new(key: String) -> ApiKey {
         ApiKey {
-            key,
+            key: SecretString::new(key),
             prefix: Some("App".to_string()),
         }
     }
@@ -79,8 +466,11 @@ impl ApiKey {
     /// Reads `IB_API_KEY`, and optionally `IB_API_KEY_PREFIX`, variables from environment.
     pub fn from_env() -> Result<ApiKey, VarError> {
         Ok(ApiKey {
-            key: env::var("IB_API_KEY")?,
+            key: SecretString::new(env::var("IB_API_KEY")?),
             prefix: Some(env::var("IB_API_KEY_PREFIX").unwrap_or_else(|_| "App".to_string())),
         })
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,178 @@
+//! Client-side rate limiting of requests sent to the Infobip API.
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::api::timer::{self, Instant};
+
+/// Holds the maximum number of requests allowed in a period of time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Number of requests allowed per period. Up to this many requests can be sent in a burst.
+    pub requests: u32,
+
+    /// Length of the period.
+    pub period: Duration,
+}
+
+impl RateLimit {
+    /// Creates a new `RateLimit` allowing `requests` requests every `period`.
+    pub fn new(requests: u32, period: Duration) -> RateLimit {
+        RateLimit { requests, period }
+    }
+
+    /// Creates a new `RateLimit` allowing `requests` requests per second.
+    pub fn per_second(requests: u32) -> RateLimit {
+        RateLimit::new(requests, Duration::from_secs(1))
+    }
+
+    /// Creates a new `RateLimit` allowing `requests` requests per minute.
+    pub fn per_minute(requests: u32) -> RateLimit {
+        RateLimit::new(requests, Duration::from_secs(60))
+    }
+
+    // Time between two requests when sending at the steady rate.
+    fn interval(&self) -> Duration {
+        self.period / self.requests.max(1)
+    }
+}
+
+// Token bucket state, stored as the time at which the bucket will be full again.
+#[derive(Debug)]
+struct Bucket {
+    path_prefix: String,
+    limit: RateLimit,
+    full_at: Option<Instant>,
+}
+
+impl Bucket {
+    fn new(path_prefix: &str, limit: RateLimit) -> Bucket {
+        Bucket {
+            path_prefix: path_prefix.to_string(),
+            limit,
+            full_at: None,
+        }
+    }
+
+    // Returns `true` if the path starts with the whole segments of the prefix, so that `/sms/1`
+    // matches `/sms/1/logs` but not `/sms/10`.
+    fn matches(&self, path: &str) -> bool {
+        match path.strip_prefix(&self.path_prefix) {
+            Some(rest) => {
+                rest.is_empty() || rest.starts_with('/') || self.path_prefix.ends_with('/')
+            }
+            None => false,
+        }
+    }
+
+    // Returns how long to wait before a request sent at `now` fits in the bucket.
+    fn wait_time(&self, now: Instant) -> Duration {
+        let full_at = self.full_at.map_or(now, |full_at| full_at.max(now));
+        let burst = self.limit.interval() * self.limit.requests.saturating_sub(1);
+
+        full_at.saturating_duration_since(now + burst)
+    }
+
+    // Takes a token for a request sent at `sent_at`.
+    fn take(&mut self, sent_at: Instant) {
+        let full_at = self.full_at.map_or(sent_at, |full_at| full_at.max(sent_at));
+        self.full_at = Some(full_at + self.limit.interval());
+    }
+}
+
+/// Token bucket rate limiter that delays requests to stay under Infobip throughput limits.
+///
+/// Limits can be set for the whole account and for endpoint families, identified by a path
+/// prefix like `/sms/2/text/advanced` or `/email`. Prefixes match whole path segments, so `/sms/1`
+/// applies to `/sms/1/logs` but not to `/sms/10`. A request waits until both the account limit
+/// and the limit of the longest matching prefix allow it. Set the limiter on a `Configuration` to
+/// share it between every client built from that configuration and their clones.
+///
+/// # Example
+/// ```
+/// # use infobip_sdk::configuration::{RateLimit, RateLimiter};
+/// let rate_limiter = RateLimiter::new()
+///     .with_account_limit(RateLimit::per_second(100))
+///     .with_endpoint_limit("/sms/2/text/advanced", RateLimit::per_second(20))
+///     .with_endpoint_limit("/email/3/send", RateLimit::per_second(10));
+/// ```
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    buckets: Mutex<Buckets>,
+}
+
+#[derive(Debug, Default)]
+struct Buckets {
+    account: Option<Bucket>,
+    endpoints: Vec<Bucket>,
+}
+
+impl RateLimiter {
+    /// Creates a new `RateLimiter` without limits.
+    pub fn new() -> RateLimiter {
+        Default::default()
+    }
+
+    /// Sets the limit shared by all requests.
+    pub fn with_account_limit(mut self, limit: RateLimit) -> RateLimiter {
+        self.buckets.get_mut().unwrap().account = Some(Bucket::new("", limit));
+
+        self
+    }
+
+    /// Sets the limit of the endpoint family whose paths start with `path_prefix`.
+    pub fn with_endpoint_limit(mut self, path_prefix: &str, limit: RateLimit) -> RateLimiter {
+        let endpoints = &mut self.buckets.get_mut().unwrap().endpoints;
+        endpoints.retain(|bucket| bucket.path_prefix != path_prefix);
+        endpoints.push(Bucket::new(path_prefix, limit));
+
+        self
+    }
+
+    /// Reserves a slot for a request to `path` and returns how long to wait before sending it.
+    pub fn reserve(&self, path: &str) -> Duration {
+        self.reserve_at(path, Instant::now())
+    }
+
+    pub(crate) fn reserve_at(&self, path: &str, now: Instant) -> Duration {
+        let mut guard = self.buckets.lock().unwrap();
+        let Buckets { account, endpoints } = &mut *guard;
+
+        let endpoint = endpoints
+            .iter_mut()
+            .filter(|bucket| bucket.matches(path))
+            .max_by_key(|bucket| bucket.path_prefix.len());
+
+        let mut buckets: Vec<&mut Bucket> = account.iter_mut().chain(endpoint).collect();
+
+        let wait_time = buckets
+            .iter()
+            .map(|bucket| bucket.wait_time(now))
+            .max()
+            .unwrap_or_default();
+
+        for bucket in buckets.iter_mut() {
+            bucket.take(now + wait_time);
+        }
+
+        wait_time
+    }
+
+    /// Waits asynchronously until a request to `path` is allowed.
+    pub async fn acquire(&self, path: &str) {
+        let wait_time = self.reserve(path);
+
+        if !wait_time.is_zero() {
+            timer::sleep(wait_time).await;
+        }
+    }
+
+    /// Blocks the current thread until a request to `path` is allowed.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn acquire_blocking(&self, path: &str) {
+        let wait_time = self.reserve(path);
+
+        if !wait_time.is_zero() {
+            std::thread::sleep(wait_time);
+        }
+    }
+}
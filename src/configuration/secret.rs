@@ -0,0 +1,70 @@
+use std::fmt;
+
+use serde::{Deserialize, Deserializer};
+use zeroize::Zeroize;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Holds a credential, like an API key or a password, that must not end up in logs.
+///
+/// `Debug` and `Display` print `[REDACTED]` instead of the value, and the memory holding the value
+/// is zeroed when it is dropped. Use `expose_secret()` to read the value.
+///
+/// # Example
+/// ```
+/// # use infobip_sdk::configuration::SecretString;
+/// let secret = SecretString::from("some-api-key");
+///
+/// assert_eq!(format!("{:?}", secret), "SecretString([REDACTED])");
+/// assert_eq!(secret.expose_secret(), "some-api-key");
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SecretString(String);
+
+impl SecretString {
+    /// Creates a new `SecretString`.
+    pub fn new(secret: String) -> SecretString {
+        SecretString(secret)
+    }
+
+    /// Returns the value of the secret.
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        SecretString(secret)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(secret: &str) -> Self {
+        SecretString(secret.to_string())
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretString({})", REDACTED)
+    }
+}
+
+impl fmt::Display for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(SecretString)
+    }
+}
@@ -0,0 +1,118 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderValue};
+
+use crate::configuration::{ApiKey, BasicAuth, Configuration, ConfigurationError};
+
+#[test]
+fn test_builder_defaults() {
+    let configuration = Configuration::builder("https://some.api.infobip.com")
+        .build()
+        .unwrap();
+
+    assert_eq!(configuration.base_url(), "https://some.api.infobip.com");
+    assert!(configuration.api_key().is_none());
+    assert!(configuration.basic_auth().is_none());
+    assert!(configuration.bearer_access_token().is_none());
+    assert!(!configuration.retry_policy().is_enabled());
+    assert!(configuration.timeout().is_none());
+    assert!(configuration.proxy().is_none());
+    assert!(configuration.default_headers().is_empty());
+    assert!(configuration.user_agent_suffix().is_none());
+}
+
+#[test]
+fn test_builder_all_options() {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-tenant-id", HeaderValue::from_static("tenant"));
+
+    let builder = Configuration::builder("https://some.api.infobip.com")
+        .api_key(ApiKey::new("some-api-key".to_string()))
+        .basic_auth(BasicAuth::new("username".into(), Some("password".into())))
+        .bearer_access_token("some-token")
+        .connect_timeout(Duration::from_secs(1))
+        .timeout(Duration::from_secs(2))
+        .read_timeout(Duration::from_secs(3))
+        .default_headers(headers)
+        .default_header("x-team".parse().unwrap(), HeaderValue::from_static("sms"))
+        .user_agent_suffix("my-app/1.0");
+    // The default transport without Tokio does not support proxies.
+    #[cfg(any(tokio, not(async_io)))]
+    let builder = builder.proxy(reqwest::Proxy::all("http://localhost:3128").unwrap());
+    let configuration = builder.build().unwrap();
+
+    assert_eq!(
+        configuration.api_key().unwrap().key.expose_secret(),
+        "some-api-key"
+    );
+    assert_eq!(configuration.basic_auth().unwrap().username, "username");
+    assert_eq!(
+        configuration.bearer_access_token().unwrap().expose_secret(),
+        "some-token"
+    );
+    assert_eq!(
+        configuration.connect_timeout(),
+        Some(Duration::from_secs(1))
+    );
+    assert_eq!(configuration.timeout(), Some(Duration::from_secs(2)));
+    assert_eq!(configuration.read_timeout(), Some(Duration::from_secs(3)));
+    #[cfg(any(tokio, not(async_io)))]
+    assert!(configuration.proxy().is_some());
+    assert_eq!(configuration.default_headers().len(), 2);
+    assert_eq!(configuration.user_agent_suffix(), Some("my-app/1.0"));
+}
+
+#[test]
+fn test_builder_normalizes_base_url() {
+    let configuration = Configuration::builder("some.api.infobip.com/")
+        .build()
+        .unwrap();
+
+    assert_eq!(configuration.base_url(), "https://some.api.infobip.com");
+}
+
+#[test]
+fn test_builder_keeps_http_base_url() {
+    let configuration = Configuration::builder("http://localhost:8080")
+        .build()
+        .unwrap();
+
+    assert_eq!(configuration.base_url(), "http://localhost:8080");
+}
+
+#[test]
+fn test_builder_invalid_base_url() {
+    for base_url in ["", "ftp://some.api.infobip.com", "https://"] {
+        let error = Configuration::builder(base_url).build().unwrap_err();
+
+        assert!(matches!(error, ConfigurationError::InvalidBaseUrl(_)));
+    }
+}
+
+#[test]
+fn test_builder_invalid_correlation_id() {
+    let error = Configuration::builder("https://some.api.infobip.com")
+        .correlation_id("some\nid")
+        .build()
+        .unwrap_err();
+
+    assert!(matches!(error, ConfigurationError::InvalidCorrelationId(_)));
+}
+
+#[test]
+fn test_clones_share_settings_until_modified() {
+    let configuration = Configuration::builder("https://some.api.infobip.com")
+        .api_key(ApiKey::new("some-api-key".to_string()))
+        .build()
+        .unwrap();
+
+    let mut clone = configuration.clone();
+    assert!(Arc::ptr_eq(&clone.settings, &configuration.settings));
+
+    clone.set_correlation_id("some-id");
+    assert!(!Arc::ptr_eq(&clone.settings, &configuration.settings));
+    assert_eq!(clone.correlation_id(), Some("some-id"));
+    assert_eq!(configuration.correlation_id(), None);
+    assert!(clone.api_key().is_some());
+}
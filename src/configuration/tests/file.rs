@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::configuration::file::from_str;
+use crate::configuration::{Configuration, ConfigurationError};
+
+const TOML_FILE: &str = r#"
+[marketing]
+base_url = "https://marketing.api.infobip.com"
+api_key = "marketing-api-key"
+timeout_secs = 2.5
+user_agent_suffix = "marketing/1.0"
+
+[marketing.default_headers]
+x-tenant-id = "marketing"
+
+[staging]
+base_url = "https://staging.api.infobip.com"
+username = "username"
+password = "password"
+
+[transactional]
+base_url = "https://transactional.api.infobip.com"
+"#;
+
+const JSON_FILE: &str = r#"
+{
+    "default": {
+        "base_url": "https://some.api.infobip.com",
+        "bearer_access_token": "some-token",
+        "connect_timeout_secs": 3
+    }
+}
+"#;
+
+fn no_env(_name: &str) -> Option<String> {
+    None
+}
+
+#[test]
+fn test_toml_profile() {
+    let configuration =
+        from_str(Path::new("infobip.toml"), TOML_FILE, "marketing", no_env).unwrap();
+
+    assert_eq!(
+        configuration.base_url(),
+        "https://marketing.api.infobip.com"
+    );
+    let api_key = configuration.api_key().unwrap();
+    assert_eq!(api_key.key.expose_secret(), "marketing-api-key");
+    assert_eq!(api_key.prefix.as_deref(), Some("App"));
+    assert_eq!(configuration.timeout(), Some(Duration::from_millis(2500)));
+    assert_eq!(configuration.user_agent_suffix(), Some("marketing/1.0"));
+    assert_eq!(
+        configuration.default_headers().get("x-tenant-id").unwrap(),
+        "marketing"
+    );
+}
+
+#[test]
+fn test_toml_basic_auth_profile() {
+    let configuration = from_str(Path::new("infobip.toml"), TOML_FILE, "staging", no_env).unwrap();
+
+    let basic_auth = configuration.basic_auth().unwrap();
+    assert_eq!(basic_auth.username, "username");
+    assert_eq!(
+        basic_auth
+            .password
+            .as_ref()
+            .map(|password| password.expose_secret()),
+        Some("password")
+    );
+    assert!(configuration.api_key().is_none());
+}
+
+#[test]
+fn test_json_profile() {
+    let configuration = from_str(Path::new("infobip.json"), JSON_FILE, "default", no_env).unwrap();
+
+    assert_eq!(
+        configuration.bearer_access_token().unwrap().expose_secret(),
+        "some-token"
+    );
+    assert_eq!(
+        configuration.connect_timeout(),
+        Some(Duration::from_secs(3))
+    );
+}
+
+#[test]
+fn test_env_overrides_profile() {
+    let env = HashMap::from([
+        ("IB_TRANSACTIONAL_API_KEY", "transactional-api-key"),
+        ("IB_TRANSACTIONAL_API_KEY_PREFIX", "Bearer"),
+        ("IB_MARKETING_API_KEY", "wrong-profile"),
+    ]);
+
+    let configuration = from_str(
+        Path::new("infobip.toml"),
+        TOML_FILE,
+        "transactional",
+        |name| env.get(name).map(|value| value.to_string()),
+    )
+    .unwrap();
+
+    let api_key = configuration.api_key().unwrap();
+    assert_eq!(api_key.key.expose_secret(), "transactional-api-key");
+    assert_eq!(api_key.prefix.as_deref(), Some("Bearer"));
+}
+
+#[test]
+fn test_missing_key() {
+    let error = from_str(
+        Path::new("infobip.toml"),
+        TOML_FILE,
+        "transactional",
+        no_env,
+    )
+    .unwrap_err();
+
+    assert!(matches!(
+        &error,
+        ConfigurationError::MissingKey { profile, key }
+            if profile == "transactional" && key == "api_key"
+    ));
+    assert_eq!(
+        error.to_string(),
+        "missing `api_key` in profile `transactional`"
+    );
+}
+
+#[test]
+fn test_missing_base_url() {
+    let error = from_str(
+        Path::new("infobip.toml"),
+        "[default]\napi_key = \"some-api-key\"",
+        "default",
+        no_env,
+    )
+    .unwrap_err();
+
+    assert_eq!(error.to_string(), "missing `base_url` in profile `default`");
+}
+
+#[test]
+fn test_profile_not_found() {
+    let error = from_str(Path::new("infobip.toml"), TOML_FILE, "production", no_env).unwrap_err();
+
+    assert!(matches!(error, ConfigurationError::ProfileNotFound(name) if name == "production"));
+}
+
+#[test]
+fn test_invalid_file() {
+    let error = from_str(
+        Path::new("infobip.toml"),
+        "[default]\napi_kye = \"typo\"",
+        "default",
+        no_env,
+    )
+    .unwrap_err();
+
+    assert!(matches!(error, ConfigurationError::InvalidFile { .. }));
+}
+
+#[test]
+fn test_invalid_value() {
+    let error = from_str(
+        Path::new("infobip.toml"),
+        "[default]\nbase_url = \"https://some.api.infobip.com\"\napi_key = \"key\"\ntimeout_secs = -1",
+        "default",
+        no_env,
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "invalid value for `timeout_secs` in profile `default`"
+    );
+}
+
+#[test]
+fn test_unsupported_format() {
+    let error = from_str(Path::new("infobip.yaml"), "", "default", no_env).unwrap_err();
+
+    assert!(matches!(error, ConfigurationError::UnsupportedFormat(_)));
+}
+
+#[test]
+fn test_from_file_profile() {
+    let path = std::env::temp_dir().join(format!("infobip-sdk-{}.toml", std::process::id()));
+    std::fs::write(&path, TOML_FILE).unwrap();
+
+    let result = Configuration::from_file_profile(&path, "staging");
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        result.unwrap().base_url(),
+        "https://staging.api.infobip.com"
+    );
+}
+
+#[test]
+fn test_from_file_missing_file() {
+    let error = Configuration::from_file_profile("does-not-exist.toml", "default").unwrap_err();
+
+    assert!(matches!(error, ConfigurationError::Io { .. }));
+}
+
+#[test]
+fn test_client_credentials_profile() {
+    let content = "[default]\nbase_url = \"https://some.api.infobip.com\"\nclient_id = \"some-id\"";
+
+    let error = from_str(Path::new("infobip.toml"), content, "default", no_env).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "missing `client_secret` in profile `default`"
+    );
+
+    let configuration = from_str(Path::new("infobip.toml"), content, "default", |name| {
+        (name == "IB_DEFAULT_CLIENT_SECRET").then(|| "some-secret".to_string())
+    })
+    .unwrap();
+    assert!(configuration.token_provider().is_some());
+    assert!(configuration.api_key().is_none());
+}
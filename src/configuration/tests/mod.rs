@@ -0,0 +1,4 @@
+mod builder;
+mod file;
+mod rate_limit;
+mod secret;
@@ -0,0 +1,116 @@
+use std::time::{Duration, Instant};
+
+use crate::configuration::{RateLimit, RateLimiter};
+
+#[test]
+fn test_rate_limiter_without_limits() {
+    let rate_limiter = RateLimiter::new();
+    let now = Instant::now();
+
+    for _ in 0..100 {
+        assert_eq!(
+            rate_limiter.reserve_at("/sms/2/text/advanced", now),
+            Duration::ZERO
+        );
+    }
+}
+
+#[test]
+fn test_rate_limiter_allows_burst_then_waits() {
+    let rate_limiter = RateLimiter::new().with_account_limit(RateLimit::per_second(2));
+    let now = Instant::now();
+
+    assert_eq!(rate_limiter.reserve_at("/sms/1/logs", now), Duration::ZERO);
+    assert_eq!(rate_limiter.reserve_at("/sms/1/logs", now), Duration::ZERO);
+    assert_eq!(
+        rate_limiter.reserve_at("/sms/1/logs", now),
+        Duration::from_millis(500)
+    );
+    assert_eq!(
+        rate_limiter.reserve_at("/sms/1/logs", now),
+        Duration::from_millis(1000)
+    );
+}
+
+#[test]
+fn test_rate_limiter_refills_over_time() {
+    let rate_limiter = RateLimiter::new().with_account_limit(RateLimit::per_second(2));
+    let now = Instant::now();
+
+    rate_limiter.reserve_at("/sms/1/logs", now);
+    rate_limiter.reserve_at("/sms/1/logs", now);
+
+    let later = now + Duration::from_secs(1);
+    assert_eq!(
+        rate_limiter.reserve_at("/sms/1/logs", later),
+        Duration::ZERO
+    );
+    assert_eq!(
+        rate_limiter.reserve_at("/sms/1/logs", later),
+        Duration::ZERO
+    );
+}
+
+#[test]
+fn test_rate_limiter_endpoint_families() {
+    let rate_limiter = RateLimiter::new()
+        .with_endpoint_limit("/sms", RateLimit::per_second(10))
+        .with_endpoint_limit("/sms/2/text/advanced", RateLimit::per_second(1));
+    let now = Instant::now();
+
+    assert_eq!(
+        rate_limiter.reserve_at("/sms/2/text/advanced", now),
+        Duration::ZERO
+    );
+    assert_eq!(
+        rate_limiter.reserve_at("/sms/2/text/advanced", now),
+        Duration::from_secs(1)
+    );
+    assert_eq!(rate_limiter.reserve_at("/sms/1/logs", now), Duration::ZERO);
+    assert_eq!(
+        rate_limiter.reserve_at("/email/3/send", now),
+        Duration::ZERO
+    );
+}
+
+#[test]
+fn test_rate_limiter_matches_whole_path_segments() {
+    let rate_limiter = RateLimiter::new()
+        .with_endpoint_limit("/sms/1", RateLimit::per_second(1))
+        .with_endpoint_limit("/email/", RateLimit::per_second(1));
+    let now = Instant::now();
+
+    assert_eq!(rate_limiter.reserve_at("/sms/1/logs", now), Duration::ZERO);
+    assert_eq!(
+        rate_limiter.reserve_at("/sms/1", now),
+        Duration::from_secs(1)
+    );
+    assert_eq!(rate_limiter.reserve_at("/sms/10/logs", now), Duration::ZERO);
+    assert_eq!(rate_limiter.reserve_at("/sms/10/logs", now), Duration::ZERO);
+    assert_eq!(
+        rate_limiter.reserve_at("/email/3/send", now),
+        Duration::ZERO
+    );
+    assert_eq!(
+        rate_limiter.reserve_at("/email/3/send", now),
+        Duration::from_secs(1)
+    );
+    assert_eq!(rate_limiter.reserve_at("/emails", now), Duration::ZERO);
+}
+
+#[test]
+fn test_rate_limiter_account_and_endpoint_limits() {
+    let rate_limiter = RateLimiter::new()
+        .with_account_limit(RateLimit::per_second(1))
+        .with_endpoint_limit("/email/3/send", RateLimit::per_second(10));
+    let now = Instant::now();
+
+    assert_eq!(
+        rate_limiter.reserve_at("/email/3/send", now),
+        Duration::ZERO
+    );
+    assert_eq!(
+        rate_limiter.reserve_at("/sms/2/text/advanced", now),
+        Duration::from_secs(1)
+    );
+}
@@ -0,0 +1,78 @@
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, PROXY_AUTHORIZATION};
+
+use crate::configuration::{ApiKey, BasicAuth, Configuration, SecretString};
+
+#[test]
+fn test_secret_string_is_redacted() {
+    let secret = SecretString::from("some-secret");
+
+    assert_eq!(format!("{:?}", secret), "SecretString([REDACTED])");
+    assert_eq!(secret.to_string(), "[REDACTED]");
+    assert_eq!(secret.expose_secret(), "some-secret");
+}
+
+#[test]
+fn test_credentials_are_redacted() {
+    let api_key = ApiKey::new("some-api-key".to_string());
+    let basic_auth = BasicAuth::new("username".to_string(), Some("some-password".to_string()));
+
+    let api_key_debug = format!("{:?}", api_key);
+    let basic_auth_debug = format!("{:?}", basic_auth);
+
+    assert!(!api_key_debug.contains("some-api-key"));
+    assert!(!basic_auth_debug.contains("some-password"));
+    assert!(basic_auth_debug.contains("username"));
+}
+
+#[test]
+fn test_configuration_is_redacted() {
+    let configuration = Configuration::builder("https://some.api.infobip.com")
+        .api_key(ApiKey::new("some-api-key".to_string()))
+        .basic_auth(BasicAuth::new(
+            "username".to_string(),
+            Some("some-password".to_string()),
+        ))
+        .bearer_access_token("some-token")
+        .build()
+        .unwrap();
+
+    let debug = format!("{:?}", configuration);
+
+    assert!(!debug.contains("some-api-key"));
+    assert!(!debug.contains("some-password"));
+    assert!(!debug.contains("some-token"));
+}
+
+#[test]
+fn test_default_credential_headers_are_redacted() {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-api-key", HeaderValue::from_static("some-header-key"));
+    headers.insert("x-tenant-id", HeaderValue::from_static("some-tenant"));
+    let configuration = Configuration::builder("https://some.api.infobip.com")
+        .default_header(AUTHORIZATION, HeaderValue::from_static("App some-api-key"))
+        .default_header(
+            PROXY_AUTHORIZATION,
+            HeaderValue::from_static("Basic some-proxy-credentials"),
+        )
+        .default_headers(headers)
+        .build()
+        .unwrap();
+
+    let debug = format!("{:?}", configuration);
+
+    assert!(!debug.contains("some-api-key"));
+    assert!(!debug.contains("some-proxy-credentials"));
+    assert!(!debug.contains("some-header-key"));
+    assert!(debug.contains("some-tenant"));
+    assert_eq!(
+        configuration.default_headers()[AUTHORIZATION],
+        "App some-api-key"
+    );
+}
+
+#[test]
+fn test_secret_string_from_file_value() {
+    let secret: SecretString = serde_json::from_str(r#""some-secret""#).unwrap();
+
+    assert_eq!(secret.expose_secret(), "some-secret");
+}
@@ -21,7 +21,32 @@
 //! To use the library, you'll need to set up an [Infobip account](https://www.infobip.com/signup).
 //! Then you can use your API Key and custom base URL to call the endpoints. You can use the
 //! `Configuration::from_env_api_key()` method to load the configuration from the environment. To
-//! do that, set the `IB_API_KEY` and `IB_BASE_URL` variables.
+//! do that, set the `IB_API_KEY` and `IB_BASE_URL` variables. Keys, passwords and tokens are held
+//! in `SecretString` values, which are redacted when a configuration or client is printed.
+//!
+//! To use basic authentication or an OAuth2 access token, or to set timeouts, a proxy, custom root
+//! certificates or default headers, use `Configuration::builder()`:
+//!
+//! ```no_run
+//! # use std::time::Duration;
+//! # use infobip_sdk::configuration::Configuration;
+//! let configuration = Configuration::builder("https://some.api.infobip.com")
+//!     .bearer_access_token("some-access-token")
+//!     .connect_timeout(Duration::from_secs(5))
+//!     .timeout(Duration::from_secs(30))
+//!     .proxy(reqwest::Proxy::all("http://proxy.example.com:3128").unwrap())
+//!     .user_agent_suffix("my-app/1.0")
+//!     .build()
+//!     .unwrap();
+//! ```
+//!
+//! For access tokens that expire, set a token provider with `token_provider()`. The built-in
+//! `api::auth::OAuth2TokenProvider` obtains tokens with your client ID and secret, caches them and
+//! refreshes them before they expire.
+//!
+//! To keep the details of several accounts in one TOML or JSON file, with one named profile per
+//! account, use `Configuration::from_file()`. It loads the profile named by the `IB_PROFILE`
+//! variable, and values can be overridden with variables like `IB_<PROFILE>_API_KEY`.
 //!
 //! ## Usage
 //!
@@ -60,12 +85,31 @@
 //! }
 //! ```
 //!
+//! To use several channels, build one `InfobipClient` and get the channel clients from it. They
+//! share the same configuration and connection pool:
+//!
+//! ```no_run
+//! use infobip_sdk::configuration::Configuration;
+//! use infobip_sdk::InfobipClient;
+//!
+//! let client = InfobipClient::with_configuration(Configuration::from_env_api_key().unwrap());
+//!
+//! let sms_client = client.sms();
+//! let email_client = client.email();
+//! let whatsapp_client = client.whatsapp();
+//! ```
+//!
+//! For code that does not run on an async runtime, `BlockingSmsClient`, `BlockingEmailClient` and
+//! `BlockingWhatsAppClient` have the same methods as their asynchronous counterparts, and wait for
+//! each call to finish. They must not be used from within an async runtime.
+//!
 //! ## Examples
 //!
 //! The best way to learn how to use the library is to look at the official
 //! [docs.rs documentation](https://docs.rs/infobip_sdk/), which has simple examples on how to use
 //! every endpoint. You can also look at integration tests under the [tests](./tests) directory,
-//! which work similarly to how you would use them in a real scenario.
+//! which work similarly to how you would use them in a real scenario. They replay API responses
+//! recorded under `tests/cassettes`, and can record them again with `IB_CASSETTE_MODE=record`.
 //!
 //! ## Notes
 //!
@@ -125,6 +169,150 @@
 //! is done automatically when calling an endpoint, or you can call the `.validate()` method of the
 //! model.
 //!
+//! ### Handling Errors
+//! Endpoint functions return an `api::SdkError`. Authentication failures, missing permissions,
+//! missing resources, rate limiting, server errors and timeouts have their own variants, and
+//! methods like `is_retryable()` and `is_auth_error()` help decide what to do with a failed call.
+//! Responses, and errors returned by the server, carry an `api::ResponseMetadata` with the response
+//! headers, the final URL, the time taken and the number of attempts.
+//!
+//! ```no_run
+//! # use infobip_sdk::api::sms::SmsClient;
+//! # use infobip_sdk::configuration::Configuration;
+//! # use infobip_sdk::model::sms::PreviewRequestBody;
+//! # #[tokio::main]
+//! # async fn main() {
+//! # let sms_client = SmsClient::with_configuration(Configuration::from_env_api_key().unwrap());
+//! match sms_client.preview(PreviewRequestBody::new("Hello!")).await {
+//!     Ok(response) => println!("{:?}", response.body),
+//!     Err(error) if error.is_retryable() => println!("try again in {:?}", error.retry_after()),
+//!     Err(error) => println!("giving up: {}", error),
+//! }
+//! # }
+//! ```
+//!
+//! Every request carries an `X-Correlation-Id` header, to match calls with your own logs. The ID is
+//! generated for each call, unless you set one on the configuration or with the
+//! `with_correlation_id()` method of a client. It is available in the response metadata and with
+//! `SdkError::correlation_id()`.
+//!
+//! ### Retrying Failed Requests
+//! By default, every request is sent once. To retry requests that failed with a rate limiting or
+//! gateway error, set a `RetryPolicy` on the configuration. The `Retry-After` header sent by the
+//! server is respected. Use the `without_retries()` method of a client for calls that must not be
+//! repeated.
+//!
+//! ```no_run
+//! # use infobip_sdk::api::sms::SmsClient;
+//! # use infobip_sdk::configuration::{Configuration, RetryPolicy};
+//! let mut configuration = Configuration::from_env_api_key().unwrap();
+//! configuration.set_retry_policy(RetryPolicy::default());
+//!
+//! let sms_client = SmsClient::with_configuration(configuration);
+//! ```
+//!
+//! ### Idempotent Sends
+//! A send that times out may still have been delivered, so sending it again risks a duplicate
+//! message. With `set_idempotent_sends(true)` on the configuration, or the
+//! `with_idempotent_sends()` method of a client, message sends fill in missing bulk and message IDs,
+//! generated once per call. After a failure without a clear outcome, the SMS and Email clients check
+//! the message logs for the bulk ID and only send the request again if it is not there.
+//!
+//! ### Rate Limiting
+//! To stay under the throughput limits of your account, set a `RateLimiter` on the configuration.
+//! Requests that exceed a limit wait until they are allowed, instead of failing. The limiter is
+//! shared by all clients built from the configuration and its clones.
+//!
+//! ```no_run
+//! # use std::sync::Arc;
+//! # use infobip_sdk::api::sms::SmsClient;
+//! # use infobip_sdk::configuration::{Configuration, RateLimit, RateLimiter};
+//! let mut configuration = Configuration::from_env_api_key().unwrap();
+//! configuration.set_rate_limiter(Arc::new(
+//!     RateLimiter::new()
+//!         .with_account_limit(RateLimit::per_second(100))
+//!         .with_endpoint_limit("/sms/2/text/advanced", RateLimit::per_second(20)),
+//! ));
+//!
+//! let sms_client = SmsClient::with_configuration(configuration);
+//! ```
+//!
+//! ### Listing Results
+//! The SMS and Email `logs()` and `delivery_reports()` methods, and `EmailClient::domains()`,
+//! return one page of results. Their `*_stream()` variants request pages or time windows one after
+//! the other and return a `Stream` of results, skipping the ones already returned and stopping
+//! after a given number of them. The blocking clients have `*_iter()` variants that return an
+//! `Iterator` instead.
+//!
+//! ```no_run
+//! # use futures_util::StreamExt;
+//! # use infobip_sdk::api::sms::SmsClient;
+//! # use infobip_sdk::configuration::Configuration;
+//! # use infobip_sdk::model::sms::LogsQueryParameters;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let sms_client = SmsClient::with_configuration(Configuration::from_env_api_key()?);
+//!
+//! let logs = sms_client.logs_stream(LogsQueryParameters::new(), 5000);
+//! futures_util::pin_mut!(logs);
+//!
+//! while let Some(log) = logs.next().await {
+//!     println!("{:?}", log?.message_id);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ### Custom HTTP Transport
+//! Clients send requests with `reqwest` by default. To use a different HTTP stack, or to answer
+//! requests from memory in tests, implement the `api::transport::Transport` trait and build the
+//! client with `with_transport()`.
+//!
+//! ### Custom Endpoints
+//! Every client method sends one of the endpoints in `api::sms::endpoints`, `api::email::endpoints`
+//! or `api::whatsapp::endpoints`. To call an endpoint the SDK does not cover yet, implement the
+//! `api::endpoint::Endpoint` trait for it and pass it to the `execute()` method of a client. It is
+//! sent with the same authentication, retries, middleware and error handling.
+//!
+//! For one-off calls, build an `api::raw::RawRequest` with a method, a path, query parameters and
+//! an optional JSON or multipart body, and pass it to the `raw()` method of any client, including
+//! `InfobipClient`. The response body is deserialized into `serde_json::Value` or a type of your
+//! choice.
+//!
+//! Path parameters, like template names or 2FA PIN IDs, are percent-encoded into their own path
+//! segment, so values with `/`, `?` or `#` are sent as they are. A request whose path parameters do
+//! not match the placeholders of its path fails with `SdkError::InvalidPath` before it is sent.
+//!
+//! ### Testing Without an Account
+//! The `testing` feature adds `api::testing::FakeServer`, a transport that fakes the SMS, 2FA,
+//! Email and WhatsApp endpoints in memory. It assigns message IDs, returns delivery reports and
+//! logs for sent messages, tracks scheduled bulks and 2FA PINs, and can inject errors and latency.
+//! Enable it for dev-dependencies only, and build clients with `FakeServer::client()`.
+//!
+//! ### Async Runtimes
+//! The asynchronous clients do not depend on a particular executor. The default `tokio` feature
+//! adds the blocking clients and reads email attachments from disk without blocking. Without it,
+//! attachments are read on the calling thread. Waits for retries and rate limiting are timed by
+//! the shared timer thread of `futures-timer`, so Tokio runtimes do not need `enable_time()`.
+//!
+//! The default `ReqwestTransport` needs a Tokio reactor. To run the clients on another executor,
+//! e.g. `smol` or `async-std`, enable the `async-io` feature and disable the default features
+//! except for the channels and TLS you use. Clients then send requests with `AsyncIoTransport`,
+//! which drives its connections itself and does not support proxies or custom root certificates.
+//! With both features, `AsyncIoTransport` can be passed to `with_transport()`.
+//!
+//! ### WebAssembly
+//! With the `wasm` feature, the asynchronous clients compile for `wasm32-unknown-unknown`, where
+//! requests are sent with the Fetch API. Blocking clients, proxies, custom root certificates and
+//! reading email attachments from disk are not available there, so attach files with
+//! `attachment_contents` and `inline_image_contents` instead. On this target, transport and token
+//! futures are not `Send`.
+//!
+//! ### Middleware
+//! To add headers, log requests or measure latency around every call, implement the
+//! `api::middleware::Middleware` trait and add it to the configuration with `add_middleware()`.
+//! Middleware runs in the order it was added, for every client built from the configuration.
+//!
 //! ### Optional Features
 //!
 #![doc = document_features::document_features!()]
@@ -135,9 +323,14 @@
 //! [dependencies.infobip_sdk]
 //! version = "0.6.0"
 //! default-features = false
-//! features = ["rustls-tls", "email", "sms", "whatsapp"]
+//! features = ["rustls-tls", "email", "sms", "whatsapp", "tokio"]
 //! ```
 
+#[cfg(all(target_arch = "wasm32", not(feature = "wasm")))]
+compile_error!("building for wasm32 requires the `wasm` feature");
+
 pub mod api;
 pub mod configuration;
 pub mod model;
+
+pub use api::client::InfobipClient;
@@ -47,15 +47,25 @@ pub struct SendRequestBody {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub template_id: Option<i32>,
 
-    /// File attachment.
+    /// File attachment, given as the path of a file on disk. Files can not be read from disk on
+    /// `wasm32`, use `attachment_contents` there.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub attachments: Option<Vec<String>>,
 
     /// Allows for inserting an image file inside the HTML code of the email by using
-    /// `cid:FILENAME` instead of providing an external link to the image.
+    /// `cid:FILENAME` instead of providing an external link to the image. Given as the path of a
+    /// file on disk, like `attachments`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub inline_images: Option<Vec<String>>,
 
+    /// File attachments held in memory, sent after the ones in `attachments`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachment_contents: Option<Vec<Attachment>>,
+
+    /// Inline images held in memory, sent after the ones in `inline_images`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inline_image_contents: Option<Vec<Attachment>>,
+
     /// The real-time Intermediate delivery report that will be sent on your callback server.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub intermediate_report: Option<bool>,
@@ -149,6 +159,26 @@ impl SendRequestBody {
     }
 }
 
+/// File sent with an email from memory.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Attachment {
+    /// Name of the file, used to reference inline images with `cid:FILENAME`.
+    pub file_name: String,
+
+    /// Contents of the file.
+    pub contents: Vec<u8>,
+}
+
+impl Attachment {
+    pub fn new(file_name: &str, contents: impl Into<Vec<u8>>) -> Self {
+        Self {
+            file_name: file_name.into(),
+            contents: contents.into(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SentMessageDetails {
@@ -227,6 +257,7 @@ pub struct SendResponseBody {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
 pub struct BulksQueryParameters {
     #[validate(length(min = 1))]
     pub bulk_id: String,
@@ -549,11 +580,11 @@ pub struct ValidateAddressResponseBody {
 
     /// Reason is provided when validMailbox status is unknown.
     /// 1. INBOX_FULL - The user quota exceeded / The user inbox is full / The user doesn't accept
-    /// any more requests.
+    ///    any more requests.
     /// 2. UNEXPECTED_FAILURE - The mail Server returned a temporary error.
     /// 3. THROTTLED - The mail server is not allowing us momentarily because of too many requests.
     /// 4. TIMED_OUT - The Mail Server took a longer time to respond / there was a delay in the
-    /// network.
+    ///    network.
     /// 5. TEMP_REJECTION - Mail server temporarily rejected.
     /// 6. UNABLE_TO_CONNECT - Unable to connect to the Mail Server.
     #[serde(skip_serializing_if = "Option::is_none")]
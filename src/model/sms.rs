@@ -1,6 +1,7 @@
 //! Models for calling SMS endpoints.
 
 use regex::Regex;
+use serde::Serializer;
 use serde_derive::{Deserialize, Serialize};
 use std::collections::HashMap;
 use validator::Validate;
@@ -794,6 +795,7 @@ pub struct SendResponseBody {
 pub type SendBinaryResponseBody = SendResponseBody;
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
 pub struct ScheduledQueryParameters {
     #[validate(length(min = 1))]
     pub bulk_id: String,
@@ -831,9 +833,9 @@ pub struct LogsQueryParameters {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bulk_id: Option<String>,
 
-    /// Unique message ID for which a log is requested.
+    /// Unique message IDs for which logs are requested.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub message_id: Option<String>,
+    pub message_id: Option<Vec<String>>,
 
     /// Sent message status. Possible values: ACCEPTED, PENDING, UNDELIVERABLE, DELIVERED,
     /// REJECTED, EXPIRED.
@@ -859,9 +861,9 @@ pub struct LogsQueryParameters {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<i32>,
 
-    /// Mobile Country Code.
+    /// Mobile Country Codes.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub mcc: Option<String>,
+    pub mcc: Option<Vec<String>>,
 
     /// Mobile Network Code.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1009,6 +1011,7 @@ pub struct InboundSmsReport {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
 pub struct SendOverQueryParametersQueryParameters {
     /// Username for authentication.
     pub username: String,
@@ -1024,6 +1027,7 @@ pub struct SendOverQueryParametersQueryParameters {
     pub from: Option<String>,
 
     /// List of message recipients.
+    #[serde(serialize_with = "serialize_comma_separated")]
     pub to: Vec<String>,
 
     /// Content of the message being sent.
@@ -1088,6 +1092,15 @@ impl SendOverQueryParametersQueryParameters {
     }
 }
 
+// Serializes the list as one comma-separated value, which is how the API takes recipients in the
+// query string.
+fn serialize_comma_separated<S: Serializer>(
+    values: &[String],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&values.join(","))
+}
+
 pub type SendOverQueryParametersResponseBody = SendResponseBody;
 
 pub type RescheduleQueryParameters = ScheduledQueryParameters;
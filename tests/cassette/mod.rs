@@ -0,0 +1,404 @@
+// Record and replay of HTTP interactions, so that the integration tests can run without an
+// Infobip account.
+//
+// With `IB_CASSETTE_MODE=record`, requests are sent to the API configured by `IB_API_KEY` and
+// `IB_BASE_URL`, and the interactions are saved under `tests/cassettes` when the test ends.
+// Otherwise, requests are answered from the saved cassette. Credentials are never saved, and phone
+// numbers and email addresses are masked, both when recording and before matching a request.
+//
+// A request matches a recorded one with the same method, path, query and body. Multipart bodies
+// are streamed, so only their method, path and query are compared. Each recorded interaction
+// answers one request, with the recorded status, headers and body. A request without an exact
+// match fails. With `IB_CASSETTE_STRICT=0`, it is answered by the next interaction with the same
+// method and path instead, e.g. to replay requests whose bodies hold the current time.
+//
+// The cassettes in the repository were written after the examples of the API reference, as they
+// could not be recorded against an account yet. Recording them again replaces them.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use infobip_sdk::api::transport::{ReqwestTransport, Transport, TransportFuture};
+use infobip_sdk::api::SdkError;
+use infobip_sdk::configuration::{ApiKey, Configuration};
+use lazy_static::lazy_static;
+use regex::Regex;
+use reqwest::header::{
+    HeaderMap, HeaderName, HeaderValue, CONNECTION, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE,
+    SET_COOKIE, TRANSFER_ENCODING,
+};
+use reqwest::{Request, Response, ResponseBuilderExt, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const CASSETTE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cassettes");
+const REPLAY_BASE_URL: &str = "https://cassette.api.infobip.com";
+const REDACTED: &str = "REDACTED";
+const MASKED_EMAIL: &str = "someone@example.com";
+const MASKED_PHONE_NUMBER: &str = "41793026727";
+
+// Response headers that are not saved.
+const SKIPPED_HEADERS: [HeaderName; 5] = [
+    CONNECTION,
+    CONTENT_ENCODING,
+    CONTENT_LENGTH,
+    SET_COOKIE,
+    TRANSFER_ENCODING,
+];
+
+// Keys of JSON bodies, forms and query strings whose values are credentials.
+const SECRET_KEYS: &[&str] = &[
+    "access_token",
+    "apiKey",
+    "client_secret",
+    "password",
+    "token",
+    "username",
+];
+
+// Keys of JSON bodies, forms and query strings whose values hold phone numbers.
+const PHONE_KEYS: &[&str] = &[
+    "destination",
+    "from",
+    "msisdn",
+    "phoneNumber",
+    "sender",
+    "to",
+];
+
+lazy_static! {
+    static ref PHONE_NUMBER: Regex = Regex::new(r"\+?\d{7,15}").unwrap();
+    static ref EMAIL: Regex =
+        Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").unwrap();
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+struct RecordedRequest {
+    method: String,
+    uri: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<Value>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct RecordedResponse {
+    status: u16,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    headers: Vec<(String, String)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<Value>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+/// Transport that records interactions with the API to a cassette file, or replays them.
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    configuration: Configuration,
+    // Sends the requests while recording. Replaying when not set.
+    recorder: Option<ReqwestTransport>,
+    strict: bool,
+    interactions: Mutex<Vec<Interaction>>,
+}
+
+impl Cassette {
+    /// Loads the cassette with the given name, e.g. `sms/send_sms`, to replay it, or starts
+    /// recording it, depending on `IB_CASSETTE_MODE`.
+    pub fn load(name: &str) -> Arc<Cassette> {
+        let path = PathBuf::from(CASSETTE_DIR).join(format!("{}.json", name));
+        let strict = !matches!(
+            env::var("IB_CASSETTE_STRICT").as_deref(),
+            Ok("0") | Ok("false")
+        );
+
+        let cassette = match env::var("IB_CASSETTE_MODE").as_deref() {
+            Ok("record") => {
+                let configuration = Configuration::from_env_api_key()
+                    .expect("failed to build configuration for recording");
+                let recorder = ReqwestTransport::with_configuration(&configuration)
+                    .expect("failed to build transport for recording");
+
+                Cassette {
+                    path,
+                    configuration,
+                    recorder: Some(recorder),
+                    strict,
+                    interactions: Mutex::new(Vec::new()),
+                }
+            }
+            Ok("replay") | Err(_) => {
+                let content = fs::read_to_string(&path).unwrap_or_else(|_| {
+                    panic!(
+                        "no cassette at {}, record it with IB_CASSETTE_MODE=record",
+                        path.display()
+                    )
+                });
+                let interactions = serde_json::from_str(&content).unwrap_or_else(|error| {
+                    panic!("invalid cassette {}: {}", path.display(), error)
+                });
+
+                Cassette {
+                    path,
+                    configuration: Configuration::with_api_key(
+                        REPLAY_BASE_URL.to_string(),
+                        ApiKey::new("replayed-api-key".to_string()),
+                    ),
+                    recorder: None,
+                    strict,
+                    interactions: Mutex::new(interactions),
+                }
+            }
+            Ok(mode) => panic!("unknown IB_CASSETTE_MODE {}", mode),
+        };
+
+        Arc::new(cassette)
+    }
+
+    /// Returns the configuration to build clients with.
+    pub fn configuration(&self) -> Configuration {
+        self.configuration.clone()
+    }
+
+    /// Returns the value of an environment variable while recording, or `replay_value` when
+    /// replaying, so that cassettes can be replayed without it.
+    pub fn var(&self, name: &str, replay_value: &str) -> String {
+        if self.recorder.is_some() {
+            env::var(name).unwrap_or_else(|_| panic!("failed to load {}", name))
+        } else {
+            replay_value.to_string()
+        }
+    }
+
+    // Removes and returns the recorded response for the request.
+    fn replay(&self, request: &RecordedRequest) -> Result<RecordedResponse, SdkError> {
+        let mut interactions = self.interactions.lock().unwrap();
+
+        let path = |uri: &str| uri.split('?').next().unwrap_or_default().to_string();
+        let index = interactions
+            .iter()
+            .position(|interaction| interaction.request == *request)
+            .or_else(|| {
+                if self.strict {
+                    return None;
+                }
+                interactions.iter().position(|interaction| {
+                    interaction.request.method == request.method
+                        && path(&interaction.request.uri) == path(&request.uri)
+                })
+            })
+            .ok_or_else(|| {
+                SdkError::transport(format!(
+                    "no interaction of {} matches {} {} with body {}",
+                    self.path.display(),
+                    request.method,
+                    request.uri,
+                    request.body.as_ref().map_or(Value::Null, Clone::clone),
+                ))
+            })?;
+
+        Ok(interactions.remove(index).response)
+    }
+}
+
+impl Transport for Cassette {
+    fn execute(&self, request: Request) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let recorded_request = record_request(&request);
+            let url = request.url().clone();
+
+            let recorder = match &self.recorder {
+                Some(recorder) => recorder,
+                None => {
+                    let recorded_response = self.replay(&recorded_request)?;
+                    let mut headers = HeaderMap::new();
+                    for (name, value) in &recorded_response.headers {
+                        headers.append(
+                            HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                            HeaderValue::from_str(value).unwrap(),
+                        );
+                    }
+                    let body = match recorded_response.body {
+                        Some(Value::String(text)) => text.into_bytes(),
+                        Some(json) => json.to_string().into_bytes(),
+                        None => Vec::new(),
+                    };
+
+                    return Ok(build_response(
+                        url,
+                        StatusCode::from_u16(recorded_response.status).unwrap(),
+                        headers,
+                        body,
+                    ));
+                }
+            };
+
+            let response = recorder.execute(request).await?;
+            let status = response.status();
+            let headers = response.headers().clone();
+            let body = response.bytes().await?.to_vec();
+
+            self.interactions.lock().unwrap().push(Interaction {
+                request: recorded_request,
+                response: RecordedResponse {
+                    status: status.as_u16(),
+                    headers: record_headers(&headers),
+                    body: scrub_body(&body, content_type(&headers)),
+                },
+            });
+
+            Ok(build_response(url, status, headers, body))
+        })
+    }
+}
+
+impl Drop for Cassette {
+    fn drop(&mut self) {
+        // Interactions of a failed test are not worth replaying.
+        if self.recorder.is_none() || thread::panicking() {
+            return;
+        }
+
+        let interactions = self.interactions.get_mut().unwrap();
+        fs::create_dir_all(self.path.parent().unwrap()).expect("failed to create cassette dir");
+        fs::write(
+            &self.path,
+            serde_json::to_string_pretty(interactions).unwrap() + "\n",
+        )
+        .expect("failed to write cassette");
+    }
+}
+
+fn build_response(url: Url, status: StatusCode, headers: HeaderMap, body: Vec<u8>) -> Response {
+    let mut builder = http::Response::builder().status(status).url(url);
+    *builder.headers_mut().unwrap() = headers;
+
+    Response::from(builder.body(body).unwrap())
+}
+
+fn content_type(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+}
+
+// Returns the response headers worth replaying. Cookies are left out, and so are the headers that
+// describe the encoding of the body, which is saved decoded and formatted again.
+fn record_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter(|(name, _)| !SKIPPED_HEADERS.contains(name))
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect()
+}
+
+fn record_request(request: &Request) -> RecordedRequest {
+    let url = request.url();
+    let mut uri = url.path().to_string();
+    if let Some(query) = url.query() {
+        uri.push('?');
+        uri.push_str(&scrub_form(query));
+    }
+
+    let body = request
+        .body()
+        .and_then(|body| body.as_bytes())
+        .and_then(|body| scrub_body(body, content_type(request.headers())));
+
+    RecordedRequest {
+        method: request.method().to_string(),
+        uri,
+        body,
+    }
+}
+
+// Returns the body as JSON, or as a string if it is not JSON, with sensitive values masked.
+fn scrub_body(body: &[u8], content_type: Option<&str>) -> Option<Value> {
+    if body.is_empty() {
+        return None;
+    }
+
+    if let Ok(mut json) = serde_json::from_slice::<Value>(body) {
+        scrub_json(&mut json, None);
+        return Some(json);
+    }
+
+    let text = String::from_utf8_lossy(body);
+    if content_type.map_or(false, |value| {
+        value.starts_with("application/x-www-form-urlencoded")
+    }) {
+        Some(Value::String(scrub_form(&text)))
+    } else {
+        Some(Value::String(
+            EMAIL.replace_all(&text, MASKED_EMAIL).into_owned(),
+        ))
+    }
+}
+
+fn scrub_json(json: &mut Value, key: Option<&str>) {
+    match json {
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                scrub_json(value, Some(key));
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                scrub_json(value, key);
+            }
+        }
+        Value::String(value) => *value = scrub_value(key.unwrap_or_default(), value),
+        _ => {}
+    }
+}
+
+// Masks the values of a query string or URL encoded form. Pairs are sorted, as clients may send
+// them in any order.
+fn scrub_form(form: &str) -> String {
+    let mut pairs: Vec<(String, String)> = form_pairs(form)
+        .into_iter()
+        .map(|(key, value)| {
+            let value = scrub_value(&key, &value);
+            (key, value)
+        })
+        .collect();
+    pairs.sort();
+
+    let mut url = Url::parse(REPLAY_BASE_URL).unwrap();
+    url.query_pairs_mut().extend_pairs(pairs);
+
+    url.query().unwrap_or_default().to_string()
+}
+
+fn scrub_value(key: &str, value: &str) -> String {
+    if SECRET_KEYS.contains(&key) {
+        return REDACTED.to_string();
+    }
+
+    let value = EMAIL.replace_all(value, MASKED_EMAIL);
+    if PHONE_KEYS.contains(&key) {
+        // Every number is masked the same way, so that the numbers used while replaying do not
+        // need to match the ones that were recorded.
+        PHONE_NUMBER
+            .replace_all(&value, MASKED_PHONE_NUMBER)
+            .into_owned()
+    } else {
+        value.into_owned()
+    }
+}
+
+// Parses the pairs of a query string or URL encoded form.
+fn form_pairs(form: &str) -> Vec<(String, String)> {
+    Url::parse(&format!("{}/?{}", REPLAY_BASE_URL, form))
+        .unwrap()
+        .query_pairs()
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect()
+}
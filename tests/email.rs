@@ -1,77 +1,94 @@
-// These tests need to be run manually, due to server state dependencies. The environment variables
-// IB_API_KEY, IB_BASE_URL, IB_TEST_EMAIL_FROM, and IB_TEST_EMAIL_TO must be set.
+// These tests replay the cassettes recorded under tests/cassettes. Only listing and deleting
+// domains have cassettes. Sending, bulks, scheduled emails, delivery reports, logs, address
+// validation, and adding, fetching, tracking and verifying domains are not covered, so their tests
+// are ignored. To record them against a real account, run the ignored tests with `--ignored`,
+// IB_CASSETTE_MODE=record, and IB_API_KEY, IB_BASE_URL, IB_TEST_EMAIL_FROM, and IB_TEST_EMAIL_TO
+// set. See tests/cassette/mod.rs.
 
 #![cfg(feature = "email")]
 #![cfg(test)]
 
 use chrono::DateTime;
 use reqwest::StatusCode;
-use std::env;
+use std::sync::Arc;
 
 use infobip_sdk::api::email::EmailClient;
-use infobip_sdk::configuration;
 use infobip_sdk::model::email::DkimKeyLength::L1024;
 use infobip_sdk::model::email::*;
 
-fn get_test_email_client() -> EmailClient {
-    EmailClient::with_configuration(
-        configuration::Configuration::from_env_api_key()
-            .expect("failed to build default test client"),
-    )
+use cassette::Cassette;
+
+mod cassette;
+
+fn get_test_email_client(cassette: &Arc<Cassette>) -> EmailClient {
+    EmailClient::with_transport(cassette.configuration(), cassette.clone())
 }
 
-fn get_test_from() -> String {
-    env::var("IB_TEST_EMAIL_FROM").expect("failed to load test email from")
+fn get_test_from(cassette: &Arc<Cassette>) -> String {
+    cassette.var("IB_TEST_EMAIL_FROM", "someone@example.com")
 }
 
-fn get_test_to() -> String {
-    env::var("IB_TEST_EMAIL_TO").expect("failed to load test email to")
+fn get_test_to(cassette: &Arc<Cassette>) -> String {
+    cassette.var("IB_TEST_EMAIL_TO", "someone@example.com")
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn send() {
-    let mut request_body = SendRequestBody::new(&get_test_to());
-    request_body.from = Some(get_test_from());
+    let cassette = Cassette::load("email/send");
+    let mut request_body = SendRequestBody::new(&get_test_to(&cassette));
+    request_body.from = Some(get_test_from(&cassette));
     request_body.subject = Some("Test subject".to_string());
     request_body.text = Some("Hello world!".to_string());
     request_body.attachments = Some(vec!["tests/image.png".to_string()]);
 
-    let response = get_test_email_client().send(request_body).await.unwrap();
+    let response = get_test_email_client(&cassette)
+        .send(request_body)
+        .await
+        .unwrap();
 
     assert_eq!(response.status, StatusCode::OK);
     assert!(!response.body.messages.unwrap().is_empty());
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn send_bulk() {
-    let mut request_body = SendRequestBody::new(&get_test_to());
-    request_body.from = Some(get_test_from());
+    let cassette = Cassette::load("email/send_bulk");
+    let mut request_body = SendRequestBody::new(&get_test_to(&cassette));
+    request_body.from = Some(get_test_from(&cassette));
     request_body.subject = Some("Test subject".to_string());
     request_body.text = Some("Hello world!".to_string());
     request_body.send_at = Some("2022-10-05T16:28:52Z".to_string());
     request_body.bulk_id = Some("test-bulk-id-rust-003".to_string());
 
-    let response = get_test_email_client().send(request_body).await.unwrap();
+    let response = get_test_email_client(&cassette)
+        .send(request_body)
+        .await
+        .unwrap();
 
     assert_eq!(response.status, StatusCode::OK);
     assert!(!response.body.messages.unwrap().is_empty());
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn bulks() {
+    let cassette = Cassette::load("email/bulks");
     let query_params = BulksQueryParameters::new("test-bulk-id-rust-003");
 
-    let response = get_test_email_client().bulks(query_params).await.unwrap();
+    let response = get_test_email_client(&cassette)
+        .bulks(query_params)
+        .await
+        .unwrap();
 
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn reschedule() {
+    let cassette = Cassette::load("email/reschedule");
     let query_params = RescheduleQueryParameters::new("test-bulk-id-rust-003");
     let send_at = "2022-10-05T17:29:52Z";
     let expected_send_at = DateTime::parse_from_rfc3339(send_at)
@@ -80,7 +97,7 @@ async fn reschedule() {
 
     let request_body = RescheduleRequestBody::new(send_at);
 
-    let response = get_test_email_client()
+    let response = get_test_email_client(&cassette)
         .reschedule(query_params, request_body)
         .await
         .unwrap();
@@ -89,12 +106,13 @@ async fn reschedule() {
     assert_eq!(response.body.send_at.unwrap(), expected_send_at as u64);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn scheduled_status() {
+    let cassette = Cassette::load("email/scheduled_status");
     let query_params = ScheduledStatusQueryParameters::new("test-bulk-id-rust-003");
 
-    let response = get_test_email_client()
+    let response = get_test_email_client(&cassette)
         .scheduled_status(query_params)
         .await
         .unwrap();
@@ -102,13 +120,14 @@ async fn scheduled_status() {
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn update_scheduled_status() {
+    let cassette = Cassette::load("email/update_scheduled_status");
     let query_params = UpdateScheduledStatusQueryParameters::new("test-bulk-id-rust-003");
     let request_body = UpdateScheduledStatusRequestBody::new(BulkStatus::Canceled);
 
-    let response = get_test_email_client()
+    let response = get_test_email_client(&cassette)
         .update_scheduled_status(query_params, request_body)
         .await
         .unwrap();
@@ -116,12 +135,13 @@ async fn update_scheduled_status() {
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn delivery_reports() {
+    let cassette = Cassette::load("email/delivery_reports");
     let query_params = DeliveryReportsQueryParameters::default();
 
-    let response = get_test_email_client()
+    let response = get_test_email_client(&cassette)
         .delivery_reports(query_params)
         .await
         .unwrap();
@@ -129,23 +149,28 @@ async fn delivery_reports() {
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn logs() {
+    let cassette = Cassette::load("email/logs");
     let query_params = LogsQueryParameters::default();
 
-    let response = get_test_email_client().logs(query_params).await.unwrap();
+    let response = get_test_email_client(&cassette)
+        .logs(query_params)
+        .await
+        .unwrap();
 
     assert_eq!(response.status, StatusCode::OK);
     println!("{}", serde_json::to_string_pretty(&response.body).unwrap());
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn validate_address() {
+    let cassette = Cassette::load("email/validate_address");
     let query_params = ValidateAddressRequestBody::new("someone@infobip.com");
 
-    let response = get_test_email_client()
+    let response = get_test_email_client(&cassette)
         .validate_address(query_params)
         .await
         .unwrap();
@@ -153,24 +178,28 @@ async fn validate_address() {
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
 #[tokio::test]
 async fn domains() {
+    let cassette = Cassette::load("email/domains");
     let query_params = DomainsQueryParameters::default();
 
-    let response = get_test_email_client().domains(query_params).await.unwrap();
+    let response = get_test_email_client(&cassette)
+        .domains(query_params)
+        .await
+        .unwrap();
 
     assert_eq!(response.status, StatusCode::OK);
     println!("{}", serde_json::to_string_pretty(&response.body).unwrap());
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn add_domain() {
+    let cassette = Cassette::load("email/add_domain");
     let mut request_body = AddDomainRequestBody::new("test-domain-rust-001.com");
     request_body.dkim_key_length = Some(L1024);
 
-    let response = get_test_email_client()
+    let response = get_test_email_client(&cassette)
         .add_domain(request_body)
         .await
         .unwrap();
@@ -179,10 +208,11 @@ async fn add_domain() {
     println!("{}", serde_json::to_string_pretty(&response.body).unwrap());
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn domain() {
-    let response = get_test_email_client()
+    let cassette = Cassette::load("email/domain");
+    let response = get_test_email_client(&cassette)
         .domain("test-domain-rust-001.com")
         .await
         .unwrap();
@@ -191,10 +221,10 @@ async fn domain() {
     println!("{}", serde_json::to_string_pretty(&response.body).unwrap());
 }
 
-#[ignore]
 #[tokio::test]
 async fn delete_domain() {
-    let status = get_test_email_client()
+    let cassette = Cassette::load("email/delete_domain");
+    let status = get_test_email_client(&cassette)
         .delete_domain("test-domain-rust-001.com")
         .await
         .unwrap();
@@ -202,13 +232,14 @@ async fn delete_domain() {
     assert_eq!(status, StatusCode::NO_CONTENT);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn update_tracking() {
+    let cassette = Cassette::load("email/update_tracking");
     let mut request_body = UpdateTrackingRequestBody::new();
     request_body.opens = Some(false);
 
-    let response = get_test_email_client()
+    let response = get_test_email_client(&cassette)
         .update_tracking("test-domain-rust-001.com", request_body)
         .await
         .unwrap();
@@ -217,10 +248,11 @@ async fn update_tracking() {
     println!("{}", serde_json::to_string_pretty(&response.body).unwrap());
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn verify_domain() {
-    let status = get_test_email_client()
+    let cassette = Cassette::load("email/verify_domain");
+    let status = get_test_email_client(&cassette)
         .verify_domain("test-domain-rust-001.com")
         .await
         .unwrap();
@@ -1,64 +1,76 @@
-// These tests need to be run manually, due to server state dependencies. The environment variables
-// IB_API_KEY, IB_BASE_URL, and IB_TEST_DESTINATION_NUMBER must be set.
+// These tests replay the cassettes recorded under tests/cassettes. Only the preview and the sending
+// of a single text message have cassettes. Previews of several texts, bulk, binary and query
+// parameter sending, delivery reports, logs, inbound reports, scheduled messages and 2FA are not
+// covered, so their tests are ignored. To record them against a real account, run the ignored tests
+// with `--ignored`, IB_CASSETTE_MODE=record, and IB_API_KEY, IB_BASE_URL, and
+// IB_TEST_DESTINATION_NUMBER set. See tests/cassette/mod.rs.
 
 #![cfg(feature = "sms")]
 #![cfg(test)]
 
-use std::env;
+use std::sync::Arc;
 
 use reqwest::StatusCode;
 
-use infobip_sdk::api::sms::{BlockingSmsClient, SmsClient};
-use infobip_sdk::configuration;
+use infobip_sdk::api::sms::SmsClient;
 use infobip_sdk::model::sms::*;
 
+use cassette::Cassette;
+
+#[cfg(tokio)]
+use infobip_sdk::api::sms::BlockingSmsClient;
+
+mod cassette;
+
 const DUMMY_TEXT: &str = "Dummy text for tests. Some special chars: áéíø";
 const DUMMY_BULK_ID: &str = "dummy-rust-sdk-bulk-id-3";
 
-fn test_sms_client() -> SmsClient {
-    SmsClient::with_configuration(
-        configuration::Configuration::from_env_api_key()
-            .expect("failed to build default test SMS client"),
-    )
+fn test_sms_client(cassette: &Arc<Cassette>) -> SmsClient {
+    SmsClient::with_transport(cassette.configuration(), cassette.clone())
 }
 
-fn test_blocking_sms_client() -> BlockingSmsClient {
-    BlockingSmsClient::with_configuration(
-        configuration::Configuration::from_env_api_key()
-            .expect("failed to build default test blocking SMS client"),
-    )
+#[cfg(tokio)]
+fn test_blocking_sms_client(cassette: &Arc<Cassette>) -> BlockingSmsClient {
+    BlockingSmsClient::with_transport(cassette.configuration(), cassette.clone())
 }
 
-fn test_destination_number() -> String {
-    env::var("IB_TEST_DESTINATION_NUMBER").expect("failed to load test destination number")
+fn test_destination_number(cassette: &Arc<Cassette>) -> String {
+    cassette.var("IB_TEST_DESTINATION_NUMBER", "41793026727")
 }
 
-#[ignore]
 #[tokio::test]
 async fn preview_sms() {
+    let cassette = Cassette::load("sms/preview_sms");
     let request_body = PreviewRequestBody::new(DUMMY_TEXT);
 
-    let response = test_sms_client().preview(request_body).await.unwrap();
+    let response = test_sms_client(&cassette)
+        .preview(request_body)
+        .await
+        .unwrap();
 
     assert_eq!(response.status, StatusCode::OK);
     assert!(!response.body.previews.unwrap().is_empty());
 }
 
-#[ignore]
+#[cfg(tokio)]
 #[test]
 fn preview_sms_blocking() {
+    let cassette = Cassette::load("sms/preview_sms_blocking");
     let request_body = PreviewRequestBody::new(DUMMY_TEXT);
 
-    let response = test_blocking_sms_client().preview(request_body).unwrap();
+    let response = test_blocking_sms_client(&cassette)
+        .preview(request_body)
+        .unwrap();
 
     assert_eq!(response.status, StatusCode::OK);
     assert!(!response.body.previews.unwrap().is_empty());
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn preview_sms_multiple() {
-    let sms_client = test_sms_client();
+    let cassette = Cassette::load("sms/preview_sms_multiple");
+    let sms_client = test_sms_client(&cassette);
 
     let request_body1 = PreviewRequestBody::new(DUMMY_TEXT);
     let request_body2 = PreviewRequestBody::new(DUMMY_TEXT);
@@ -110,10 +122,12 @@ async fn preview_sms_multiple() {
         .is_empty());
 }
 
-#[ignore]
+#[cfg(tokio)]
+#[ignore = "no cassette recorded"]
 #[test]
 fn preview_sms_multiple_blocking() {
-    let sms_client = test_blocking_sms_client();
+    let cassette = Cassette::load("sms/preview_sms_multiple_blocking");
+    let sms_client = test_blocking_sms_client(&cassette);
 
     let request_body1 = PreviewRequestBody::new(DUMMY_TEXT);
     let request_body2 = PreviewRequestBody::new(DUMMY_TEXT);
@@ -135,13 +149,14 @@ fn preview_sms_multiple_blocking() {
     assert!(!response4.body.previews.unwrap().is_empty());
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn sms_delivery_reports() {
+    let cassette = Cassette::load("sms/sms_delivery_reports");
     let mut parameters = DeliveryReportsQueryParameters::new();
     parameters.limit = Some(10);
 
-    let response = test_sms_client()
+    let response = test_sms_client(&cassette)
         .delivery_reports(parameters)
         .await
         .unwrap();
@@ -149,65 +164,76 @@ async fn sms_delivery_reports() {
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
 #[tokio::test]
 async fn send_sms() {
-    let mut message = Message::new(vec![Destination::new(&test_destination_number())]);
+    let cassette = Cassette::load("sms/send_sms");
+    let mut message = Message::new(vec![Destination::new(&test_destination_number(&cassette))]);
     message.text = Some(DUMMY_TEXT.into());
 
     let request_body = SendRequestBody::new(vec![message]);
 
-    let response = test_sms_client().send(request_body).await.unwrap();
+    let response = test_sms_client(&cassette).send(request_body).await.unwrap();
 
     assert_eq!(response.status, StatusCode::OK);
     assert_eq!(response.body.messages.unwrap().len(), 1usize);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn send_bulk_sms() {
-    let mut message = Message::new(vec![Destination::new(&test_destination_number())]);
+    let cassette = Cassette::load("sms/send_bulk_sms");
+    let mut message = Message::new(vec![Destination::new(&test_destination_number(&cassette))]);
     message.text = Some(DUMMY_TEXT.into());
     message.send_at = Some("2022-10-10T00:00:00Z".to_string());
 
     let mut request_body = SendRequestBody::new(vec![message]);
     request_body.bulk_id = Some(DUMMY_BULK_ID.into());
 
-    let response = test_sms_client().send(request_body).await.unwrap();
+    let response = test_sms_client(&cassette).send(request_body).await.unwrap();
 
     assert_eq!(response.status, StatusCode::OK);
     assert_eq!(response.body.messages.unwrap().len(), 1usize);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn send_binary_sms() {
-    let mut message = BinaryMessage::new(vec![Destination::new(&test_destination_number())]);
+    let cassette = Cassette::load("sms/send_binary_sms");
+    let mut message =
+        BinaryMessage::new(vec![Destination::new(&test_destination_number(&cassette))]);
     message.binary = Some(BinaryData::new("0f c2 4a bf 34 13 ba"));
 
     let mut request_body = SendBinaryRequestBody::new(vec![message]);
     request_body.bulk_id = Some("test-bulk-id-5319".to_string());
 
-    let response = test_sms_client().send_binary(request_body).await.unwrap();
+    let response = test_sms_client(&cassette)
+        .send_binary(request_body)
+        .await
+        .unwrap();
 
     assert_eq!(response.status, StatusCode::OK);
     assert_eq!(response.body.messages.unwrap().len(), 1usize);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn logs() {
+    let cassette = Cassette::load("sms/logs");
     let query_parameters = LogsQueryParameters::new();
-    let response = test_sms_client().logs(query_parameters).await.unwrap();
+    let response = test_sms_client(&cassette)
+        .logs(query_parameters)
+        .await
+        .unwrap();
 
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn inbound_reports() {
+    let cassette = Cassette::load("sms/inbound_reports");
     let query_parameters = InboundReportsQueryParameters::new();
-    let response = test_sms_client()
+    let response = test_sms_client(&cassette)
         .inbound_reports(query_parameters)
         .await
         .unwrap();
@@ -215,14 +241,15 @@ async fn inbound_reports() {
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn send_over_query_parameters() {
+    let cassette = Cassette::load("sms/send_over_query_parameters");
     let destinations = vec!["31612345678".to_string(), "31698765432".to_string()];
     let query_parameters =
         SendOverQueryParametersQueryParameters::new("username", "password", destinations);
 
-    let response = test_sms_client()
+    let response = test_sms_client(&cassette)
         .send_over_query_parameters(query_parameters)
         .await
         .unwrap();
@@ -230,12 +257,13 @@ async fn send_over_query_parameters() {
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn scheduled() {
+    let cassette = Cassette::load("sms/scheduled");
     let query_parameters = ScheduledStatusQueryParameters::new(DUMMY_BULK_ID);
 
-    let response = test_sms_client()
+    let response = test_sms_client(&cassette)
         .scheduled_status(query_parameters)
         .await
         .unwrap();
@@ -243,12 +271,13 @@ async fn scheduled() {
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn scheduled_status() {
+    let cassette = Cassette::load("sms/scheduled_status");
     let query_parameters = ScheduledStatusQueryParameters::new(DUMMY_BULK_ID);
 
-    let response = test_sms_client()
+    let response = test_sms_client(&cassette)
         .scheduled_status(query_parameters)
         .await
         .unwrap();
@@ -256,13 +285,14 @@ async fn scheduled_status() {
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn reschedule() {
+    let cassette = Cassette::load("sms/reschedule");
     let query_parameters = RescheduleQueryParameters::new(DUMMY_BULK_ID);
     let request_body = RescheduleRequestBody::new("2022-10-02T00:00:00");
 
-    let response = test_sms_client()
+    let response = test_sms_client(&cassette)
         .reschedule(query_parameters, request_body)
         .await
         .unwrap();
@@ -270,34 +300,37 @@ async fn reschedule() {
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn update_scheduled_status() {
+    let cassette = Cassette::load("sms/update_scheduled_status");
     let query_parameters = UpdateScheduledStatusQueryParameters::new(DUMMY_BULK_ID);
     let request_body = UpdateScheduledStatusRequestBody::new(ScheduledStatus::Canceled);
 
-    let response = test_sms_client()
+    let response = test_sms_client(&cassette)
         .update_scheduled_status(query_parameters, request_body)
         .await
         .unwrap();
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn tfa_applications() {
-    let response = test_sms_client().tfa_applications().await.unwrap();
+    let cassette = Cassette::load("sms/tfa_applications");
+    let response = test_sms_client(&cassette).tfa_applications().await.unwrap();
 
     println!("{:?}", response.body);
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn create_tfa_application() {
+    let cassette = Cassette::load("sms/create_tfa_application");
     let request_body = CreateTfaApplicationRequestBody::new("rust-application");
 
-    let response = test_sms_client()
+    let response = test_sms_client(&cassette)
         .create_tfa_application(request_body)
         .await
         .unwrap();
@@ -306,10 +339,11 @@ async fn create_tfa_application() {
     assert_eq!(response.status, StatusCode::CREATED);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn tfa_application() {
-    let response = test_sms_client()
+    let cassette = Cassette::load("sms/tfa_application");
+    let response = test_sms_client(&cassette)
         .tfa_application("02CC3CAAFD733136AA15DFAC720A0C42")
         .await
         .unwrap();
@@ -318,9 +352,10 @@ async fn tfa_application() {
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn update_tfa_application() {
+    let cassette = Cassette::load("sms/update_tfa_application");
     let configuration = TfaApplicationConfiguration {
         allow_multiple_pin_verifications: Some(true),
         pin_attempts: None,
@@ -332,7 +367,7 @@ async fn update_tfa_application() {
     let mut request_body = UpdateTfaApplicationRequestBody::new("rust-application-2");
     request_body.configuration = Some(configuration);
 
-    let response = test_sms_client()
+    let response = test_sms_client(&cassette)
         .update_tfa_application("02CC3CAAFD733136AA15DFAC720A0C42", request_body)
         .await
         .unwrap();
@@ -341,10 +376,11 @@ async fn update_tfa_application() {
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn tfa_message_templates() {
-    let response = test_sms_client()
+    let cassette = Cassette::load("sms/tfa_message_templates");
+    let response = test_sms_client(&cassette)
         .tfa_message_templates("02CC3CAAFD733136AA15DFAC720A0C42")
         .await
         .unwrap();
@@ -353,13 +389,14 @@ async fn tfa_message_templates() {
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn create_tfa_message_template() {
+    let cassette = Cassette::load("sms/create_tfa_message_template");
     let request_body =
         CreateTfaMessageTemplateRequestBody::new("Your Rust PIN 2 is {{pin}}", PinType::Numeric, 6);
 
-    let response = test_sms_client()
+    let response = test_sms_client(&cassette)
         .create_tfa_message_template("02CC3CAAFD733136AA15DFAC720A0C42", request_body)
         .await
         .unwrap();
@@ -368,10 +405,11 @@ async fn create_tfa_message_template() {
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn tfa_message_template() {
-    let response = test_sms_client()
+    let cassette = Cassette::load("sms/tfa_message_template");
+    let response = test_sms_client(&cassette)
         .tfa_message_template(
             "02CC3CAAFD733136AA15DFAC720A0C42",
             "44A45DA3067F882BB4D87D6A48F9681E",
@@ -383,13 +421,14 @@ async fn tfa_message_template() {
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn update_tfa_message_template() {
+    let cassette = Cassette::load("sms/update_tfa_message_template");
     let request_body =
         UpdateTfaMessageTemplateRequestBody::new("Your Rust PIN 3 is {{pin}}", PinType::Numeric, 6);
 
-    let response = test_sms_client()
+    let response = test_sms_client(&cassette)
         .update_tfa_message_template(
             "02CC3CAAFD733136AA15DFAC720A0C42",
             "44A45DA3067F882BB4D87D6A48F9681E",
@@ -402,9 +441,10 @@ async fn update_tfa_message_template() {
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn send_pin_over_sms() {
+    let cassette = Cassette::load("sms/send_pin_over_sms");
     let query_parameters = SendPinOverSmsQueryParameters::new();
     let request_body = SendPinOverSmsRequestBody::new(
         "02CC3CAAFD733136AA15DFAC720A0C42",
@@ -412,7 +452,7 @@ async fn send_pin_over_sms() {
         "555555555555",
     );
 
-    let response = test_sms_client()
+    let response = test_sms_client(&cassette)
         .send_pin_over_sms(query_parameters, request_body)
         .await
         .unwrap();
@@ -421,12 +461,13 @@ async fn send_pin_over_sms() {
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn resend_pin_over_sms() {
+    let cassette = Cassette::load("sms/resend_pin_over_sms");
     let request_body = ResendPinOverSmsRequestBody::default();
 
-    let response = test_sms_client()
+    let response = test_sms_client(&cassette)
         .resend_pin_over_sms("AAA30929B83F2ED86CC34781BCB7A546", request_body)
         .await
         .unwrap();
@@ -435,16 +476,17 @@ async fn resend_pin_over_sms() {
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn send_pin_over_voice() {
+    let cassette = Cassette::load("sms/send_pin_over_voice");
     let request_body = SendPinOverVoiceRequestBody::new(
         "02CC3CAAFD733136AA15DFAC720A0C42",
         "44A45DA3067F882BB4D87D6A48F9681E",
         "555555555555",
     );
 
-    let response = test_sms_client()
+    let response = test_sms_client(&cassette)
         .send_pin_over_voice(request_body)
         .await
         .unwrap();
@@ -453,12 +495,13 @@ async fn send_pin_over_voice() {
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn resend_pin_over_voice() {
+    let cassette = Cassette::load("sms/resend_pin_over_voice");
     let request_body = ResendPinOverVoiceRequestBody::default();
 
-    let response = test_sms_client()
+    let response = test_sms_client(&cassette)
         .resend_pin_over_voice("AAA30929B83F2ED86CC34781BCB7A546", request_body)
         .await
         .unwrap();
@@ -467,12 +510,13 @@ async fn resend_pin_over_voice() {
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn verify_phone_number() {
+    let cassette = Cassette::load("sms/verify_phone_number");
     let request_body = VerifyPhoneNumberRequestBody::new("123456");
 
-    let response = test_sms_client()
+    let response = test_sms_client(&cassette)
         .verify_phone_number("AAA30929B83F2ED86CC34781BCB7A546", request_body)
         .await
         .unwrap();
@@ -481,11 +525,12 @@ async fn verify_phone_number() {
     assert_eq!(response.status, StatusCode::OK);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn tfa_verification_status() {
+    let cassette = Cassette::load("sms/tfa_verification_status");
     let query_parameters = TfaVerificationStatusQueryParameters::new("555555555555");
-    let response = test_sms_client()
+    let response = test_sms_client(&cassette)
         .tfa_verification_status("02CC3CAAFD733136AA15DFAC720A0C42", query_parameters)
         .await
         .unwrap();
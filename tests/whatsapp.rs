@@ -1,168 +1,203 @@
-// These tests need to be run manually, due to server state dependencies. The environment variables
-// IB_API_KEY, IB_BASE_URL, IB_TEST_SENDER, and IB_TEST_DESTINATION_NUMBER must be set.
+// These tests replay the cassettes recorded under tests/cassettes. Only sending text messages has
+// a cassette. Media, location, contact, interactive and template messages, and the management of
+// templates are not covered, so their tests are ignored. To record them against a real account,
+// run the ignored tests with `--ignored`, IB_CASSETTE_MODE=record, and IB_API_KEY, IB_BASE_URL,
+// IB_TEST_SENDER, and IB_TEST_DESTINATION_NUMBER set. See tests/cassette/mod.rs.
 
 #![cfg(feature = "whatsapp")]
 #![cfg(test)]
 
-use std::env;
+use std::sync::Arc;
 
 use reqwest::StatusCode;
 
 use infobip_sdk::api::whatsapp::WhatsAppClient;
-use infobip_sdk::configuration;
 use infobip_sdk::model::whatsapp::*;
 
+use cassette::Cassette;
+
+mod cassette;
+
 const DUMMY_TEXT: &str = "Dummy text for tests. Some special chars: áéíø";
 
-fn test_wa_client() -> WhatsAppClient {
-    WhatsAppClient::with_configuration(
-        configuration::Configuration::from_env_api_key()
-            .expect("failed to build default test client"),
-    )
+fn test_wa_client(cassette: &Arc<Cassette>) -> WhatsAppClient {
+    WhatsAppClient::with_transport(cassette.configuration(), cassette.clone())
 }
 
-fn test_destination_number() -> String {
-    env::var("IB_TEST_DESTINATION_NUMBER").expect("failed to load test destination number")
+fn test_destination_number(cassette: &Arc<Cassette>) -> String {
+    cassette.var("IB_TEST_DESTINATION_NUMBER", "41793026727")
 }
 
-fn test_sender_number() -> String {
-    env::var("IB_TEST_SENDER").expect("failed to load test sender number")
+fn test_sender_number(cassette: &Arc<Cassette>) -> String {
+    cassette.var("IB_TEST_SENDER", "447860099299")
 }
 
-#[ignore]
 #[tokio::test]
 async fn send_text() {
+    let cassette = Cassette::load("whatsapp/send_text");
     let request_body = SendTextRequestBody::new(
-        &test_sender_number(),
-        &test_destination_number(),
+        &test_sender_number(&cassette),
+        &test_destination_number(&cassette),
         TextContent::new(DUMMY_TEXT),
     );
 
-    let response = test_wa_client().send_text(request_body).await.unwrap();
+    let response = test_wa_client(&cassette)
+        .send_text(request_body)
+        .await
+        .unwrap();
 
     assert_eq!(response.status, StatusCode::OK);
     assert!(!response.body.message_id.unwrap().is_empty());
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn send_document() {
+    let cassette = Cassette::load("whatsapp/send_document");
     let request_body = SendDocumentRequestBody::new(
-        &test_sender_number(),
-        &test_destination_number(),
+        &test_sender_number(&cassette),
+        &test_destination_number(&cassette),
         DocumentContent::new(
             "https://perso.limsi.fr/pointal/_media/python:cours:mementopython3-english.pdf",
         ),
     );
 
-    let response = test_wa_client().send_document(request_body).await.unwrap();
+    let response = test_wa_client(&cassette)
+        .send_document(request_body)
+        .await
+        .unwrap();
 
     assert_eq!(response.status, StatusCode::OK);
     assert!(!response.body.message_id.unwrap().is_empty());
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn send_image() {
+    let cassette = Cassette::load("whatsapp/send_image");
     let request_body = SendImageRequestBody::new(
-        &test_sender_number(),
-        &test_destination_number(),
+        &test_sender_number(&cassette),
+        &test_destination_number(&cassette),
         ImageContent::new("https://rustacean.net/assets/rustacean-flat-happy.png"),
     );
 
-    let response = test_wa_client().send_image(request_body).await.unwrap();
+    let response = test_wa_client(&cassette)
+        .send_image(request_body)
+        .await
+        .unwrap();
 
     assert_eq!(response.status, StatusCode::OK);
     assert!(!response.body.message_id.unwrap().is_empty());
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn send_audio() {
+    let cassette = Cassette::load("whatsapp/send_audio");
     let request_body = SendAudioRequestBody::new(
-        &test_sender_number(),
-        &test_destination_number(),
+        &test_sender_number(&cassette),
+        &test_destination_number(&cassette),
         AudioContent::new("https://download.samplelib.com/mp3/sample-3s.mp3"),
     );
 
-    let response = test_wa_client().send_audio(request_body).await.unwrap();
+    let response = test_wa_client(&cassette)
+        .send_audio(request_body)
+        .await
+        .unwrap();
 
     assert_eq!(response.status, StatusCode::OK);
     assert!(!response.body.message_id.unwrap().is_empty());
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn send_video() {
+    let cassette = Cassette::load("whatsapp/send_video");
     let request_body = SendVideoRequestBody::new(
-        &test_sender_number(),
-        &test_destination_number(),
+        &test_sender_number(&cassette),
+        &test_destination_number(&cassette),
         VideoContent::new("https://download.samplelib.com/mp4/sample-5s.mp4"),
     );
 
-    let response = test_wa_client().send_video(request_body).await.unwrap();
+    let response = test_wa_client(&cassette)
+        .send_video(request_body)
+        .await
+        .unwrap();
 
     assert_eq!(response.status, StatusCode::OK);
     assert!(!response.body.message_id.unwrap().is_empty());
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn send_sticker() {
+    let cassette = Cassette::load("whatsapp/send_sticker");
     let request_body = SendStickerRequestBody::new(
-        &test_sender_number(),
-        &test_destination_number(),
+        &test_sender_number(&cassette),
+        &test_destination_number(&cassette),
         StickerContent::new("https://www.gstatic.com/webp/gallery/1.webp"),
     );
 
-    let response = test_wa_client().send_sticker(request_body).await.unwrap();
+    let response = test_wa_client(&cassette)
+        .send_sticker(request_body)
+        .await
+        .unwrap();
     assert_eq!(response.status, StatusCode::OK);
     assert!(!response.body.message_id.unwrap().is_empty());
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn send_location() {
+    let cassette = Cassette::load("whatsapp/send_location");
     let request_body = SendLocationRequestBody::new(
-        &test_sender_number(),
-        &test_destination_number(),
+        &test_sender_number(&cassette),
+        &test_destination_number(&cassette),
         LocationContent::new(0.0, 0.0),
     );
 
-    let response = test_wa_client().send_location(request_body).await.unwrap();
+    let response = test_wa_client(&cassette)
+        .send_location(request_body)
+        .await
+        .unwrap();
     assert_eq!(response.status, StatusCode::OK);
     assert!(!response.body.message_id.unwrap().is_empty());
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn send_contact() {
+    let cassette = Cassette::load("whatsapp/send_contact");
     let contact = Contact::new(ContactName::new("John", "John Doe"));
     let request_body = SendContactRequestBody::new(
-        &test_sender_number(),
-        &test_destination_number(),
+        &test_sender_number(&cassette),
+        &test_destination_number(&cassette),
         ContactContent::new(vec![contact]),
     );
 
-    let response = test_wa_client().send_contact(request_body).await.unwrap();
+    let response = test_wa_client(&cassette)
+        .send_contact(request_body)
+        .await
+        .unwrap();
     assert_eq!(response.status, StatusCode::OK);
     assert!(!response.body.message_id.unwrap().is_empty());
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn send_interactive_buttons() {
+    let cassette = Cassette::load("whatsapp/send_interactive_buttons");
     let button = InteractiveButton::new_reply_button("1", "Button Title");
     let request_body = SendInteractiveButtonsRequestBody::new(
-        &test_sender_number(),
-        &test_destination_number(),
+        &test_sender_number(&cassette),
+        &test_destination_number(&cassette),
         InteractiveButtonsContent::new(
             InteractiveBody::new("Hello"),
             InteractiveButtonsAction::new(vec![button]),
         ),
     );
 
-    let response = test_wa_client()
+    let response = test_wa_client(&cassette)
         .send_interactive_buttons(request_body)
         .await
         .unwrap();
@@ -171,23 +206,24 @@ async fn send_interactive_buttons() {
     assert!(!response.body.message_id.unwrap().is_empty());
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn send_interactive_list() {
+    let cassette = Cassette::load("whatsapp/send_interactive_list");
     let row = InteractiveRow::new("1", "Row Title");
 
     let section = InteractiveListSection::new(vec![row]);
 
     let request_body = SendInteractiveListRequestBody::new(
-        &test_sender_number(),
-        &test_destination_number(),
+        &test_sender_number(&cassette),
+        &test_destination_number(&cassette),
         InteractiveListContent::new(
             InteractiveBody::new("Hello"),
             InteractiveListAction::new("Section Title", vec![section]),
         ),
     );
 
-    let response = test_wa_client()
+    let response = test_wa_client(&cassette)
         .send_interactive_list(request_body)
         .await
         .unwrap();
@@ -196,16 +232,17 @@ async fn send_interactive_list() {
     assert!(!response.body.message_id.unwrap().is_empty());
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn send_interactive_product() {
+    let cassette = Cassette::load("whatsapp/send_interactive_product");
     let request_body = SendInteractiveProductRequestBody::new(
-        &test_sender_number(),
-        &test_destination_number(),
+        &test_sender_number(&cassette),
+        &test_destination_number(&cassette),
         InteractiveProductContent::new(InteractiveProductAction::new("1", "2")),
     );
 
-    let response = test_wa_client()
+    let response = test_wa_client(&cassette)
         .send_interactive_product(request_body)
         .await
         .unwrap();
@@ -214,13 +251,14 @@ async fn send_interactive_product() {
     assert!(!response.body.message_id.unwrap().is_empty());
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn send_interactive_multiproduct() {
+    let cassette = Cassette::load("whatsapp/send_interactive_multiproduct");
     let section = InteractiveMultiproductSection::new(vec!["1".to_string(), "2".to_string()]);
     let request_body = SendInteractiveMultiproductRequestBody::new(
-        &test_sender_number(),
-        &test_destination_number(),
+        &test_sender_number(&cassette),
+        &test_destination_number(&cassette),
         InteractiveMultiproductContent::new(
             InteractiveMultiproductHeader::new_text_header("Header text"),
             InteractiveBody::new("Body text"),
@@ -228,7 +266,7 @@ async fn send_interactive_multiproduct() {
         ),
     );
 
-    let response = test_wa_client()
+    let response = test_wa_client(&cassette)
         .send_interactive_multiproduct(request_body)
         .await
         .unwrap();
@@ -237,9 +275,10 @@ async fn send_interactive_multiproduct() {
     assert!(!response.body.message_id.unwrap().is_empty());
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn create_template() {
+    let cassette = Cassette::load("whatsapp/create_template");
     let structure = TemplateStructure::new(TemplateBody::new("hello"));
     let request_body = CreateTemplateRequestBody::new(
         "rust_sdk_test_template",
@@ -248,8 +287,8 @@ async fn create_template() {
         structure,
     );
 
-    let response = test_wa_client()
-        .create_template(&test_sender_number(), request_body)
+    let response = test_wa_client(&cassette)
+        .create_template(&test_sender_number(&cassette), request_body)
         .await
         .unwrap();
 
@@ -257,11 +296,12 @@ async fn create_template() {
     assert!(!response.body.id.unwrap().is_empty());
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn templates() {
-    let response = test_wa_client()
-        .templates(&test_sender_number())
+    let cassette = Cassette::load("whatsapp/templates");
+    let response = test_wa_client(&cassette)
+        .templates(&test_sender_number(&cassette))
         .await
         .unwrap();
 
@@ -269,34 +309,39 @@ async fn templates() {
     assert!(!response.body.templates.unwrap().is_empty());
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn delete_template() {
-    let status = test_wa_client()
-        .delete_template(&test_sender_number(), "rust_sdk_test_template")
+    let cassette = Cassette::load("whatsapp/delete_template");
+    let status = test_wa_client(&cassette)
+        .delete_template(&test_sender_number(&cassette), "rust_sdk_test_template")
         .await
         .unwrap();
 
     assert_eq!(status, StatusCode::NO_CONTENT);
 }
 
-#[ignore]
+#[ignore = "no cassette recorded"]
 #[tokio::test]
 async fn send_template() {
+    let cassette = Cassette::load("whatsapp/send_template");
     let template_content = TemplateContent::new(
         "rust_sdk_test_template",
         TemplateData::new(TemplateBodyContent::new(vec!["hello".to_string()])),
         TemplateLanguage::EnUs,
     );
     let message = FailoverMessage::new(
-        &test_sender_number(),
-        &test_destination_number(),
+        &test_sender_number(&cassette),
+        &test_destination_number(&cassette),
         template_content,
     );
 
     let request_body = SendTemplateRequestBody::new(vec![message]);
 
-    let response = test_wa_client().send_template(request_body).await.unwrap();
+    let response = test_wa_client(&cassette)
+        .send_template(request_body)
+        .await
+        .unwrap();
 
     assert_eq!(response.status, StatusCode::OK);
     assert!(!response.body.messages.unwrap().is_empty());